@@ -1,30 +1,31 @@
+use std::rc::Rc;
+
 use sokol_bindings::{
-    cstr,
     sapp::{self, IconDesc},
-    setup_default_context,
-    sg::{self, begin_default_pass, end_pass, commit, query_backend, Action, Bindings, Color, ColorAttachmentAction, PassAction, Pipeline, PipelineDesc},
+    sg::{self, apply_scissor_rect, apply_viewport, begin_default_pass, end_pass, commit, query_backend, Action, Bindings, Color, ColorAttachmentAction, OwnedBuffer, OwnedImage, OwnedPipeline, OwnedShader, PassAction, PipelineBuilder, Rect},
     Int,
 };
 use math::{
+    Scale,
     angle::{Angle, Radians, TAU},
     mat4::Mat4,
+    quat::Quat,
     vec3::{Vec3, vec3},
 };
 use sokol_extras::{
     debug::axes,
     images::white,
-    shaders::{self, textured_lit},
+    mesh::{MeshBuilder, Submesh},
+    scene::{
+        transform::{self, Transform},
+        Context, GameObject, GameObjectId, Material, Mesh, MeshRenderer, Scene, Script,
+    },
+    shaders::textured_lit,
+    skybox,
 };
 
-mod skybox;
 mod decoded;
 
-#[derive(Default)]
-struct ModelState {
-    bind: Bindings,
-    pipe: Pipeline,
-}
-
 type Radius = f32;
 
 #[derive(Debug, Default)]
@@ -45,171 +46,270 @@ impl Eye {
     }
 }
 
-#[derive(Default)]
-struct State {
-    skybox: skybox::State,
-    model: ModelState,
-    axes: axes::State,
-    eye: Eye,
-    light_dir: Vec3,
-    center: Vec3,
-    time: f32,
+// Translation constant. An observed but currently not well understood scaling
+// that is needed to make the numbers from the tutorial produce the expected
+// translation amounts.
+const T_K: f32 = 1./4.;
+
+/// The renderers for the different parts of the clock.
+struct ClockRenderers {
+    face: MeshRenderer,
+    indicator: MeshRenderer,
+    arm: MeshRenderer,
+    seconds_arm: MeshRenderer,
 }
 
-// Near/Far clipping plane distances along z.
-const NEAR: f32 = 0.01;
-// An f32 has 24 mantissa bits, so 2 to the 24th power seems reasonable here.
-const FAR: f32 = 16777216.0;
+struct ClockPivots {
+    hours: GameObjectId,
+    minutes: GameObjectId,
+    seconds: GameObjectId,
+}
 
-const CYLINDER_INDEX_START: shaders::Index = 0;
-const CYLINDER_INDEX_ONE_PAST_END: shaders::Index = CYLINDER_INDEX_START + math::geom::CYLINDER_INDEX_COUNT as shaders::Index;
-const CUBE1_INDEX_START: shaders::Index = CYLINDER_INDEX_ONE_PAST_END;
-const CUBE1_INDEX_ONE_PAST_END: shaders::Index = CUBE1_INDEX_START + math::geom::CUBE_INDEX_COUNT as shaders::Index;
-const CUBE2_INDEX_START: shaders::Index = CUBE1_INDEX_ONE_PAST_END;
-const CUBE2_INDEX_ONE_PAST_END: shaders::Index = CUBE2_INDEX_START + math::geom::CUBE_INDEX_COUNT as shaders::Index;
-const INDEX_LEN: usize = CUBE2_INDEX_ONE_PAST_END as usize;
-
-const CYLINDER_VERTEX_START: shaders::Index = 0;
-const CYLINDER_VERTEX_ONE_PAST_END: shaders::Index = CYLINDER_VERTEX_START + math::geom::CYLINDER_POINT_COUNT as shaders::Index;
-const CUBE1_VERTEX_START: shaders::Index = CYLINDER_VERTEX_ONE_PAST_END;
-const CUBE1_VERTEX_ONE_PAST_END: shaders::Index = CUBE1_VERTEX_START + math::geom::CUBE_POINT_COUNT as shaders::Index;
-const CUBE2_VERTEX_START: shaders::Index = CUBE1_VERTEX_ONE_PAST_END;
-const CUBE2_VERTEX_ONE_PAST_END: shaders::Index = CUBE2_VERTEX_START + math::geom::CUBE_POINT_COUNT as shaders::Index;
-const VERTEX_LEN: usize = CUBE2_VERTEX_ONE_PAST_END as usize;
-
-struct IndexedMesh {
-    pub vertices: [textured_lit::Vertex; VERTEX_LEN],
-    pub indices: [shaders::Index; INDEX_LEN],
+/// Arranged like the Unity version. The arms are children of pivots, which
+/// rotate around the center of the clock. The parts are spawned as children of
+/// the game object this script is attached to.
+struct Clock {
+    renderers: ClockRenderers,
+    pivots: Option<ClockPivots>,
 }
 
-fn gen_mesh() -> IndexedMesh {
-    use math::geom::Scale;
+impl Script for Clock {
+    fn awake(&mut self, context: &mut Context) {
+        let root = Some(context.game_object());
+        let renderers = &self.renderers;
 
-    let mut vertices = [textured_lit::VERTEX_DEFAULT; VERTEX_LEN];
-    let mut indices = [0; INDEX_LEN];
+        context.spawn(GameObject {
+            parent: root,
+            transform: Transform {
+                scale: vec3!(10., 10., 0.2),
+                ..transform::DEFAULT
+            },
+            renderer: Some(renderers.face),
+            ..<_>::default()
+        });
 
-    let cylinder_mesh = math::geom::gen_cylinder_mesh(Scale {
-        x: 1./8.,
-        y: 1./8.,
-        z: 1./4.,
-    });
+        for i in 0..12 {
+            let rotation = Quat::from_axis_angle(
+                Radians(i as f32 * TAU / 12.),
+                vec3!(z)
+            );
 
-    for i in CYLINDER_VERTEX_START..CYLINDER_VERTEX_ONE_PAST_END {
-        let read_i = (i - CYLINDER_VERTEX_START) as usize;
-        let point = cylinder_mesh.points[read_i];
-        let normal = Vec3::from(cylinder_mesh.normals[read_i]);
-
-        vertices[i as usize] = textured_lit::vertex!{
-            point.x,
-            point.y,
-            point.z,
-            normal.x,
-            normal.y,
-            normal.z,
-            0xFFFFFFFF,
-            0,
-            0,
-        };
-    }
+            context.spawn(GameObject {
+                parent: root,
+                transform: Transform {
+                    position: rotation * vec3!(0., 4. * T_K, 0.25 * T_K),
+                    rotation,
+                    scale: vec3!(0.5, 1., 0.1),
+                },
+                renderer: Some(renderers.indicator),
+                ..<_>::default()
+            });
+        }
 
-    for i in CYLINDER_INDEX_START..CYLINDER_INDEX_ONE_PAST_END {
-        let i = i as usize;
-        indices[i] = cylinder_mesh.indices[i - CYLINDER_INDEX_START as usize]
-            + CYLINDER_VERTEX_START as shaders::Index;
-    }
+        let mut spawn_pivot_and_arm = |arm: Transform, renderer: MeshRenderer| {
+            let pivot = context.spawn(GameObject {
+                parent: root,
+                ..<_>::default()
+            });
 
-    let cube_mesh = math::geom::gen_cube_mesh(1./8.);
+            context.spawn(GameObject {
+                parent: Some(pivot),
+                transform: arm,
+                renderer: Some(renderer),
+                ..<_>::default()
+            });
 
-    for i in CUBE1_VERTEX_START..CUBE1_VERTEX_ONE_PAST_END {
-        let read_i = (i - CUBE1_VERTEX_START) as usize;
-        let point = cube_mesh.points[read_i];
-        let normal = Vec3::from(cube_mesh.normals[read_i]);
-
-        vertices[i as usize] = textured_lit::vertex!{
-            point.x,
-            point.y,
-            point.z,
-            normal.x,
-            normal.y,
-            normal.z,
-            0xFF494949,
-            0,
-            0,
+            pivot
         };
-    }
 
-    for i in CUBE1_INDEX_START..CUBE1_INDEX_ONE_PAST_END {
-        let i = i as usize;
-        indices[i] = cube_mesh.indices[i - CUBE1_INDEX_START as usize]
-            + CUBE1_VERTEX_START;
+        let hours = spawn_pivot_and_arm(Transform {
+            position: vec3!(0., T_K, 0.35 * T_K),
+            scale: vec3!(0.3, 2.5, 0.1),
+            ..transform::DEFAULT
+        }, renderers.arm);
+        let minutes = spawn_pivot_and_arm(Transform {
+            position: vec3!(0., 0.75 * T_K, 0.25 * T_K),
+            scale: vec3!(0.2, 4., 0.1),
+            ..transform::DEFAULT
+        }, renderers.arm);
+        let seconds = spawn_pivot_and_arm(Transform {
+            position: vec3!(0., 1.25 * T_K, 0.45 * T_K),
+            scale: vec3!(0.1, 5., 0.1),
+            ..transform::DEFAULT
+        }, renderers.seconds_arm);
+
+        self.pivots = Some(ClockPivots {
+            hours,
+            minutes,
+            seconds,
+        });
     }
 
-    for i in CUBE2_VERTEX_START..CUBE2_VERTEX_ONE_PAST_END {
-        let read_i = (i - CUBE2_VERTEX_START) as usize;
-        let point = cube_mesh.points[read_i];
-        let normal = Vec3::from(cube_mesh.normals[read_i]);
-
-        vertices[i as usize] = textured_lit::vertex!{
-            point.x,
-            point.y,
-            point.z,
-            normal.x,
-            normal.y,
-            normal.z,
-            0xFF0000B3,
-            0,
-            0,
+    fn update(&mut self, context: &mut Context, _dt: f32) {
+        let Some(pivots) = &self.pivots else { return };
+
+        // This project is not focused on displaying an accurate time. So let's
+        // just act naively and get something a very approximate time of day in
+        // UTC.
+
+        use std::time::SystemTime;
+
+        let (hour, minute, second) = match SystemTime::now().duration_since(SystemTime::UNIX_EPOCH) {
+            // There's probably a better way to avoid f32 precision issues, but this
+            // works, at least right now.
+            Ok(n) => (
+                ((n / (60 * 60)).as_secs_f64() % 12.) as f32,
+                ((n / 60).as_secs_f64() % 60.) as f32,
+                (n.as_secs_f64() % 60.) as f32,
+            ),
+            // We don't really care about edges cases like this right now either.
+            Err(_) => (0., 0., 0.),
         };
-    }
 
-    for i in CUBE2_INDEX_START..CUBE2_INDEX_ONE_PAST_END {
-        let i = i as usize;
-        indices[i] = cube_mesh.indices[i - CUBE2_INDEX_START as usize]
-            + CUBE2_VERTEX_START;
+        for (pivot, rotation) in [
+            (pivots.hours, hour * -TAU / 12.),
+            (pivots.minutes, minute * -TAU / 60.),
+            (pivots.seconds, second * -TAU / 60.),
+        ] {
+            let Some(transform_id) = context.transform_id_of(pivot) else {
+                continue
+            };
+
+            context.local_mut(transform_id).rotation = Quat::from_axis_angle(
+                Radians(rotation),
+                vec3!(z)
+            );
+        }
     }
+}
 
-    IndexedMesh {
-        vertices,
-        indices,
-    }
+/// The resources the model uses. The pipeline keeps the shader alive.
+#[allow(dead_code)]
+struct Gfx {
+    pipeline: OwnedPipeline,
+    image: OwnedImage,
+    vertices: OwnedBuffer,
+    indices: OwnedBuffer,
+    context: sg::Context,
+}
+
+#[derive(Default)]
+struct State {
+    gfx: Option<Gfx>,
+    skybox: skybox::State,
+    scene: Scene,
+    axes: axes::State,
+    eye: Eye,
+    light_dir: Vec3,
+    center: Vec3,
+    time: f32,
+    /// Toggled with tab. Shows a second, fixed, camera beside the main one.
+    split_screen: bool,
+}
+
+// Near/Far clipping plane distances along z.
+const NEAR: f32 = 0.01;
+// An f32 has 24 mantissa bits, so 2 to the 24th power seems reasonable here.
+const FAR: f32 = 16777216.0;
+
+fn init_skybox(skybox: &mut skybox::State) {
+    let decoded = decoded::png_with_checkerboard_fallback(
+        include_bytes!("../../../assets/skybox.png"),
+    );
+
+    skybox::init(
+        skybox,
+        &skybox::CubePixels::from_cross(
+            skybox::Rgba8::new(decoded.w, decoded.h, &decoded.image_bytes)
+        ),
+    );
 }
 
 fn init(state: &mut State) {
-    setup_default_context();
+    let context = sg::Context::setup_default();
 
-    skybox::init(&mut state.skybox);
+    init_skybox(&mut state.skybox);
     axes::init(&mut state.axes);
 
-    let mesh = gen_mesh();
-
-    let vertices = mesh.vertices;
+    let mut mesh = MeshBuilder::<textured_lit::Vertex>::new();
 
-    state.model.bind.vertex_buffers[0] = sg::make_immutable_vertex_buffer!(
-        vertices,
-        "model-vertices"
+    let cylinder_mesh = math::geom::gen_cylinder_mesh(
+        Scale {
+            x: 1./8.,
+            y: 1./8.,
+            z: 1./4.,
+        },
+        16,
+        true,
     );
+    let cube_mesh = math::geom::gen_cube_mesh(1./8.);
 
-    let indices = mesh.indices;
+    let face = mesh.append(&cylinder_mesh, None, 0xFFFFFFFF);
+    let cube1 = mesh.append(&cube_mesh, None, 0xFF494949);
+    let cube2 = mesh.append(&cube_mesh, None, 0xFF0000B3);
 
-    state.model.bind.index_buffer = sg::make_immutable_index_buffer!(
-        indices,
-        "model-indices"
-    );
+    let vertices = OwnedBuffer::immutable_vertices(&context, &mesh.vertices, "model-vertices")
+        .expect("model vertex buffer should be valid");
+    let indices = OwnedBuffer::immutable_indices(&context, &mesh.indices, "model-indices")
+        .expect("model index buffer should be valid");
 
-    state.model.bind.fs_images[textured_lit::SLOT_TEX as usize] = white::make();
+    // SAFETY: Nothing else has the handle.
+    let image = unsafe { OwnedImage::from_handle(&context, white::make()) }
+        .expect("white image should be valid");
+
+    let mut bindings = Bindings::default();
+    bindings.vertex_buffers[0] = vertices.handle();
+    bindings.index_buffer = indices.handle();
+    bindings.fs_images[textured_lit::SLOT_TEX as usize] = image.handle();
 
     let (shader, layout, depth) = textured_lit::make_shader_etc(query_backend());
 
-    let pipeline_desc = PipelineDesc{
-        layout,
-        shader,
-        index_type: sg::IndexType::UInt16 as _,
-        cull_mode: sg::CullMode::Back as _,
-        depth,
-        label: cstr!("model-pipeline"),
-        ..PipelineDesc::default()
+    // SAFETY: Nothing else has the handle.
+    let shader = unsafe { OwnedShader::from_handle(&context, shader) }
+        .expect("model shader should be valid");
+
+    let owned_pipeline = PipelineBuilder::new(Rc::new(shader), layout)
+        .index_type(sg::IndexType::UInt16)
+        .cull_mode(sg::CullMode::Back)
+        .depth(depth)
+        .label("model-pipeline")
+        .bindings(&bindings)
+        .build(&context)
+        .expect("model pipeline should be valid");
+    let pipeline = owned_pipeline.handle();
+
+    let renderer = |Submesh { base_element, element_count }: Submesh| MeshRenderer {
+        mesh: Mesh {
+            bindings,
+            base_element,
+            element_count,
+        },
+        material: Material {
+            pipeline,
+            diffuse_colour: vec3!(1., 1., 1.),
+        },
     };
-    state.model.pipe = unsafe { sg::make_pipeline(&pipeline_desc) };
+
+    state.scene.spawn(GameObject {
+        scripts: vec![Box::new(Clock {
+            renderers: ClockRenderers {
+                face: renderer(face),
+                indicator: renderer(cube1),
+                arm: renderer(cube1),
+                seconds_arm: renderer(cube2),
+            },
+            pivots: None,
+        })],
+        ..<_>::default()
+    });
+
+    state.gfx = Some(Gfx {
+        pipeline: owned_pipeline,
+        image,
+        vertices,
+        indices,
+        context,
+    });
 
     state.eye.x = Radians(TAU / 4.);
     state.eye.y = Radians(TAU / 4.);
@@ -220,7 +320,10 @@ fn init(state: &mut State) {
 }
 
 fn frame(state: &mut State) {
-    state.time += sapp::frame_duration() as f32;
+    let dt = sapp::frame_duration() as f32;
+    state.time += dt;
+
+    state.scene.frame(dt);
 
     let mut pass_action = PassAction::default();
     pass_action.colors[0] = ColorAttachmentAction {
@@ -232,140 +335,85 @@ fn frame(state: &mut State) {
     let h = sapp::height();
 
     /* compute model-view-projection matrix for vertex shader */
-    let proj = Mat4::perspective(Radians(TAU / 6.), w as f32/h as f32, (NEAR, FAR));
-    let view = get_view_matrix(state);
-    let view_proj = proj * view;
-
-    begin_default_pass(&pass_action, sapp::width(), sapp::height());
-
-    skybox::draw(&state.skybox, view_proj);
-
-    draw_model(&state, view_proj);
-
-    end_pass();
-
-    if cfg!(feature = "debug-axes") {
-        begin_default_pass(&sokol_extras::debug::pass_action(), w, h);
-
-        axes::draw(&state.axes, view_proj * Mat4::scale(vec3!(25., 25., 25.)));
-
-        end_pass();
-    }
-
-    commit();
-}
-
-fn draw_model(state: &State, view_proj: Mat4) {
-    let model = &state.model;
-    let eye_pos = state.eye.to_vec3();
-    let light_dir = state.light_dir;
-
-    unsafe {
-        sg::apply_pipeline(model.pipe);
-        sg::apply_bindings(&model.bind);
-    }
+    let view_and_proj = |viewport: Rect, eye_pos: Vec3, fov: Radians| {
+        let proj = Mat4::perspective(fov, viewport.aspect_ratio(), (NEAR, FAR));
+        let view = Mat4::look_at(eye_pos, state.center, vec3!(y));
+        (view, proj)
+    };
 
-    let fs_params = textured_lit::FSParams {
-        light_dir,
-        eye_pos,
+    let full = Rect::full(w, h);
+    let (main_viewport, side_viewport) = if state.split_screen {
+        full.split_at_x(w * 2 / 3)
+    } else {
+        (full, Rect::default())
     };
 
-    let diffuse_colour = vec3!(1., 1., 1.);
+    let main_eye_pos = state.eye.to_vec3();
+    let (main_view, main_proj) = view_and_proj(main_viewport, main_eye_pos, Radians(TAU / 6.));
+    let main_view_proj = main_proj * main_view;
 
-    // Translation constant. An observed but currently not well understood scaling
-    // that is needed to make the numbers from the tutorial produce the expected
-    // translation amounts.
-    const T_K: f32 = 1./4.;
+    begin_default_pass(&pass_action, w, h);
 
-    macro_rules! draw {
-        ($model_matrix: expr, $start_i: expr, $end_i: expr) => {{
-            let model = $model_matrix;
+    apply_viewport(main_viewport, true);
+    apply_scissor_rect(main_viewport, true);
 
-            textured_lit::apply_uniforms(
-                textured_lit::VSParams {
-                    model,
-                    mvp: view_proj * model,
-                    diffuse_colour,
-                },
-                fs_params
-            );
+    skybox::draw(&state.skybox, main_view, main_proj);
 
-            unsafe {
-                sg::draw($start_i as Int, ($end_i - $start_i) as Int, 1);
-            }
-        }}
-    }
+    draw_model(state, main_view_proj, main_eye_pos);
 
-    // Clock face
-    {
-        let model = Mat4::scale(vec3!(10., 10., 0.2));
-
-        draw!(model, CYLINDER_INDEX_START, CYLINDER_INDEX_ONE_PAST_END);
-    }
+    if state.split_screen {
+        // Looking at the clock face from up close, with a narrower field of view.
+        let side_eye_pos = vec3!(1., 1., 2.);
+        let (side_view, side_proj) = view_and_proj(side_viewport, side_eye_pos, Radians(TAU / 8.));
+        let side_view_proj = side_proj * side_view;
 
-    // Hour markers
-    for i in 0..12 {
-        let angle = i as f32 * TAU / 12.;
+        apply_viewport(side_viewport, true);
+        apply_scissor_rect(side_viewport, true);
 
-        let model =
-            Mat4::rotation(Radians(angle), vec3!(z)) *
-            Mat4::translate(vec3!(0., 4. * T_K, 0.25 * T_K)) *
-            Mat4::scale(vec3!(0.5, 1., 0.1));
+        skybox::draw(&state.skybox, side_view, side_proj);
 
-        draw!(model, CUBE1_INDEX_START, CUBE1_INDEX_ONE_PAST_END);
+        draw_model(state, side_view_proj, side_eye_pos);
     }
 
-    // This project is not focused on displaying an accurate time. So let's
-    // just act naively and get something a very approximate time of day in
-    // UTC.
+    end_pass();
 
-    use std::time::SystemTime;
+    if cfg!(feature = "debug-axes") {
+        begin_default_pass(&sokol_extras::debug::pass_action(), w, h);
 
-    let (hour, minute, second) = match SystemTime::now().duration_since(SystemTime::UNIX_EPOCH) {
-        // There's probably a better way to avoid f32 precision issues, but this
-        // works, at least right now.
-        Ok(n) => (
-            ((n / (60 * 60)).as_secs_f64() % 12.) as f32,
-            ((n / 60).as_secs_f64() % 60.) as f32,
-            (n.as_secs_f64() % 60.) as f32,
-        ),
-        // We don't really care about edges cases like this right now either.
-        Err(_) => (0., 0., 0.),
-    };
+        apply_viewport(main_viewport, true);
 
-    // Hour hand
-    {
-        let model =
-            Mat4::rotation(Radians(hour * -TAU / 12.), vec3!(z)) *
-            Mat4::translate(vec3!(0., T_K, 0.35 * T_K)) *
-            Mat4::scale(vec3!(0.3, 2.5, 0.1));
+        axes::draw(&state.axes, main_view_proj * Mat4::scale(vec3!(25., 25., 25.)));
 
-        draw!(model, CUBE1_INDEX_START, CUBE1_INDEX_ONE_PAST_END);
+        end_pass();
     }
 
-    // Minute hand
-    {
-        let model =
-            Mat4::rotation(Radians(minute * -TAU / 60.), vec3!(z)) *
-            Mat4::translate(vec3!(0., 0.75 * T_K, 0.25 * T_K)) *
-            Mat4::scale(vec3!(0.2, 4., 0.1));
-
-        draw!(model, CUBE1_INDEX_START, CUBE1_INDEX_ONE_PAST_END);
-    }
+    commit();
+}
 
-    // Second hand
-    {
-        let model =
-            Mat4::rotation(Radians(second * -TAU / 60.), vec3!(z)) *
-            Mat4::translate(vec3!(0., 1.25 * T_K, 0.45 * T_K)) *
-            Mat4::scale(vec3!(0.1, 5., 0.1));
+fn draw_model(state: &State, view_proj: Mat4, eye_pos: Vec3) {
+    let fs_params = textured_lit::FSParams {
+        light_dir: state.light_dir,
+        eye_pos,
+    };
 
-        draw!(model, CUBE2_INDEX_START, CUBE2_INDEX_ONE_PAST_END);
-    }
+    state.scene.draw(|model, material| {
+        textured_lit::apply_uniforms(
+            textured_lit::VSParams {
+                model,
+                mvp: view_proj * model,
+                diffuse_colour: material.diffuse_colour,
+            },
+            fs_params
+        );
+    });
 }
 
-fn cleanup(_state: &mut State) {
-    sg::shutdown()
+fn cleanup(state: &mut State) {
+    state.scene.destroy_all();
+
+    // Shuts down sokol_gfx, once the model resources are destroyed. This needs
+    // to happen here, since the graphics context is gone once we return.
+    state.gfx = None;
 }
 
 fn event(event: &sapp::Event, state: &mut State) {
@@ -377,6 +425,9 @@ fn event(event: &sapp::Event, state: &mut State) {
     const CENTER_MOVE_SCALE: f32 = 1./32.;
 
     match event.kind {
+        EventKind::KeyDown { key_code: KeyCode::Tab, .. } => {
+            state.split_screen = !state.split_screen;
+        }
         EventKind::KeyDown { key_code, modifiers, .. } => {
             macro_rules! do_move {
                 () => {
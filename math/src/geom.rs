@@ -1,5 +1,17 @@
 use core::ops::{Mul, MulAssign};
-use crate::vec3::{Normal, normal, Vec3, vec3};
+use crate::{
+    vec3::{Normal, normal, Vec3, vec3},
+    vec4::Vec4,
+};
+
+mod grid;
+pub use grid::{gen_grid_mesh, GridSize};
+mod rounded_cube;
+pub use rounded_cube::{gen_rounded_cube_mesh, RoundedCube};
+mod revolution;
+pub use revolution::{gen_capsule_mesh, gen_cone_mesh, gen_cylinder_mesh, gen_torus_mesh};
+mod sphere;
+pub use sphere::{gen_cube_sphere_mesh, gen_icosphere_mesh, gen_uv_sphere_mesh};
 
 pub type Coord = f32;
 
@@ -114,6 +126,96 @@ pub struct IndexedMesh<const POINT_COUNT: usize, const INDEX_COUNT: usize> {
     pub indices: [Index; INDEX_COUNT],
 }
 
+/// A texture coordinate. `u` goes from left to right and `v` goes from bottom to
+/// top, across the texture.
+#[derive(Clone, Copy, Debug, Default, PartialEq, PartialOrd)]
+pub struct UV {
+    pub u: Coord,
+    pub v: Coord,
+}
+
+/// A type that can be used for the indices of a `Mesh`. `Index` is enough for
+/// most meshes, but `u32` allows for meshes with more points.
+pub trait MeshIndex: Copy {
+    fn from_usize(i: usize) -> Option<Self>;
+}
+
+impl MeshIndex for u16 {
+    fn from_usize(i: usize) -> Option<Self> {
+        Self::try_from(i).ok()
+    }
+}
+
+impl MeshIndex for u32 {
+    fn from_usize(i: usize) -> Option<Self> {
+        Self::try_from(i).ok()
+    }
+}
+
+/// An indexed mesh with a size that is only known at runtime. Each point has a
+/// corresponding normal and UV at the same index.
+///
+/// `tangents` are in the same form as Unity's: the `xyz` part points in the
+/// direction that `u` increases, and the cross product of the normal and that,
+/// multiplied by `w`, points in the direction that `v` increases. Only some
+/// generators produce tangents, and the rest leave `tangents` empty.
+#[derive(Clone, Debug, Default)]
+pub struct Mesh<I = Index> {
+    pub points: Vec<Point>,
+    pub normals: Vec<Normal>,
+    pub uvs: Vec<UV>,
+    pub tangents: Vec<Vec4>,
+    pub indices: Vec<I>,
+}
+
+impl <I: MeshIndex> Mesh<I> {
+    /// Appends a point with its normal and UV, and returns its index.
+    ///
+    /// # Panics
+    /// If the index would not fit in an `I`.
+    fn push_vertex(&mut self, point: Point, normal: Normal, uv: UV) -> I {
+        let index = I::from_usize(self.points.len())
+            .expect("mesh should have few enough points to fit in its index type");
+
+        self.points.push(point);
+        self.normals.push(normal);
+        self.uvs.push(uv);
+
+        index
+    }
+}
+
+impl <'mesh> From<&'mesh Mesh> for MeshRef<'mesh> {
+    fn from(mesh: &'mesh Mesh) -> Self {
+        Self {
+            points: &mesh.points,
+            normals: &mesh.normals,
+            indices: &mesh.indices,
+        }
+    }
+}
+
+/// A borrowed view of an indexed mesh, which allows working with meshes of
+/// different sizes through the same type. Only meshes with `Index` indices can
+/// be viewed this way.
+#[derive(Clone, Copy)]
+pub struct MeshRef<'mesh> {
+    pub points: &'mesh [Point],
+    pub normals: &'mesh [Normal],
+    pub indices: &'mesh [Index],
+}
+
+impl <'mesh, const POINT_COUNT: usize, const INDEX_COUNT: usize>
+From<&'mesh IndexedMesh<POINT_COUNT, INDEX_COUNT>> for MeshRef<'mesh> {
+    fn from(mesh: &'mesh IndexedMesh<POINT_COUNT, INDEX_COUNT>) -> Self {
+        Self {
+            points: &mesh.points,
+            normals: &mesh.normals,
+            indices: &mesh.indices,
+        }
+    }
+}
+
 pub const CUBE_INDEX_COUNT: Index = 36;
 pub const CUBE_INDEX_COUNT_USIZE: usize = CUBE_INDEX_COUNT as usize;
 
@@ -208,159 +310,42 @@ pub fn gen_cube_mesh(scale: Coord)
     }
 }
 
-const RING_POINT_COUNT: Index = 16;
-const RING_POINT_COUNT_USIZE: usize = RING_POINT_COUNT as usize;
-const DISC_POINT_COUNT: Index = RING_POINT_COUNT + 1;
-
-pub const CYLINDER_POINT_COUNT: Index = (DISC_POINT_COUNT + RING_POINT_COUNT) * 2;
-pub const CYLINDER_POINT_COUNT_USIZE: usize = CYLINDER_POINT_COUNT as usize;
-
-pub const CYLINDER_INDEX_COUNT: Index = RING_POINT_COUNT * (
-    // Top disc
-    3
-    // Downward pointing edge triangles
-    + 3
-    // Upward pointing edge triangles
-    + 3
-    // Bottom disc
-    + 3
-);
-pub const CYLINDER_INDEX_COUNT_USIZE: usize = CYLINDER_INDEX_COUNT as usize;
-
-pub fn gen_cylinder_mesh(scale: Scale)
--> IndexedMesh<CYLINDER_POINT_COUNT_USIZE, CYLINDER_INDEX_COUNT_USIZE> {
-    let mut points = [Point::default(); CYLINDER_POINT_COUNT_USIZE];
-    let mut normals = [Normal::default(); CYLINDER_POINT_COUNT_USIZE];
-
-    let mut sin_cos_table = [(0., 0.); RING_POINT_COUNT_USIZE];
-
-    for i in 0..RING_POINT_COUNT as usize {
-        let theta = i as Coord * TAU / RING_POINT_COUNT_COORD;
-        
-        sin_cos_table[i] = theta.sin_cos();
-    }
-    let sin_cos_table = sin_cos_table;
-
-    const TOP_RING_DISK_START: Index = 1;
-    const TOP_RING_SHAFT_START: Index = TOP_RING_DISK_START + RING_POINT_COUNT;
-    const BOTTOM_RING_SHAFT_START: Index = TOP_RING_SHAFT_START + RING_POINT_COUNT;
-    const BOTTOM_RING_DISK_START: Index = BOTTOM_RING_SHAFT_START + RING_POINT_COUNT;
-    const BOTTOM_DISC_CENTER: Index = BOTTOM_RING_DISK_START + RING_POINT_COUNT;
-
-    const RING_POINT_COUNT_COORD: Coord = RING_POINT_COUNT as Coord;
-
-    let top_z = scale.z;
-    let bottom_z = -top_z;
-
-    let (top_normal, bottom_normal) = if scale.z > 0. {
-        (normal!(z), normal!(-z))
-    } else {
-        (normal!(-z), normal!(z))
-    };
-
-    points[0] = Point {
-        x: 0.,
-        y: 0.,
-        z: top_z,
-    };
-
-    normals[0] = top_normal;
-
-    for i in TOP_RING_DISK_START as usize..TOP_RING_SHAFT_START as usize {
-        let (sin, cos) = sin_cos_table[i - TOP_RING_DISK_START as usize];
-
-        points[i] = Point {
-            x: scale.x * cos,
-            y: scale.y * sin,
-            z: top_z,
-        };
-
-        normals[i] = top_normal;
-    }
-
-    for i in TOP_RING_SHAFT_START as usize..BOTTOM_RING_SHAFT_START as usize {
-        let (sin, cos) = sin_cos_table[i - TOP_RING_SHAFT_START as usize];
-
-        points[i] = Point {
-            x: scale.x * cos,
-            y: scale.y * sin,
-            z: top_z,
-        };
-
-        normals[i] = normal!(cos, sin, 0.);
-    }
-
-    for i in BOTTOM_RING_SHAFT_START as usize..BOTTOM_RING_DISK_START as usize {
-        let (sin, cos) = sin_cos_table[i - BOTTOM_RING_SHAFT_START as usize];
-
-        points[i] = Point {
-            x: scale.x * cos,
-            y: scale.y * sin,
-            z: bottom_z,
-        };
-
-        normals[i] = normal!(cos, sin, 0.);
-    }
-
-    for i in BOTTOM_RING_DISK_START as usize..BOTTOM_DISC_CENTER as usize {
-        let (sin, cos) = sin_cos_table[i - BOTTOM_RING_DISK_START as usize];
-
-        points[i] = Point {
-            x: scale.x * cos,
-            y: scale.y * sin,
-            z: bottom_z,
-        };
-
-        normals[i] = bottom_normal;
-    }
-
-    points[BOTTOM_DISC_CENTER as usize] = Point {
-        x: 0.,
-        y: 0.,
-        z: bottom_z,
-    };
-
-    normals[BOTTOM_DISC_CENTER as usize] = bottom_normal;
-
-    let mut indices = [0; CYLINDER_INDEX_COUNT as usize];
-
-    // Top disc
-    for index in 0..RING_POINT_COUNT {
-        let i = (index * 3) as usize;
-        indices[i] = ((index + TOP_RING_DISK_START) % RING_POINT_COUNT) + 1;
-        indices[i + 1] = index + TOP_RING_DISK_START;
-        indices[i + 2] = 0;
-    }
-
-    // Downward pointing edge triangles
-    for index in 0..RING_POINT_COUNT {
-        let i = ((index + RING_POINT_COUNT) * 3) as usize;
-        indices[i] = index + TOP_RING_SHAFT_START;
-        indices[i + 1] = ((index + 1) % RING_POINT_COUNT) + TOP_RING_SHAFT_START;
-        // Jump up to next disc
-        indices[i + 2] = index + BOTTOM_RING_SHAFT_START;
+/// Checks that each triangle is wound the same way as the cube mesh's. That is,
+/// clockwise when viewed from the outside, which is the default front face
+/// winding for sokol. `outward` should return a vector pointing out of the mesh,
+/// near the given point.
+#[cfg(test)]
+fn assert_clockwise_from_outside(
+    mesh: MeshRef,
+    outward: impl Fn(Vec3) -> Vec3,
+) {
+    for triangle in mesh.indices.chunks_exact(3) {
+        let [a, b, c] = [0, 1, 2].map(|i| Vec3::from(mesh.points[triangle[i] as usize]));
+
+        let centroid = (a + b + c) * (1. / 3.);
+
+        // For a triangle that is clockwise when viewed from the outside, this
+        // cross product points inwards.
+        let cross = (b - a).cross(c - a);
+
+        assert!(
+            cross.dot(outward(centroid)) < 0.,
+            "{triangle:?} ({a}, {b}, {c}) is wound the wrong way, or is degenerate"
+        );
     }
+}
 
-    // Upward pointing edge triangles
-    for index in 0..RING_POINT_COUNT {
-        let i = ((index + RING_POINT_COUNT * 2) * 3) as usize;
-        // Jump back to previous disc
-        indices[i] = ((index + 1) % RING_POINT_COUNT) + TOP_RING_SHAFT_START;
-        indices[i + 1] = ((index + 1) % RING_POINT_COUNT) + BOTTOM_RING_SHAFT_START;
-        indices[i + 2] = index + BOTTOM_RING_SHAFT_START;
+#[cfg(test)]
+fn assert_unit_normals(mesh: MeshRef) {
+    for &normal in mesh.normals {
+        assert_approx_eq!(Vec3::from(normal).length(), 1.);
     }
+}
 
-    // Bottom disc
-    for index in 0..RING_POINT_COUNT {
-        let i = ((index + RING_POINT_COUNT * 3) * 3) as usize;
-        indices[i] = BOTTOM_DISC_CENTER;
-        indices[i + 1] = index + BOTTOM_RING_DISK_START;
-        indices[i + 2] = ((index + 1) % RING_POINT_COUNT) + BOTTOM_RING_DISK_START;
-    }
+#[test]
+fn the_cube_mesh_is_clockwise_from_outside() {
+    let cube = gen_cube_mesh(2.);
 
-    IndexedMesh{
-        points,
-        normals,
-        indices,
-    }
-}
\ No newline at end of file
+    assert_clockwise_from_outside((&cube).into(), |centroid| centroid);
+    assert_unit_normals((&cube).into());
+}
@@ -0,0 +1,135 @@
+use crate::{
+    vec3::normal,
+    vec4::vec4,
+};
+
+use super::{Coord, Mesh, MeshIndex, Point, UV};
+
+/// The size of a grid along the `x` and `z` axes, respectively.
+pub type GridSize = (Coord, Coord);
+
+/// A flat grid of `x_segments` by `z_segments` quads in the `x`-`z` plane,
+/// centered on the origin and facing `+y`. Like the Catlike Coding "Procedural
+/// Grid" tutorial, but oriented to be used as a ground plane. Each segment count is
+/// raised to 1 if it is lower than that.
+///
+/// `u` goes from 0 to 1 along `+x` and `v` goes from 0 to 1 along `-z`, so the
+/// texture is right way up when viewed from above, with `-z` as up. The tangents
+/// point along `+x`.
+///
+/// Large grids can have more points than fit in an `Index`, so the index type
+/// can be chosen. For example, `gen_grid_mesh::<u32>(512, 512, (64., 64.))`.
+/// Only meshes with `Index` indices convert into a `MeshRef` though, so a
+/// `Mesh<u32>` cannot be passed to things that take one, like `sokol_extras`'s
+/// `MeshBuilder::append`. Its fields need to be used directly instead.
+///
+/// # Panics
+/// If the amount of points does not fit in the index type.
+pub fn gen_grid_mesh<I: MeshIndex>(
+    x_segments: u32,
+    z_segments: u32,
+    (x_size, z_size): GridSize,
+) -> Mesh<I> {
+    let x_segments = x_segments.max(1) as usize;
+    let z_segments = z_segments.max(1) as usize;
+
+    let row_length = x_segments + 1;
+    let point_count = row_length * (z_segments + 1);
+
+    assert!(
+        I::from_usize(point_count - 1).is_some(),
+        "{point_count} points do not fit in the chosen index type"
+    );
+
+    let mut mesh = Mesh {
+        points: Vec::with_capacity(point_count),
+        normals: Vec::with_capacity(point_count),
+        uvs: Vec::with_capacity(point_count),
+        tangents: Vec::with_capacity(point_count),
+        indices: Vec::with_capacity(x_segments * z_segments * 6),
+    };
+
+    for row in 0..=z_segments {
+        let v = row as Coord / z_segments as Coord;
+
+        for column in 0..=x_segments {
+            let u = column as Coord / x_segments as Coord;
+
+            mesh.push_vertex(
+                Point {
+                    x: (u - 0.5) * x_size,
+                    y: 0.,
+                    z: (0.5 - v) * z_size,
+                },
+                normal!(y),
+                UV { u, v },
+            );
+            // `+y` cross `+x` is `-z`, which is the direction `v` increases in, so
+            // `w` is positive.
+            mesh.tangents.push(vec4!(1., 0., 0., 1.));
+        }
+    }
+
+    let index = |i: usize| I::from_usize(i)
+        .expect("all the indices should fit since the last one does");
+
+    for row in 0..z_segments {
+        for column in 0..x_segments {
+            // c d
+            // a b
+            let a = row * row_length + column;
+            let b = a + 1;
+            let c = a + row_length;
+            let d = c + 1;
+
+            // Clockwise when viewed from above.
+            mesh.indices.extend([a, c, b, b, c, d].map(index));
+        }
+    }
+
+    mesh
+}
+
+#[test]
+fn grids_are_valid() {
+    use crate::vec3::{Vec3, vec3};
+    use super::{assert_clockwise_from_outside, assert_unit_normals, Index};
+
+    for (x_segments, z_segments) in [(1, 1), (10, 5), (3, 7)] {
+        let mesh: Mesh<Index> = gen_grid_mesh(x_segments, z_segments, (4., 2.));
+
+        let point_count = ((x_segments + 1) * (z_segments + 1)) as usize;
+
+        assert_eq!(mesh.points.len(), point_count);
+        assert_eq!(mesh.normals.len(), point_count);
+        assert_eq!(mesh.uvs.len(), point_count);
+        assert_eq!(mesh.tangents.len(), point_count);
+        assert_eq!(mesh.indices.len(), (x_segments * z_segments * 6) as usize);
+
+        assert_unit_normals((&mesh).into());
+        assert_clockwise_from_outside((&mesh).into(), |_| vec3!(y));
+
+        let first = Vec3::from(mesh.points[0]);
+        let last = Vec3::from(mesh.points[point_count - 1]);
+
+        assert_eq!(first, vec3!(-2., 0., 1.));
+        assert_eq!(last, vec3!(2., 0., -1.));
+        assert_eq!(mesh.uvs[0], UV { u: 0., v: 0. });
+        assert_eq!(mesh.uvs[point_count - 1], UV { u: 1., v: 1. });
+    }
+}
+
+#[test]
+fn large_grids_can_use_u32_indices() {
+    // More than `u16::MAX` points.
+    let mesh: Mesh<u32> = gen_grid_mesh(300, 300, (1., 1.));
+
+    assert_eq!(mesh.points.len(), 301 * 301);
+    assert_eq!(mesh.indices.iter().max(), Some(&(301 * 301 - 1)));
+}
+
+#[test]
+#[should_panic]
+fn large_grids_do_not_fit_u16_indices() {
+    let _: Mesh<super::Index> = gen_grid_mesh(300, 300, (1., 1.));
+}
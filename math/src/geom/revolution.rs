@@ -0,0 +1,390 @@
+//! Shapes that can be made by revolving a profile around the `z` axis.
+
+use crate::vec3::normal;
+
+use super::{Coord, Index, Mesh, Point, Scale, UV, TAU};
+
+const PI: Coord = core::f32::consts::PI;
+
+const UNSCALED: Scale = Scale { x: 1., y: 1., z: 1. };
+
+/// A point on the outline of a shape, in the plane that contains the `z` axis.
+#[derive(Clone, Copy, Debug)]
+struct ProfilePoint {
+    /// The distance from the `z` axis.
+    radius: Coord,
+    z: Coord,
+    /// The normal at this point, in the same plane as `radius` and `z`.
+    normal_radius: Coord,
+    normal_z: Coord,
+    v: Coord,
+}
+
+/// Revolves the profile around the `z` axis, appending a ring of points for each
+/// profile point, and joining neighbouring rings with triangles. The profile must
+/// be in order such that the surface's outward normal is the direction along the
+/// profile crossed with the direction of revolution. Going from `+z` to `-z`
+/// along the outside of a shape does that.
+///
+/// The radii are multiplied by `radius_scale.x` along the `x` axis and
+/// `radius_scale.y` along the `y` axis, with the normals adjusted to match.
+/// `radius_scale.z` is ignored.
+fn lathe(mesh: &mut Mesh, profile: &[ProfilePoint], segments: Index, radius_scale: Scale) {
+    let ring_start = Index::try_from(mesh.points.len())
+        .expect("mesh should have few enough points to fit in an Index");
+
+    for point in profile {
+        for segment in 0..=segments {
+            let u = segment as Coord / segments as Coord;
+            let (sin, cos) = (u * TAU).sin_cos();
+
+            mesh.push_vertex(
+                Point {
+                    x: point.radius * cos * radius_scale.x,
+                    y: point.radius * sin * radius_scale.y,
+                    z: point.z,
+                },
+                // Normals are scaled by the inverse of the scale applied to the
+                // points, so that they stay perpendicular to the surface.
+                normal!(
+                    point.normal_radius * cos / radius_scale.x,
+                    point.normal_radius * sin / radius_scale.y,
+                    point.normal_z,
+                ),
+                UV { u, v: point.v },
+            );
+        }
+    }
+
+    let row_length = segments + 1;
+
+    for (ring, pair) in profile.windows(2).enumerate() {
+        let ring = ring as Index;
+
+        for segment in 0..segments {
+            // a b
+            // c d
+            let a = ring_start + ring * row_length + segment;
+            let b = a + 1;
+            let c = a + row_length;
+            let d = c + 1;
+
+            // If a ring is a single point, then one of the triangles in each quad
+            // has no area, so we skip it.
+            if pair[0].radius != 0. {
+                mesh.indices.extend([a, b, c]);
+            }
+            if pair[1].radius != 0. {
+                mesh.indices.extend([b, d, c]);
+            }
+        }
+    }
+}
+
+/// A flat disc at the given `z`, facing `+z` if `facing_up` is true, and `-z`
+/// otherwise. The UVs map a square texture onto the disc, with the disc touching
+/// the edges of the texture.
+fn disc(mesh: &mut Mesh, z: Coord, facing_up: bool, segments: Index, radius_scale: Scale) {
+    let normal = if facing_up { normal!(z) } else { normal!(-z) };
+
+    let center = mesh.push_vertex(
+        Point { x: 0., y: 0., z },
+        normal,
+        UV { u: 0.5, v: 0.5 },
+    );
+
+    for segment in 0..segments {
+        let (sin, cos) = (segment as Coord * TAU / segments as Coord).sin_cos();
+
+        mesh.push_vertex(
+            Point {
+                x: cos * radius_scale.x,
+                y: sin * radius_scale.y,
+                z,
+            },
+            normal,
+            UV { u: 0.5 + cos / 2., v: 0.5 + sin / 2. },
+        );
+    }
+
+    for segment in 0..segments {
+        let current = center + 1 + segment;
+        let next = center + 1 + (segment + 1) % segments;
+
+        if facing_up {
+            mesh.indices.extend([center, next, current]);
+        } else {
+            mesh.indices.extend([center, current, next]);
+        }
+    }
+}
+
+/// A cylinder around the `z` axis, going from `-scale.z` to `scale.z`, with
+/// radii of `scale.x` and `scale.y` along those axes. `segments` is the number of
+/// sides around the axis, and is raised to 3 if it is lower than that. If
+/// `capped` is false, the ends are left open.
+///
+/// The UVs for the side wrap around the cylinder once, with `v` going from the
+/// bottom to the top. Each cap has a disc shaped part of the texture mapped onto
+/// it.
+pub fn gen_cylinder_mesh(scale: Scale, segments: Index, capped: bool) -> Mesh {
+    let segments = segments.max(3);
+
+    let mut mesh = Mesh::default();
+
+    if capped {
+        disc(&mut mesh, scale.z, true, segments, scale);
+    }
+
+    lathe(
+        &mut mesh,
+        &[
+            ProfilePoint { radius: 1., z: scale.z, normal_radius: 1., normal_z: 0., v: 1. },
+            ProfilePoint { radius: 1., z: -scale.z, normal_radius: 1., normal_z: 0., v: 0. },
+        ],
+        segments,
+        scale,
+    );
+
+    if capped {
+        disc(&mut mesh, -scale.z, false, segments, scale);
+    }
+
+    mesh
+}
+
+/// A cone with its base at `-scale.z` and its tip at `scale.z`, with the base
+/// having radii of `scale.x` and `scale.y` along those axes. `segments` is the
+/// number of sides around the axis, and is raised to 3 if it is lower than that.
+/// If `capped` is false, the base is left open.
+///
+/// The UVs are mapped the same way as `gen_cylinder_mesh`'s are, with the top of
+/// the texture squeezed into the tip.
+pub fn gen_cone_mesh(scale: Scale, segments: Index, capped: bool) -> Mesh {
+    let segments = segments.max(3);
+
+    let mut mesh = Mesh::default();
+
+    // The normal of a unit radius cone with a height of `2 * scale.z`. `lathe`
+    // will take care of scaling it for other radii.
+    let normal_z = 1. / (2. * scale.z);
+
+    lathe(
+        &mut mesh,
+        &[
+            ProfilePoint { radius: 0., z: scale.z, normal_radius: 1., normal_z, v: 1. },
+            ProfilePoint { radius: 1., z: -scale.z, normal_radius: 1., normal_z, v: 0. },
+        ],
+        segments,
+        scale,
+    );
+
+    if capped {
+        disc(&mut mesh, -scale.z, false, segments, scale);
+    }
+
+    mesh
+}
+
+/// A cylinder with hemispheres on each end, around the `z` axis, with a total
+/// length of `height`. `height` is raised to `2 * radius` if it is lower than that,
+/// which makes a sphere. `segments` is the number of sides around the axis, and is
+/// raised to 3 if it is lower than that. `rings` is the number of rings on each
+/// hemisphere, not counting the poles, and is raised to 1 if it is lower than that.
+///
+/// The UVs wrap around the capsule once, with `v` going from the bottom pole to
+/// the top one, in proportion to the distance along the surface.
+pub fn gen_capsule_mesh(radius: Coord, height: Coord, segments: Index, rings: Index) -> Mesh {
+    let segments = segments.max(3);
+    let rings = rings.max(1);
+
+    let cylinder_half_height = (height / 2. - radius).max(0.);
+
+    let hemisphere_length = radius * PI / 2.;
+    let total_length = 2. * (hemisphere_length + cylinder_half_height);
+
+    let mut profile = Vec::with_capacity(2 * (rings as usize + 1));
+
+    // If there is no cylinder in between the hemispheres, then the rings at their
+    // edges would be in the same place, so we only include one of them.
+    let bottom_start_ring = if cylinder_half_height > 0. { 0 } else { 1 };
+
+    for (start_z, start_angle, start_length, start_ring) in [
+        (cylinder_half_height, 0., 0., 0),
+        (
+            -cylinder_half_height,
+            PI / 2.,
+            hemisphere_length + 2. * cylinder_half_height,
+            bottom_start_ring,
+        ),
+    ] {
+        for ring in start_ring..=rings {
+            let ring_angle = ring as Coord / rings as Coord * PI / 2.;
+            // From the top pole.
+            let (sin, cos) = (start_angle + ring_angle).sin_cos();
+
+            let length = start_length + ring_angle * radius;
+
+            profile.push(ProfilePoint {
+                radius: radius * sin,
+                z: start_z + radius * cos,
+                normal_radius: sin,
+                normal_z: cos,
+                v: 1. - length / total_length,
+            });
+        }
+    }
+
+    // The last ring is the bottom pole, but `sin` of half a turn is not quite zero.
+    if let Some(bottom) = profile.last_mut() {
+        bottom.radius = 0.;
+    }
+
+    let mut mesh = Mesh::default();
+
+    lathe(&mut mesh, &profile, segments, UNSCALED);
+
+    mesh
+}
+
+/// A torus around the `z` axis. `major_radius` is the distance from the center to
+/// the middle of the tube, and `minor_radius` is the radius of the tube.
+/// `segments` is the number of sides around the `z` axis and `tube_segments` is the
+/// number around the tube. Both are raised to 3 if they are lower than that.
+///
+/// `u` goes around the `z` axis and `v` goes around the tube, starting and ending
+/// on the inside of the torus.
+pub fn gen_torus_mesh(
+    major_radius: Coord,
+    minor_radius: Coord,
+    segments: Index,
+    tube_segments: Index,
+) -> Mesh {
+    let segments = segments.max(3);
+    let tube_segments = tube_segments.max(3);
+
+    let profile: Vec<_> = (0..=tube_segments).map(|tube_segment| {
+        let v = tube_segment as Coord / tube_segments as Coord;
+        // Starting on the inside, and going over the top, so that the surface
+        // faces outwards.
+        let (sin, cos) = (PI - v * TAU).sin_cos();
+
+        ProfilePoint {
+            radius: major_radius + minor_radius * cos,
+            z: minor_radius * sin,
+            normal_radius: cos,
+            normal_z: sin,
+            v,
+        }
+    }).collect();
+
+    let mut mesh = Mesh::default();
+
+    lathe(&mut mesh, &profile, segments, UNSCALED);
+
+    mesh
+}
+
+#[cfg(test)]
+use crate::vec3::{Vec3, vec3};
+
+#[cfg(test)]
+fn assert_valid(mesh: &Mesh, outward: impl Fn(Vec3) -> Vec3) {
+    use super::{assert_clockwise_from_outside, assert_unit_normals};
+
+    assert_eq!(mesh.points.len(), mesh.normals.len());
+    assert_eq!(mesh.points.len(), mesh.uvs.len());
+    assert_eq!(mesh.indices.len() % 3, 0);
+
+    assert_unit_normals(mesh.into());
+    assert_clockwise_from_outside(mesh.into(), &outward);
+
+    for (&point, &normal) in mesh.points.iter().zip(mesh.normals.iter()) {
+        let point = Vec3::from(point);
+
+        assert!(
+            Vec3::from(normal).dot(outward(point)) > 0.,
+            "{} at {point} points inwards", Vec3::from(normal)
+        );
+    }
+
+    for uv in mesh.uvs.iter() {
+        assert!((0. ..=1.).contains(&uv.u), "{uv:?}");
+        assert!((0. ..=1.).contains(&uv.v), "{uv:?}");
+    }
+}
+
+#[test]
+fn cylinders_are_valid() {
+    for capped in [false, true] {
+        for segments in [3, 16, 33] {
+            let mesh = gen_cylinder_mesh(
+                Scale { x: 1., y: 2., z: 0.5 },
+                segments,
+                capped,
+            );
+
+            // Convex shapes around the origin, like this one, face away from it.
+            assert_valid(&mesh, |point| point);
+        }
+    }
+}
+
+#[test]
+fn cylinder_normals_account_for_the_scale() {
+    let mesh = gen_cylinder_mesh(Scale { x: 1., y: 2., z: 0.5 }, 8, false);
+
+    // The side points an eighth of the way around.
+    let (sin, cos) = (TAU / 8.).sin_cos();
+    let expected = Vec3::from(normal!(cos / 1., sin / 2., 0.));
+
+    let i = mesh.uvs.iter()
+        .position(|uv| (uv.u - 1. / 8.).abs() < 1. / 65536.)
+        .unwrap();
+
+    assert_approx_eq!(Vec3::from(mesh.normals[i]), expected);
+}
+
+#[test]
+fn cones_are_valid() {
+    for capped in [false, true] {
+        let mesh = gen_cone_mesh(Scale { x: 0.5, y: 1., z: 2. }, 12, capped);
+
+        assert_valid(&mesh, |point| point);
+    }
+}
+
+#[test]
+fn capsules_are_valid() {
+    for (radius, height) in [(0.5, 2.), (1., 1.), (0.25, 10.)] {
+        let mesh = gen_capsule_mesh(radius, height, 16, 4);
+
+        assert_valid(&mesh, |point| point);
+
+        let max_z = mesh.points.iter().map(|p| p.z).fold(Coord::NEG_INFINITY, Coord::max);
+
+        assert_approx_eq!(max_z, height.max(2. * radius) / 2.);
+    }
+}
+
+#[test]
+fn tori_are_valid() {
+    let major_radius = 2.;
+    let minor_radius = 0.5;
+
+    let mesh = gen_torus_mesh(major_radius, minor_radius, 24, 12);
+
+    // Away from the closest point on the circle running through the middle of the
+    // tube.
+    let outward = |point: Vec3| {
+        point - vec3!(point.x, point.y, 0.).normalize() * major_radius
+    };
+
+    assert_valid(&mesh, outward);
+
+    for (&point, &normal) in mesh.points.iter().zip(mesh.normals.iter()) {
+        let point = Vec3::from(point);
+
+        assert_approx_eq!(outward(point) * (1. / minor_radius), Vec3::from(normal));
+    }
+}
@@ -0,0 +1,250 @@
+use core::ops::Range;
+
+use crate::vec3::{Normal, Vec3, vec3};
+
+use super::{Coord, Index, Mesh, Point, Scale, UV};
+
+/// A `Mesh` along with the ranges of its indices that make up each face.
+#[derive(Clone, Debug, Default)]
+pub struct RoundedCube {
+    pub mesh: Mesh,
+    /// The faces, in the order `+x`, `-x`, `+y`, `-y`, `+z`, `-z`. Points along the
+    /// edges and corners are shared between faces, so the ranges may refer to
+    /// some of the same points.
+    pub faces: [Range<usize>; 6],
+}
+
+/// An index of an axis, and whether it points in the positive direction.
+type Axis = (usize, bool);
+
+// Each face is given by the axis it faces along, and the axes that the grid on
+// the face goes along. The `u` axis crossed with the `v` axis is the direction
+// the face faces, so the triangles can all be wound the same way.
+const FACES: [(Axis, Axis, Axis); 6] = [
+    ((0, true), (2, false), (1, true)),
+    ((0, false), (2, true), (1, true)),
+    ((1, true), (0, true), (2, false)),
+    ((1, false), (0, true), (2, true)),
+    ((2, true), (0, true), (1, true)),
+    ((2, false), (0, false), (1, true)),
+];
+
+/// A box going from `-scale` to `scale` along each axis, with its edges rounded
+/// off to a radius of `roundness`, like the one in the Catlike Coding "Rounded
+/// Cube" tutorial. The box is divided into a grid with the given number of
+/// segments along each axis, and the segment counts are raised to 1 if they are
+/// lower than that. `roundness` is limited to the range from 0 to the smallest
+/// part of `scale`.
+///
+/// The points on the edges between faces are shared, so there are no seams
+/// where the faces meet. Since the points are shared, there is no single way to
+/// map a texture onto them without seams, so the UVs are just the `x` and `y`
+/// positions across the box, going from 0 to 1. That works for the `z` faces.
+///
+/// # Panics
+/// If the amount of points does not fit in an `Index`.
+pub fn gen_rounded_cube_mesh(
+    scale: Scale,
+    (x_segments, y_segments, z_segments): (Index, Index, Index),
+    roundness: Coord,
+) -> RoundedCube {
+    let segments = [x_segments, y_segments, z_segments].map(|s| s.max(1) as usize);
+    let scale = [scale.x, scale.y, scale.z];
+
+    let roundness = roundness.clamp(
+        0.,
+        scale.iter().copied().fold(Coord::INFINITY, Coord::min).max(0.),
+    );
+
+    let grid_length = segments.map(|s| s + 1);
+
+    // The points are created as they are first needed, and shared afterwards.
+    // Most of the grid is inside the box and never used, but this is simple.
+    let mut grid: Vec<Option<Index>> = vec![None; grid_length.iter().product()];
+
+    let mut mesh = Mesh::default();
+
+    let mut point_index = |mesh: &mut Mesh, grid_point: [usize; 3]| -> Index {
+        let grid_index = grid_point[0]
+            + grid_length[0] * (grid_point[1] + grid_length[1] * grid_point[2]);
+
+        *grid[grid_index].get_or_insert_with(|| {
+            let (point, normal) = rounded_point(grid_point, segments, scale, roundness);
+
+            mesh.push_vertex(
+                point,
+                normal,
+                UV {
+                    u: grid_point[0] as Coord / segments[0] as Coord,
+                    v: grid_point[1] as Coord / segments[1] as Coord,
+                },
+            )
+        })
+    };
+
+    let mut faces: [Range<usize>; 6] = Default::default();
+
+    for (face, &((axis, positive), (u_axis, u_positive), (v_axis, v_positive)))
+    in faces.iter_mut().zip(FACES.iter()) {
+        let start = mesh.indices.len();
+
+        let grid_point = |u: usize, v: usize| {
+            let mut grid_point = [0; 3];
+            grid_point[axis] = if positive { segments[axis] } else { 0 };
+            grid_point[u_axis] = if u_positive { u } else { segments[u_axis] - u };
+            grid_point[v_axis] = if v_positive { v } else { segments[v_axis] - v };
+            grid_point
+        };
+
+        for v in 0..segments[v_axis] {
+            for u in 0..segments[u_axis] {
+                // c d
+                // a b
+                let a = point_index(&mut mesh, grid_point(u, v));
+                let b = point_index(&mut mesh, grid_point(u + 1, v));
+                let c = point_index(&mut mesh, grid_point(u, v + 1));
+                let d = point_index(&mut mesh, grid_point(u + 1, v + 1));
+
+                mesh.indices.extend([a, c, b, b, c, d]);
+            }
+        }
+
+        *face = start..mesh.indices.len();
+    }
+
+    RoundedCube {
+        mesh,
+        faces,
+    }
+}
+
+/// Moves the point on the box at `grid_point` out to the rounded surface, and
+/// returns it along with the normal there.
+fn rounded_point(
+    grid_point: [usize; 3],
+    segments: [usize; 3],
+    scale: [Coord; 3],
+    roundness: Coord,
+) -> (Point, Normal) {
+    let on_box = [0, 1, 2].map(|axis| {
+        (grid_point[axis] as Coord / segments[axis] as Coord * 2. - 1.) * scale[axis]
+    });
+
+    // The closest point on the smaller box that the rounded surface is built
+    // around.
+    let inner = [0, 1, 2].map(|axis| {
+        let limit = scale[axis] - roundness;
+        on_box[axis].clamp(-limit, limit)
+    });
+
+    let [x, y, z] = on_box;
+    let on_box = vec3!(x, y, z);
+    let [x, y, z] = inner;
+    let inner = vec3!(x, y, z);
+
+    let outward = on_box - inner;
+
+    let (position, normal) = if outward == vec3!() {
+        // Only possible without any rounding, in which case we give points on
+        // edges and corners the average of the normals of the faces they are on.
+        let [x, y, z] = [0, 1, 2].map(|axis| {
+            if grid_point[axis] == 0 {
+                -1.
+            } else if grid_point[axis] == segments[axis] {
+                1.
+            } else {
+                0.
+            }
+        });
+
+        (on_box, Normal::from(vec3!(x, y, z)))
+    } else {
+        let normal = Normal::from(outward);
+
+        (inner + Vec3::from(normal) * roundness, normal)
+    };
+
+    (
+        Point { x: position.x, y: position.y, z: position.z },
+        normal,
+    )
+}
+
+#[cfg(test)]
+fn assert_valid(cube: &RoundedCube, segments: (Index, Index, Index)) {
+    use super::{assert_clockwise_from_outside, assert_unit_normals};
+
+    let mesh = &cube.mesh;
+
+    let (x, y, z) = segments;
+    let [x, y, z] = [x, y, z].map(|s| s.max(1) as usize);
+
+    // Only the points on the outside of the grid are used.
+    let shell_point_count = (x + 1) * (y + 1) * (z + 1)
+        - (x.saturating_sub(1) * y.saturating_sub(1) * z.saturating_sub(1));
+
+    assert_eq!(mesh.points.len(), shell_point_count);
+    assert_eq!(mesh.normals.len(), shell_point_count);
+    assert_eq!(mesh.uvs.len(), shell_point_count);
+
+    assert_unit_normals(mesh.into());
+    assert_clockwise_from_outside(mesh.into(), |centroid| centroid);
+
+    let mut end = 0;
+    for (face, quad_count) in cube.faces.iter().zip([
+        z * y, z * y,
+        x * z, x * z,
+        x * y, x * y,
+    ]) {
+        assert_eq!(face.start, end);
+        assert_eq!(face.len(), quad_count * 6);
+        end = face.end;
+    }
+    assert_eq!(end, mesh.indices.len());
+}
+
+#[test]
+fn rounded_cubes_are_valid() {
+    for (segments, roundness) in [
+        ((1, 1, 1), 0.),
+        ((4, 6, 8), 0.25),
+        ((10, 10, 10), 1.),
+        ((3, 2, 1), 100.),
+    ] {
+        let cube = gen_rounded_cube_mesh(
+            Scale { x: 1., y: 1.5, z: 2. },
+            segments,
+            roundness,
+        );
+
+        assert_valid(&cube, segments);
+    }
+}
+
+#[test]
+fn fully_rounded_cubes_are_spheres() {
+    let cube = gen_rounded_cube_mesh(Scale { x: 2., y: 2., z: 2. }, (8, 8, 8), 2.);
+
+    for (&point, &normal) in cube.mesh.points.iter().zip(cube.mesh.normals.iter()) {
+        let point = Vec3::from(point);
+
+        assert_approx_eq!(point.length(), 2.);
+        assert_approx_eq!(point * 0.5, Vec3::from(normal));
+    }
+}
+
+#[test]
+fn unrounded_cube_faces_are_flat() {
+    let cube = gen_rounded_cube_mesh(Scale { x: 1., y: 1., z: 1. }, (2, 2, 2), 0.);
+
+    // The middle of the `+x` face.
+    let i = cube.mesh.points.iter()
+        .position(|p| Vec3::from(*p) == vec3!(1., 0., 0.))
+        .unwrap();
+
+    assert_eq!(Vec3::from(cube.mesh.normals[i]), vec3!(x));
+
+    for &index in &cube.mesh.indices[cube.faces[0].clone()] {
+        assert_eq!(cube.mesh.points[index as usize].x, 1.);
+    }
+}
@@ -0,0 +1,378 @@
+use std::collections::HashMap;
+
+use crate::{
+    vec3::{Normal, Vec3, vec3},
+    vec4::Vec4,
+};
+
+use super::{Coord, Index, Mesh, Point, UV, TAU};
+
+const PI: Coord = core::f32::consts::PI;
+
+fn point_from_vec3(Vec3 { x, y, z }: Vec3) -> Point {
+    Point { x, y, z }
+}
+
+/// A sphere made of rings of latitude and longitude, with `+y` pointing at the
+/// north pole. `longitude_segments` is the number of segments around the equator,
+/// and `latitude_segments` is the number from pole to pole. These are raised to 3
+/// and 2 respectively if they are lower than that.
+///
+/// `u` goes around the sphere, starting and ending at `+x`, and `v` goes from the
+/// south pole to the north pole. The points along the seam where `u` wraps around
+/// are duplicated, so that they can have different UVs. The tangents point around
+/// the sphere, in the direction `u` increases.
+///
+/// # Panics
+/// If the `(longitude_segments + 1) * (latitude_segments + 1)` points do not fit
+/// in an `Index`.
+pub fn gen_uv_sphere_mesh(
+    radius: Coord,
+    longitude_segments: Index,
+    latitude_segments: Index,
+) -> Mesh {
+    let longitude_segments = longitude_segments.max(3);
+    let latitude_segments = latitude_segments.max(2);
+
+    let mut mesh = Mesh::default();
+
+    for latitude in 0..=latitude_segments {
+        let v = latitude as Coord / latitude_segments as Coord;
+        // Down from the north pole.
+        let (polar_sin, polar_cos) = (v * PI).sin_cos();
+
+        for longitude in 0..=longitude_segments {
+            let u = longitude as Coord / longitude_segments as Coord;
+            let (azimuth_sin, azimuth_cos) = (u * TAU).sin_cos();
+
+            let normal = vec3!(
+                polar_sin * azimuth_cos,
+                polar_cos,
+                polar_sin * azimuth_sin,
+            );
+
+            mesh.push_vertex(
+                point_from_vec3(normal * radius),
+                Normal::from(normal),
+                UV { u, v: 1. - v },
+            );
+            // The cross product of the normal and this points south, and `v`
+            // increases going north, hence the `-1.`.
+            mesh.tangents.push(Vec4 { x: -azimuth_sin, y: 0., z: azimuth_cos, w: -1. });
+        }
+    }
+
+    let row_length = longitude_segments + 1;
+
+    for latitude in 0..latitude_segments {
+        for longitude in 0..longitude_segments {
+            // a b
+            // c d
+            let a = latitude * row_length + longitude;
+            let b = a + 1;
+            let c = a + row_length;
+            let d = c + 1;
+
+            // At the poles, one of the triangles in each quad has no area, so we
+            // skip it.
+            if latitude != 0 {
+                mesh.indices.extend([a, c, b]);
+            }
+            if latitude != latitude_segments - 1 {
+                mesh.indices.extend([b, c, d]);
+            }
+        }
+    }
+
+    mesh
+}
+
+/// A sphere made by repeatedly subdividing the triangles of an icosahedron, and
+/// moving the new points out to the surface of the sphere. Each subdivision
+/// quadruples the amount of triangles, starting from 20.
+///
+/// The UVs are a projection of the points onto a cylinder around the `y` axis,
+/// with the same orientation as `gen_uv_sphere_mesh`'s. Points on triangles that
+/// cross the seam where `u` wraps around are duplicated so that the texture does
+/// not get squished across the whole sphere. The rest of the points are shared
+/// between triangles.
+///
+/// The duplicated points get a `u` greater than 1, rather than wrapping back to
+/// 0, so textures should be sampled with a repeating wrap mode. With a clamping
+/// one, like `ClampToEdge`, the triangles along the seam get stretched out edge
+/// pixels, which shows up as a visible seam.
+///
+/// # Panics
+/// If the amount of points does not fit in an `Index`, which is the case from 7
+/// subdivisions up.
+pub fn gen_icosphere_mesh(radius: Coord, subdivisions: u8) -> Mesh {
+    // The golden ratio.
+    let t = (1. + (5. as Coord).sqrt()) / 2.;
+    // `vec3!` would try to parse `-t` as a literal.
+    let neg_t = -t;
+
+    let mut normals: Vec<Vec3> = [
+        vec3!(-1., t, 0.), vec3!(1., t, 0.), vec3!(-1., neg_t, 0.), vec3!(1., neg_t, 0.),
+        vec3!(0., -1., t), vec3!(0., 1., t), vec3!(0., -1., neg_t), vec3!(0., 1., neg_t),
+        vec3!(t, 0., -1.), vec3!(t, 0., 1.), vec3!(neg_t, 0., -1.), vec3!(neg_t, 0., 1.),
+    ].into_iter().map(Vec3::normalize).collect();
+
+    let mut triangles: Vec<[Index; 3]> = vec![
+        [0, 5, 11], [0, 1, 5], [0, 7, 1], [0, 10, 7], [0, 11, 10],
+        [1, 9, 5], [5, 4, 11], [11, 2, 10], [10, 6, 7], [7, 8, 1],
+        [3, 4, 9], [3, 2, 4], [3, 6, 2], [3, 8, 6], [3, 9, 8],
+        [4, 5, 9], [2, 11, 4], [6, 10, 2], [8, 7, 6], [9, 1, 8],
+    ];
+
+    for _ in 0..subdivisions {
+        // Neighbouring triangles share edges, so we reuse the midpoints.
+        let mut midpoints: HashMap<(Index, Index), Index> = HashMap::new();
+
+        let mut midpoint = |a: Index, b: Index| -> Index {
+            let key = (a.min(b), a.max(b));
+
+            *midpoints.entry(key).or_insert_with(|| {
+                let index = Index::try_from(normals.len())
+                    .expect("icosphere should have few enough points to fit in an Index");
+
+                normals.push(
+                    (normals[a as usize] + normals[b as usize]).normalize()
+                );
+
+                index
+            })
+        };
+
+        triangles = triangles.into_iter().flat_map(|[a, b, c]| {
+            let ab = midpoint(a, b);
+            let bc = midpoint(b, c);
+            let ca = midpoint(c, a);
+
+            [
+                [a, ab, ca],
+                [b, bc, ab],
+                [c, ca, bc],
+                [ab, bc, ca],
+            ]
+        }).collect();
+    }
+
+    let mut mesh = Mesh::default();
+
+    for &normal in normals.iter() {
+        mesh.push_vertex(
+            point_from_vec3(normal * radius),
+            Normal::from(normal),
+            cylindrical_uv(normal),
+        );
+    }
+
+    // Points that have been duplicated so that their `u` is one higher.
+    let mut wrapped: HashMap<Index, Index> = HashMap::new();
+
+    for triangle in triangles {
+        let us = triangle.map(|index| mesh.uvs[index as usize].u);
+
+        let min_u = us.iter().copied().fold(Coord::INFINITY, Coord::min);
+        let max_u = us.iter().copied().fold(Coord::NEG_INFINITY, Coord::max);
+
+        let triangle = if max_u - min_u > 0.5 {
+            triangle.map(|index| {
+                if mesh.uvs[index as usize].u >= 0.5 {
+                    return index
+                }
+
+                *wrapped.entry(index).or_insert_with(|| {
+                    let i = index as usize;
+                    let UV { u, v } = mesh.uvs[i];
+
+                    mesh.push_vertex(mesh.points[i], mesh.normals[i], UV { u: u + 1., v })
+                })
+            })
+        } else {
+            triangle
+        };
+
+        mesh.indices.extend(triangle);
+    }
+
+    mesh
+}
+
+fn cylindrical_uv(normal: Vec3) -> UV {
+    // Negating both arguments shifts the angle by half a turn, so after adding
+    // `0.5` the result goes from 0 to 1, with 0 at `+x`.
+    let u = (-normal.z).atan2(-normal.x) / TAU + 0.5;
+
+    UV {
+        u: if u >= 1. { 0. } else { u },
+        v: normal.y.clamp(-1., 1.).asin() / PI + 0.5,
+    }
+}
+
+/// A sphere made by dividing each face of a cube into a `grid_size` by
+/// `grid_size` grid of quads, and moving the points out to the surface of the
+/// sphere. The points are mapped so that the quads end up closer to being the
+/// same size than they would be by just normalizing them, as described in the
+/// Catlike Coding "Cube Sphere" tutorial.
+///
+/// Each face has its own points, with UVs that go from 0 to 1 across the face.
+/// `grid_size` is raised to 1 if it is lower than that.
+pub fn gen_cube_sphere_mesh(radius: Coord, grid_size: Index) -> Mesh {
+    let grid_size = grid_size.max(1);
+
+    // Each face is given by its normal, and the directions that `u` and `v`
+    // increase in. `u` cross `v` is the normal for each.
+    const FACES: [(Vec3, Vec3, Vec3); 6] = [
+        (vec3!(x), vec3!(-z), vec3!(y)),
+        (vec3!(-x), vec3!(z), vec3!(y)),
+        (vec3!(y), vec3!(x), vec3!(-z)),
+        (vec3!(-y), vec3!(x), vec3!(z)),
+        (vec3!(z), vec3!(x), vec3!(y)),
+        (vec3!(-z), vec3!(-x), vec3!(y)),
+    ];
+
+    let mut mesh = Mesh::default();
+
+    let row_length = grid_size + 1;
+
+    for (normal, u_direction, v_direction) in FACES {
+        let face_start = Index::try_from(mesh.points.len())
+            .expect("cube sphere should have few enough points to fit in an Index");
+
+        for row in 0..=grid_size {
+            let v = row as Coord / grid_size as Coord;
+
+            for column in 0..=grid_size {
+                let u = column as Coord / grid_size as Coord;
+
+                let on_cube = normal
+                    + u_direction * (u * 2. - 1.)
+                    + v_direction * (v * 2. - 1.);
+
+                let on_sphere = cube_to_sphere(on_cube);
+
+                mesh.push_vertex(
+                    point_from_vec3(on_sphere * radius),
+                    Normal::from(on_sphere),
+                    UV { u, v },
+                );
+            }
+        }
+
+        for row in 0..grid_size {
+            for column in 0..grid_size {
+                // c d
+                // a b
+                let a = face_start + row * row_length + column;
+                let b = a + 1;
+                let c = a + row_length;
+                let d = c + 1;
+
+                mesh.indices.extend([a, c, b, b, c, d]);
+            }
+        }
+    }
+
+    mesh
+}
+
+/// Maps a point on the surface of the cube from -1 to 1 on each axis, to the unit
+/// sphere.
+fn cube_to_sphere(Vec3 { x, y, z }: Vec3) -> Vec3 {
+    let (x2, y2, z2) = (x * x, y * y, z * z);
+
+    vec3!(
+        x * (1. - y2 / 2. - z2 / 2. + y2 * z2 / 3.).sqrt(),
+        y * (1. - x2 / 2. - z2 / 2. + x2 * z2 / 3.).sqrt(),
+        z * (1. - x2 / 2. - y2 / 2. + x2 * y2 / 3.).sqrt(),
+    )
+}
+
+#[cfg(test)]
+fn assert_is_sphere(mesh: &Mesh, radius: Coord) {
+    use super::{assert_clockwise_from_outside, assert_unit_normals};
+
+    assert_eq!(mesh.points.len(), mesh.normals.len());
+    assert_eq!(mesh.points.len(), mesh.uvs.len());
+    assert_eq!(mesh.indices.len() % 3, 0);
+
+    assert_unit_normals(mesh.into());
+    assert_clockwise_from_outside(mesh.into(), |centroid| centroid);
+
+    for (&point, &normal) in mesh.points.iter().zip(mesh.normals.iter()) {
+        let point = Vec3::from(point);
+
+        assert_approx_eq!(point.length(), radius);
+        assert_approx_eq!(point * (1. / radius), Vec3::from(normal));
+    }
+
+    for uv in mesh.uvs.iter() {
+        assert!((0. ..=1.).contains(&uv.v), "{uv:?}");
+    }
+}
+
+#[test]
+fn uv_spheres_are_spheres() {
+    for (longitude_segments, latitude_segments) in [(3, 2), (16, 8), (24, 17)] {
+        let mesh = gen_uv_sphere_mesh(2., longitude_segments, latitude_segments);
+
+        assert_is_sphere(&mesh, 2.);
+        assert_eq!(
+            mesh.indices.len(),
+            (longitude_segments * (latitude_segments - 1) * 2 * 3) as usize
+        );
+    }
+}
+
+#[test]
+fn uv_sphere_tangents_follow_the_uvs() {
+    let mesh = gen_uv_sphere_mesh(1., 8, 4);
+
+    assert_eq!(mesh.tangents.len(), mesh.points.len());
+
+    for ((tangent, normal), uv) in mesh.tangents.iter().zip(&mesh.normals).zip(&mesh.uvs) {
+        let normal = Vec3::from(*normal);
+        let bitangent = normal.cross(tangent.xyz()) * tangent.w;
+
+        assert!(normal.dot(tangent.xyz()).abs() < 0.0001, "{tangent:?} {normal:?}");
+        // At the poles the bitangent has no length.
+        if uv.v > 0. && uv.v < 1. {
+            assert!(bitangent.y > 0., "{bitangent:?} at {uv:?}");
+        }
+    }
+}
+
+#[test]
+fn icospheres_are_spheres() {
+    for subdivisions in 0..4 {
+        let mesh = gen_icosphere_mesh(0.5, subdivisions);
+
+        assert_is_sphere(&mesh, 0.5);
+        assert_eq!(mesh.indices.len(), 20 * 4usize.pow(subdivisions as u32) * 3);
+    }
+}
+
+#[test]
+fn icosphere_triangles_do_not_span_the_uv_seam() {
+    let mesh = gen_icosphere_mesh(1., 2);
+
+    for triangle in mesh.indices.chunks_exact(3) {
+        let us = [0, 1, 2].map(|i| mesh.uvs[triangle[i] as usize].u);
+
+        let min_u = us.iter().copied().fold(Coord::INFINITY, Coord::min);
+        let max_u = us.iter().copied().fold(Coord::NEG_INFINITY, Coord::max);
+
+        assert!(max_u - min_u <= 0.5, "{us:?}");
+    }
+}
+
+#[test]
+fn cube_spheres_are_spheres() {
+    for grid_size in [1, 2, 10] {
+        let mesh = gen_cube_sphere_mesh(3., grid_size);
+
+        assert_is_sphere(&mesh, 3.);
+        assert_eq!(mesh.indices.len(), (6 * grid_size * grid_size * 2 * 3) as usize);
+    }
+}
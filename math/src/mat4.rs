@@ -1,6 +1,7 @@
 use core::ops::{Index, IndexMut, Mul, MulAssign};
 use crate::{
     angle::Angle,
+    quat::Quat,
     vec3::{vec3, Vec3},
     vec4::{Vec4},
 };
@@ -237,6 +238,15 @@ impl Mul<Vec4> for Mat4 {
     }
 }
 
+/// Expressed in distance from camera. `(Near, Far)`
+pub type ClipPlanes = (Element, Element);
+
+/// Expressed in distance from camera. Used by projections without a far plane.
+pub type NearPlane = Element;
+
+/// Width / Height
+pub type AspectRatio = Element;
+
 impl Mat4 {
     pub fn diagonal(value: f32) -> Self {
         Self([
@@ -260,19 +270,78 @@ impl Mat4 {
 
         output
     }
-}
 
-/// Expressed in distance from camera. `(Near, Far)`
-pub type ClipPlanes = (Element, Element);
+    pub fn perspective(
+        field_of_view: impl Angle,
+        aspect_ratio: AspectRatio,
+        (near, far): ClipPlanes
+    ) -> Self {
+        let mut output = Self::perspective_x_y(field_of_view, aspect_ratio);
 
-/// Width / Height
-pub type AspectRatio = Element;
+        output[_2_2] = (near + far) / (near - far);
+        output[_2_3] = (2. * near * far) / (near - far);
 
-impl Mat4 {
-    pub fn perspective(
+        output
+    }
+
+    /// Like `perspective`, but with the far plane infinitely far away, so nothing
+    /// is ever clipped for being too far from the camera.
+    pub fn perspective_infinite(
+        field_of_view: impl Angle,
+        aspect_ratio: AspectRatio,
+        near: NearPlane,
+    ) -> Self {
+        let mut output = Self::perspective_x_y(field_of_view, aspect_ratio);
+
+        output[_2_2] = -1.;
+        output[_2_3] = -2. * near;
+
+        output
+    }
+
+    /// Like `perspective`, but mapping the near plane to a depth of 1 and the far
+    /// plane to a depth of -1, instead of the other way around. Use it with a
+    /// depth compare function of `CompareFunc::GreaterEqual`, and a depth buffer
+    /// cleared to 0.
+    ///
+    /// Reversed depth is usually used for its better precision with float depth
+    /// buffers, but that only comes with a clip space depth range of 0 to 1.
+    /// `sokol_gfx`'s GL backends use -1 to 1, which loses that benefit, so there
+    /// this mostly just flips the depth comparisons.
+    pub fn perspective_reversed_z(
         field_of_view: impl Angle,
         aspect_ratio: AspectRatio,
         (near, far): ClipPlanes
+    ) -> Self {
+        let mut output = Self::perspective_x_y(field_of_view, aspect_ratio);
+
+        output[_2_2] = (near + far) / (far - near);
+        output[_2_3] = (2. * near * far) / (far - near);
+
+        output
+    }
+
+    /// A combination of `perspective_infinite` and `perspective_reversed_z`. So,
+    /// it maps the near plane to a depth of 1, and points infinitely far away to
+    /// a depth of -1.
+    pub fn perspective_infinite_reversed_z(
+        field_of_view: impl Angle,
+        aspect_ratio: AspectRatio,
+        near: NearPlane,
+    ) -> Self {
+        let mut output = Self::perspective_x_y(field_of_view, aspect_ratio);
+
+        output[_2_2] = 1.;
+        output[_2_3] = 2. * near;
+
+        output
+    }
+
+    /// The parts that all of the perspective projections share. Note that the
+    /// field of view is the horizontal one, to match `perspective`.
+    fn perspective_x_y(
+        field_of_view: impl Angle,
+        aspect_ratio: AspectRatio,
     ) -> Self {
         let mut output = Self::default();
 
@@ -283,13 +352,44 @@ impl Mat4 {
         output[_0_0] = 1. / tan_theta_over_2;
         output[_1_1] = aspect_ratio / tan_theta_over_2;
         output[_3_2] = -1.;
-        output[_2_2] = (near + far) / (near - far);
-        output[_2_3] = (2. * near * far) / (near - far);
-        output[_3_3] = 0.;
 
         output
     }
 
+    /// Maps the box with the given sides, where the near and far planes are
+    /// expressed in distance from the camera, to the -1 to 1 cube, without any
+    /// perspective foreshortening.
+    pub fn orthographic(
+        left: Element,
+        right: Element,
+        bottom: Element,
+        top: Element,
+        (near, far): ClipPlanes,
+    ) -> Self {
+        let mut output = Self::identity();
+
+        output[_0_0] = 2. / (right - left);
+        output[_1_1] = 2. / (top - bottom);
+        output[_2_2] = -2. / (far - near);
+        output[_0_3] = -(right + left) / (right - left);
+        output[_1_3] = -(top + bottom) / (top - bottom);
+        output[_2_3] = -(far + near) / (far - near);
+
+        output
+    }
+
+    /// An `orthographic` projection centered on the camera, where `size` is half
+    /// the height of the view, like Unity's `Camera.orthographicSize`.
+    pub fn orthographic_from_size(
+        size: Element,
+        aspect_ratio: AspectRatio,
+        clip_planes: ClipPlanes,
+    ) -> Self {
+        let half_width = size * aspect_ratio;
+
+        Self::orthographic(-half_width, half_width, -size, size, clip_planes)
+    }
+
     pub fn look_at(
         eye: Vec3,
         center: Vec3,
@@ -358,6 +458,17 @@ impl Mat4 {
         output
     }
 
+    /// The same matrix, but without moving points. For example, to draw a
+    /// skybox with a view matrix, so it stays around the camera as the camera
+    /// moves.
+    pub fn without_translation(mut self) -> Self {
+        self[_0_3] = 0.;
+        self[_1_3] = 0.;
+        self[_2_3] = 0.;
+
+        self
+    }
+
     pub fn transpose(self) -> Self {
         Self([
             self[_0_0], self[_1_0], self[_2_0], self[_3_0],
@@ -366,5 +477,396 @@ impl Mat4 {
             self[_0_3], self[_1_3], self[_2_3], self[_3_3],
         ])
     }
+
+    pub fn determinant(self) -> Element {
+        let (a, b) = self.sub_determinants();
+
+        determinant_from_sub_determinants(a, b)
+    }
+
+    /// Returns `None` if the matrix is singular, that is, if it has no inverse.
+    /// If the matrix is known to be affine, then `affine_inverse` is cheaper.
+    pub fn inverse(self) -> Option<Self> {
+        let (a, b) = self.sub_determinants();
+
+        let determinant = determinant_from_sub_determinants(a, b);
+
+        if determinant == 0. || !determinant.is_finite() {
+            return None;
+        }
+
+        let m = self;
+
+        let mut output = Self([
+            m[_1_1] * b[5] - m[_1_2] * b[4] + m[_1_3] * b[3],
+            -m[_0_1] * b[5] + m[_0_2] * b[4] - m[_0_3] * b[3],
+            m[_3_1] * a[5] - m[_3_2] * a[4] + m[_3_3] * a[3],
+            -m[_2_1] * a[5] + m[_2_2] * a[4] - m[_2_3] * a[3],
+
+            -m[_1_0] * b[5] + m[_1_2] * b[2] - m[_1_3] * b[1],
+            m[_0_0] * b[5] - m[_0_2] * b[2] + m[_0_3] * b[1],
+            -m[_3_0] * a[5] + m[_3_2] * a[2] - m[_3_3] * a[1],
+            m[_2_0] * a[5] - m[_2_2] * a[2] + m[_2_3] * a[1],
+
+            m[_1_0] * b[4] - m[_1_1] * b[2] + m[_1_3] * b[0],
+            -m[_0_0] * b[4] + m[_0_1] * b[2] - m[_0_3] * b[0],
+            m[_3_0] * a[4] - m[_3_1] * a[2] + m[_3_3] * a[0],
+            -m[_2_0] * a[4] + m[_2_1] * a[2] - m[_2_3] * a[0],
+
+            -m[_1_0] * b[3] + m[_1_1] * b[1] - m[_1_2] * b[0],
+            m[_0_0] * b[3] - m[_0_1] * b[1] + m[_0_2] * b[0],
+            -m[_3_0] * a[3] + m[_3_1] * a[1] - m[_3_2] * a[0],
+            m[_2_0] * a[3] - m[_2_1] * a[1] + m[_2_2] * a[0],
+        ]);
+
+        let one_over_determinant = 1. / determinant;
+        for element in output.0.iter_mut() {
+            *element *= one_over_determinant;
+        }
+
+        Some(output)
+    }
+
+    /// The determinants of the 2x2 sub-matrices of the top two rows, and of the
+    /// bottom two rows, respectively. These are shared between the determinant and
+    /// inverse calculations.
+    fn sub_determinants(self) -> ([Element; 6], [Element; 6]) {
+        let m = self;
+
+        (
+            [
+                m[_0_0] * m[_1_1] - m[_0_1] * m[_1_0],
+                m[_0_0] * m[_1_2] - m[_0_2] * m[_1_0],
+                m[_0_0] * m[_1_3] - m[_0_3] * m[_1_0],
+                m[_0_1] * m[_1_2] - m[_0_2] * m[_1_1],
+                m[_0_1] * m[_1_3] - m[_0_3] * m[_1_1],
+                m[_0_2] * m[_1_3] - m[_0_3] * m[_1_2],
+            ],
+            [
+                m[_2_0] * m[_3_1] - m[_2_1] * m[_3_0],
+                m[_2_0] * m[_3_2] - m[_2_2] * m[_3_0],
+                m[_2_0] * m[_3_3] - m[_2_3] * m[_3_0],
+                m[_2_1] * m[_3_2] - m[_2_2] * m[_3_1],
+                m[_2_1] * m[_3_3] - m[_2_3] * m[_3_1],
+                m[_2_2] * m[_3_3] - m[_2_3] * m[_3_2],
+            ],
+        )
+    }
+
+    /// A cheaper version of `inverse` that only works on affine matrices. That is,
+    /// ones where the bottom row is `0, 0, 0, 1`, like the ones produced by
+    /// multiplying `translate`, `rotation` and `scale` matrices together. The
+    /// bottom row is assumed to be `0, 0, 0, 1` without being checked.
+    /// Returns `None` if the matrix is singular, for example if it scales an axis
+    /// down to zero.
+    pub fn affine_inverse(self) -> Option<Self> {
+        let m = self;
+
+        // Cofactors of the upper-left 3x3 part.
+        let c00 = m[_1_1] * m[_2_2] - m[_1_2] * m[_2_1];
+        let c01 = m[_1_2] * m[_2_0] - m[_1_0] * m[_2_2];
+        let c02 = m[_1_0] * m[_2_1] - m[_1_1] * m[_2_0];
+
+        let determinant = m[_0_0] * c00 + m[_0_1] * c01 + m[_0_2] * c02;
+
+        if determinant == 0. || !determinant.is_finite() {
+            return None;
+        }
+
+        let d = 1. / determinant;
+
+        let mut output = Self::identity();
+
+        output[_0_0] = c00 * d;
+        output[_0_1] = (m[_0_2] * m[_2_1] - m[_0_1] * m[_2_2]) * d;
+        output[_0_2] = (m[_0_1] * m[_1_2] - m[_0_2] * m[_1_1]) * d;
+
+        output[_1_0] = c01 * d;
+        output[_1_1] = (m[_0_0] * m[_2_2] - m[_0_2] * m[_2_0]) * d;
+        output[_1_2] = (m[_0_2] * m[_1_0] - m[_0_0] * m[_1_2]) * d;
+
+        output[_2_0] = c02 * d;
+        output[_2_1] = (m[_0_1] * m[_2_0] - m[_0_0] * m[_2_1]) * d;
+        output[_2_2] = (m[_0_0] * m[_1_1] - m[_0_1] * m[_1_0]) * d;
+
+        // The inverse translation is the original translation, negated, then
+        // transformed by the inverse of the upper-left 3x3 part.
+        let (tx, ty, tz) = (m[_0_3], m[_1_3], m[_2_3]);
+        for row in 0..3 {
+            output[row_col!(row, 3)] = -(
+                output[row_col!(row, 0)] * tx
+                + output[row_col!(row, 1)] * ty
+                + output[row_col!(row, 2)] * tz
+            );
+        }
+
+        Some(output)
+    }
+
+    /// The inverse of `decompose`. The output applies the scale first, then the
+    /// rotation, then the translation. That is, the output is equal to
+    /// `Mat4::translate(translation) * Mat4::from(rotation) * Mat4::scale(scale)`.
+    pub fn compose(translation: Vec3, rotation: Quat, scale: Vec3) -> Self {
+        let mut output = rotation.to_mat4();
+
+        for row in 0..3 {
+            output[row_col!(row, 0)] *= scale.x;
+            output[row_col!(row, 1)] *= scale.y;
+            output[row_col!(row, 2)] *= scale.z;
+        }
+
+        output[_0_3] = translation.x;
+        output[_1_3] = translation.y;
+        output[_2_3] = translation.z;
+
+        output
+    }
+
+    /// Splits an affine matrix into a translation, a rotation and a scale, such
+    /// that passing them to `compose` gives back the original matrix. Any shear in
+    /// the matrix is lost, since it can't be represented by those parts.
+    ///
+    /// If the matrix flips the handedness of the space, (that is, it has a
+    /// negative determinant,) then the flip is represented by a negative x scale.
+    pub fn decompose(self) -> (Vec3, Quat, Vec3) {
+        let translation = vec3!(self[_0_3], self[_1_3], self[_2_3]);
+
+        let (x_axis, y_axis, z_axis) = (self.x_axis(), self.y_axis(), self.z_axis());
+
+        let mut scale = vec3!(x_axis.length(), y_axis.length(), z_axis.length());
+
+        if x_axis.cross(y_axis).dot(z_axis) < 0. {
+            scale.x = -scale.x;
+        }
+
+        // `Vec3::normalize` leaves zero length axes alone, so a zero scale results
+        // in a rotation that is as sensible as we can manage.
+        let rotation = Quat::from_mat4(Self::from_columns(
+            if scale.x == 0. { x_axis } else { x_axis * (1. / scale.x) },
+            y_axis.normalize(),
+            z_axis.normalize(),
+        ));
+
+        (translation, rotation, scale)
+    }
+
+    fn from_columns(x_axis: Vec3, y_axis: Vec3, z_axis: Vec3) -> Self {
+        Self([
+            x_axis.x, y_axis.x, z_axis.x, 0.,
+            x_axis.y, y_axis.y, z_axis.y, 0.,
+            x_axis.z, y_axis.z, z_axis.z, 0.,
+            0., 0., 0., 1.,
+        ])
+    }
+}
+
+fn determinant_from_sub_determinants(a: [Element; 6], b: [Element; 6]) -> Element {
+    a[0] * b[5] - a[1] * b[4] + a[2] * b[3]
+    + a[3] * b[2] - a[4] * b[1] + a[5] * b[0]
+}
+
+#[cfg(test)]
+use crate::angle::{Degrees, Radians};
+
+#[cfg(test)]
+fn known_affine_matrices() -> [Mat4; 4] {
+    let trs = Mat4::translate(vec3!(4., 5., 6.))
+        * Mat4::rotation(Degrees(30.), vec3!(1., 2., 3.))
+        * Mat4::scale(vec3!(2., 3., 0.5));
+
+    // Like a clock hand.
+    let rts = Mat4::rotation(Radians(PI), vec3!(y))
+        * Mat4::translate(vec3!(0., 0.25, 0.25))
+        * Mat4::scale(vec3!(0.1, 5., 0.1));
+
+    [
+        Mat4::identity(),
+        Mat4::translate(vec3!(1., -2., 3.)),
+        trs,
+        rts,
+    ]
+}
+
+#[test]
+fn determinant_works_on_these_known_matrices() {
+    assert_eq!(Mat4::identity().determinant(), 1.);
+    assert_eq!(Mat4::scale(vec3!(2., 3., 4.)).determinant(), 24.);
+    assert_eq!(Mat4::translate(vec3!(2., 3., 4.)).determinant(), 1.);
+    assert_approx_eq!(Mat4::rotation(Degrees(45.), vec3!(1., 1., 0.)).determinant(), 1.);
+
+    assert_eq!(
+        Mat4([
+            2.,3.,5.,7.,
+            11.,13.,17.,19.,
+            23.,29.,31.,37.,
+            41.,43.,47.,53.,
+        ]).determinant(),
+        880.
+    );
 }
 
+#[test]
+fn inverse_times_the_original_is_the_identity() {
+    let general = Mat4([
+        2.,3.,5.,7.,
+        11.,13.,17.,19.,
+        23.,29.,31.,37.,
+        41.,43.,47.,53.,
+    ]);
+    let projection = Mat4::perspective(Degrees(60.), 4./3., (0.01, 100.));
+
+    for m in known_affine_matrices().into_iter().chain([general, projection]) {
+        let inverse = m.inverse().unwrap();
+
+        assert_approx_eq!(inverse * m, Mat4::identity());
+        assert_approx_eq!(m * inverse, Mat4::identity());
+    }
+}
+
+#[test]
+fn inverse_of_a_singular_matrix_is_none() {
+    assert_eq!(Mat4::default().inverse(), None);
+    assert_eq!(Mat4::scale(vec3!(1., 0., 1.)).inverse(), None);
+    assert_eq!(Mat4::scale(vec3!(1., 0., 1.)).affine_inverse(), None);
+    // The last row is a copy of the first.
+    assert_eq!(
+        Mat4([
+            1.,2.,3.,4.,
+            5.,6.,7.,8.,
+            9.,10.,11.,12.,
+            1.,2.,3.,4.,
+        ]).inverse(),
+        None
+    );
+}
+
+#[test]
+fn affine_inverse_matches_inverse() {
+    for m in known_affine_matrices() {
+        assert_approx_eq!(m.affine_inverse().unwrap(), m.inverse().unwrap());
+    }
+}
+
+#[test]
+fn decompose_then_compose_gives_back_the_original() {
+    for m in known_affine_matrices() {
+        let (translation, rotation, scale) = m.decompose();
+
+        assert_approx_eq!(Mat4::compose(translation, rotation, scale), m);
+    }
+}
+
+#[test]
+fn decompose_recovers_the_parts_of_this_example() {
+    let rotation = Quat::from_axis_angle(Degrees(30.), vec3!(1., 2., 3.));
+
+    let m = Mat4::translate(vec3!(4., 5., 6.))
+        * Mat4::from(rotation)
+        * Mat4::scale(vec3!(2., 3., 0.5));
+
+    let (t, r, s) = m.decompose();
+
+    assert_approx_eq!(t, vec3!(4., 5., 6.));
+    assert_approx_eq!(r, rotation);
+    assert_approx_eq!(s, vec3!(2., 3., 0.5));
+}
+
+#[test]
+fn decompose_represents_a_flip_as_a_negative_x_scale() {
+    let m = Mat4::scale(vec3!(-2., 1., 1.));
+
+    let (_, r, s) = m.decompose();
+
+    assert_approx_eq!(s, vec3!(-2., 1., 1.));
+    assert_approx_eq!(r, Quat::identity());
+}
+
+#[cfg(test)]
+/// Returns the normalized device coordinates of `point` after `projection`.
+fn project(projection: Mat4, point: Vec3) -> Vec3 {
+    let clip = projection * crate::vec4::vec4!(point.x, point.y, point.z, 1.);
+
+    clip.xyz() * (1. / clip.w)
+}
+
+#[test]
+fn perspective_projects_these_known_points() {
+    let p = Mat4::perspective(Degrees(90.), 2., (1., 10.));
+
+    assert_approx_eq!(project(p, vec3!(0., 0., -1.)), vec3!(0., 0., -1.));
+    assert_approx_eq!(project(p, vec3!(0., 0., -10.)), vec3!(0., 0., 1.));
+    // With a 90 degree horizontal field of view, the edge of the view is where x
+    // is the same distance from the center as the point is from the camera.
+    assert_approx_eq!(project(p, vec3!(5., 2.5, -5.)), vec3!(1., 1., project(p, vec3!(0., 0., -5.)).z));
+}
+
+#[test]
+fn perspective_infinite_projects_these_known_points() {
+    let p = Mat4::perspective_infinite(Degrees(90.), 2., 1.);
+
+    assert_approx_eq!(project(p, vec3!(0., 0., -1.)), vec3!(0., 0., -1.));
+    assert_approx_eq!(project(p, vec3!(-3., 1.5, -3.)), vec3!(-1., 1., project(p, vec3!(0., 0., -3.)).z));
+
+    // Far away points approach, but stay inside, the far end of the depth range.
+    let far = project(p, vec3!(0., 0., -16777216.)).z;
+    assert!(far < 1., "{far}");
+    assert_approx_eq!(far, 1.);
+
+    // Depth still increases with distance.
+    assert!(project(p, vec3!(0., 0., -100.)).z < project(p, vec3!(0., 0., -1000.)).z);
+}
+
+#[test]
+fn perspective_reversed_z_projects_these_known_points() {
+    let p = Mat4::perspective_reversed_z(Degrees(90.), 2., (1., 10.));
+
+    assert_approx_eq!(project(p, vec3!(0., 0., -1.)), vec3!(0., 0., 1.));
+    assert_approx_eq!(project(p, vec3!(0., 0., -10.)), vec3!(0., 0., -1.));
+    assert_approx_eq!(project(p, vec3!(5., -2.5, -5.)), vec3!(1., -1., project(p, vec3!(0., 0., -5.)).z));
+
+    // The depths are the same as the regular perspective ones, but flipped.
+    let regular = Mat4::perspective(Degrees(90.), 2., (1., 10.));
+    assert_approx_eq!(
+        project(p, vec3!(0., 0., -3.)).z,
+        -project(regular, vec3!(0., 0., -3.)).z
+    );
+}
+
+#[test]
+fn perspective_infinite_reversed_z_projects_these_known_points() {
+    let p = Mat4::perspective_infinite_reversed_z(Degrees(90.), 1., 0.5);
+
+    assert_approx_eq!(project(p, vec3!(0., 0., -0.5)), vec3!(0., 0., 1.));
+    assert_approx_eq!(project(p, vec3!(2., 2., -2.)), vec3!(1., 1., project(p, vec3!(0., 0., -2.)).z));
+
+    let far = project(p, vec3!(0., 0., -16777216.)).z;
+    assert!(far > -1., "{far}");
+    assert_approx_eq!(far, -1.);
+}
+
+#[test]
+fn orthographic_projects_these_known_points() {
+    let p = Mat4::orthographic(-1., 3., -2., 2., (1., 5.));
+
+    assert_approx_eq!(project(p, vec3!(-1., -2., -1.)), vec3!(-1., -1., -1.));
+    assert_approx_eq!(project(p, vec3!(3., 2., -5.)), vec3!(1., 1., 1.));
+    assert_approx_eq!(project(p, vec3!(1., 0., -3.)), vec3!(0., 0., 0.));
+    // No perspective foreshortening.
+    assert_approx_eq!(project(p, vec3!(3., 2., -1.)), vec3!(1., 1., -1.));
+}
+
+#[test]
+fn orthographic_from_size_projects_these_known_points() {
+    let p = Mat4::orthographic_from_size(5., 2., (0., 10.));
+
+    assert_approx_eq!(p, Mat4::orthographic(-10., 10., -5., 5., (0., 10.)));
+    assert_approx_eq!(project(p, vec3!(10., -5., 0.)), vec3!(1., -1., -1.));
+}
+
+#[test]
+fn without_translation_keeps_the_rotation() {
+    let rotation = Mat4::rotation(Radians(1.), vec3!(1., 2., 3.));
+    let view = Mat4::translate(vec3!(4., 5., 6.)) * rotation;
+
+    assert_eq!(view.without_translation(), rotation);
+}
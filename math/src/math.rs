@@ -1,4 +1,17 @@
+// This needs to be defined before the modules that use it in their tests.
+#[cfg(test)]
+macro_rules! assert_approx_eq {
+    ($actual: expr, $expected: expr $(,)?) => {{
+        let (actual, expected) = ($actual, $expected);
+        assert!(
+            $crate::ApproxEq::approx_eq(&actual, &expected),
+            "{actual} was not approximately equal to {expected}"
+        );
+    }}
+}
+
 pub mod mat4;
+pub mod quat;
 pub mod vec3;
 pub mod vec4;
 pub mod angle;
@@ -6,3 +19,60 @@ pub mod geom;
 // I'm not sure whether these will stay in `geom` so we'll commit to making them 
 // available at the root.
 pub use geom::{Point, point, Scale, scale};
+
+#[cfg(test)]
+trait ApproxEq {
+    fn approx_eq(&self, other: &Self) -> bool;
+}
+
+#[cfg(test)]
+fn elements_approx_eq(a: &[f32], b: &[f32]) -> bool {
+    const EPSILON: f32 = 1. / 65536.;
+
+    a.len() == b.len()
+    && a.iter().zip(b.iter()).all(|(a, b)|
+        (a - b).abs() <= EPSILON * a.abs().max(b.abs()).max(1.)
+    )
+}
+
+#[cfg(test)]
+impl ApproxEq for f32 {
+    fn approx_eq(&self, other: &Self) -> bool {
+        elements_approx_eq(&[*self], &[*other])
+    }
+}
+
+#[cfg(test)]
+impl ApproxEq for vec3::Vec3 {
+    fn approx_eq(&self, other: &Self) -> bool {
+        elements_approx_eq(&[self.x, self.y, self.z], &[other.x, other.y, other.z])
+    }
+}
+
+#[cfg(test)]
+impl ApproxEq for vec4::Vec4 {
+    fn approx_eq(&self, other: &Self) -> bool {
+        elements_approx_eq(
+            &[self.x, self.y, self.z, self.w],
+            &[other.x, other.y, other.z, other.w]
+        )
+    }
+}
+
+#[cfg(test)]
+impl ApproxEq for mat4::Mat4 {
+    fn approx_eq(&self, other: &Self) -> bool {
+        elements_approx_eq(&self.to_row_major(), &other.to_row_major())
+    }
+}
+
+#[cfg(test)]
+impl ApproxEq for quat::Quat {
+    /// `q` and `-q` represent the same rotation, so we consider them equal.
+    fn approx_eq(&self, other: &Self) -> bool {
+        let a = [self.x, self.y, self.z, self.w];
+
+        elements_approx_eq(&a, &[other.x, other.y, other.z, other.w])
+        || elements_approx_eq(&a, &[-other.x, -other.y, -other.z, -other.w])
+    }
+}
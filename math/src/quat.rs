@@ -0,0 +1,453 @@
+use core::ops::{Mul, MulAssign, Neg};
+use crate::{
+    angle::Angle,
+    mat4::Mat4,
+    vec3::{vec3, Vec3},
+};
+
+pub type Element = f32;
+
+/// A rotation, stored as a unit quaternion. Compared to a rotation `Mat4`,
+/// these can be composed many times without drifting away from being a pure
+/// rotation, (as long as they are renormalized now and then,) and can be
+/// smoothly interpolated between with `slerp` or `nlerp`.
+///
+/// This is the same concept as the `rotation` exposed by Unity's `Transform`.
+#[derive(Clone, Copy, Debug, PartialEq, PartialOrd)]
+pub struct Quat {
+    pub x: Element,
+    pub y: Element,
+    pub z: Element,
+    pub w: Element,
+}
+
+/// The rotation that does not rotate anything.
+pub const IDENTITY: Quat = Quat {
+    x: 0.,
+    y: 0.,
+    z: 0.,
+    w: 1.,
+};
+
+impl Default for Quat {
+    fn default() -> Self {
+        IDENTITY
+    }
+}
+
+impl core::fmt::Display for Quat {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        write!(f, "({}, {}, {}, {})", self.x, self.y, self.z, self.w)
+    }
+}
+
+impl Quat {
+    pub fn identity() -> Self {
+        IDENTITY
+    }
+
+    /// A rotation of `angle` around `axis`, using the right hand rule. That is,
+    /// the same rotation that `Mat4::rotation` with the same parameters produces.
+    /// The axis does not need to be normalized beforehand.
+    pub fn from_axis_angle(angle: impl Angle, axis: Vec3) -> Self {
+        let axis = axis.normalize();
+
+        let (sin, cos) = (angle.raw_radians() / 2.).sin_cos();
+
+        Self {
+            x: axis.x * sin,
+            y: axis.y * sin,
+            z: axis.z * sin,
+            w: cos,
+        }
+    }
+
+    /// Rotations around the x, y and z axes, applied in the same order as Unity's
+    /// `Quaternion.Euler`: first around z, then around x, and then around y.
+    pub fn from_euler(x: impl Angle, y: impl Angle, z: impl Angle) -> Self {
+        Self::from_axis_angle(y, vec3!(y))
+        * Self::from_axis_angle(x, vec3!(x))
+        * Self::from_axis_angle(z, vec3!(z))
+    }
+
+    /// A rotation that maps the positive z axis onto `forward`, and the positive
+    /// y axis as close to `up` as possible, while staying perpendicular to
+    /// `forward`. If `forward` and `up` are parallel, or either is all zeroes, then
+    /// the identity rotation is returned.
+    pub fn look_rotation(forward: Vec3, up: Vec3) -> Self {
+        let z = forward.normalize();
+        let x = up.cross(z).normalize();
+
+        if x == vec3!() || z == vec3!() {
+            return IDENTITY;
+        }
+
+        let y = z.cross(x);
+
+        Self::from_basis(x, y, z)
+    }
+
+    /// Returns the rotation that the upper-left 3x3 part of `matrix` represents.
+    /// Any translation is ignored. That 3x3 part is expected to be a pure rotation,
+    /// that is, without any scale or shear.
+    pub fn from_mat4(matrix: Mat4) -> Self {
+        Self::from_basis(matrix.x_axis(), matrix.y_axis(), matrix.z_axis())
+    }
+
+    /// Converts the rotation matrix with the given columns into a quaternion.
+    fn from_basis(x_axis: Vec3, y_axis: Vec3, z_axis: Vec3) -> Self {
+        // Named after the row and column of the rotation matrix.
+        let (m00, m10, m20) = (x_axis.x, x_axis.y, x_axis.z);
+        let (m01, m11, m21) = (y_axis.x, y_axis.y, y_axis.z);
+        let (m02, m12, m22) = (z_axis.x, z_axis.y, z_axis.z);
+
+        let trace = m00 + m11 + m22;
+
+        // Branch on the largest component, to avoid dividing by something close
+        // to zero.
+        let output = if trace > 0. {
+            let s = (trace + 1.).sqrt() * 2.;
+            Self {
+                x: (m21 - m12) / s,
+                y: (m02 - m20) / s,
+                z: (m10 - m01) / s,
+                w: s / 4.,
+            }
+        } else if m00 > m11 && m00 > m22 {
+            let s = (1. + m00 - m11 - m22).sqrt() * 2.;
+            Self {
+                x: s / 4.,
+                y: (m01 + m10) / s,
+                z: (m02 + m20) / s,
+                w: (m21 - m12) / s,
+            }
+        } else if m11 > m22 {
+            let s = (1. + m11 - m00 - m22).sqrt() * 2.;
+            Self {
+                x: (m01 + m10) / s,
+                y: s / 4.,
+                z: (m12 + m21) / s,
+                w: (m02 - m20) / s,
+            }
+        } else {
+            let s = (1. + m22 - m00 - m11).sqrt() * 2.;
+            Self {
+                x: (m02 + m20) / s,
+                y: (m12 + m21) / s,
+                z: s / 4.,
+                w: (m10 - m01) / s,
+            }
+        };
+
+        output.normalize()
+    }
+
+    pub fn to_mat4(self) -> Mat4 {
+        let Self { x, y, z, w } = self;
+
+        let (xx, yy, zz) = (x * x, y * y, z * z);
+        let (xy, xz, yz) = (x * y, x * z, y * z);
+        let (wx, wy, wz) = (w * x, w * y, w * z);
+
+        Mat4::from_row_major([
+            1. - 2. * (yy + zz), 2. * (xy - wz), 2. * (xz + wy), 0.,
+            2. * (xy + wz), 1. - 2. * (xx + zz), 2. * (yz - wx), 0.,
+            2. * (xz - wy), 2. * (yz + wx), 1. - 2. * (xx + yy), 0.,
+            0., 0., 0., 1.,
+        ])
+    }
+
+    pub fn xyz(self) -> Vec3 {
+        vec3!(self.x, self.y, self.z)
+    }
+
+    pub fn dot(self, other: Self) -> Element {
+        self.x * other.x
+        + self.y * other.y
+        + self.z * other.z
+        + self.w * other.w
+    }
+
+    pub fn length(self) -> Element {
+        self.length_squared().sqrt()
+    }
+
+    pub fn length_squared(self) -> Element {
+        self.dot(self)
+    }
+
+    /// Returns a new `Quat` that has a length of `1.0`, unless the passed in `Quat`
+    /// is the all zeroes `Quat`. In that case, the identity rotation is returned.
+    pub fn normalize(self) -> Self {
+        let length = self.length();
+
+        // Avoid divide-by-zero
+        if length == 0. {
+            return IDENTITY;
+        }
+
+        self.scale(1. / length)
+    }
+
+    /// For unit quaternions, which all the constructors produce, this is the same
+    /// as `inverse`, but cheaper.
+    pub fn conjugate(self) -> Self {
+        Self {
+            x: -self.x,
+            y: -self.y,
+            z: -self.z,
+            w: self.w,
+        }
+    }
+
+    /// The rotation that undoes this one. If the `Quat` is all zeroes, the
+    /// identity rotation is returned.
+    pub fn inverse(self) -> Self {
+        let length_squared = self.length_squared();
+
+        // Avoid divide-by-zero
+        if length_squared == 0. {
+            return IDENTITY;
+        }
+
+        self.conjugate().scale(1. / length_squared)
+    }
+
+    /// Normalized linear interpolation. Cheaper than `slerp`, but the rotation
+    /// does not proceed at a constant speed as `t` goes from `0.0` to `1.0`.
+    /// Takes the shortest path between the two rotations.
+    pub fn nlerp(self, other: Self, t: Element) -> Self {
+        let other = if self.dot(other) < 0. { -other } else { other };
+
+        self.scale(1. - t).add(other.scale(t)).normalize()
+    }
+
+    /// Spherical linear interpolation. The rotation proceeds at a constant speed
+    /// as `t` goes from `0.0` to `1.0`. Takes the shortest path between the two
+    /// rotations.
+    pub fn slerp(self, other: Self, t: Element) -> Self {
+        let mut cos_theta = self.dot(other);
+
+        let other = if cos_theta < 0. {
+            cos_theta = -cos_theta;
+            -other
+        } else {
+            other
+        };
+
+        // When the rotations are very close together, `sin_theta` gets close to
+        // zero, so we avoid dividing by it. The result is practically the same
+        // in that case anyway.
+        if cos_theta > 0.9995 {
+            return self.nlerp(other, t);
+        }
+
+        let theta = cos_theta.acos();
+        let sin_theta = theta.sin();
+
+        let self_scale = ((1. - t) * theta).sin() / sin_theta;
+        let other_scale = (t * theta).sin() / sin_theta;
+
+        self.scale(self_scale).add(other.scale(other_scale)).normalize()
+    }
+
+    /// Rotate `v` by this rotation.
+    pub fn rotate(self, v: Vec3) -> Vec3 {
+        let u = self.xyz();
+        let uv = u.cross(v);
+        let uuv = u.cross(uv);
+
+        v + ((uv * self.w) + uuv) * 2.
+    }
+
+    // These two are only used internally, since they don't produce rotations.
+    fn scale(self, s: Element) -> Self {
+        Self {
+            x: self.x * s,
+            y: self.y * s,
+            z: self.z * s,
+            w: self.w * s,
+        }
+    }
+
+    fn add(self, other: Self) -> Self {
+        Self {
+            x: self.x + other.x,
+            y: self.y + other.y,
+            z: self.z + other.z,
+            w: self.w + other.w,
+        }
+    }
+}
+
+impl Neg for Quat {
+    type Output = Self;
+
+    /// Note that this produces a `Quat` representing the same rotation.
+    fn neg(self) -> Self::Output {
+        self.scale(-1.)
+    }
+}
+
+/// `a * b` is the rotation that applies `b` first, then `a`, the same as with
+/// `Mat4` multiplication.
+impl Mul for Quat {
+    type Output = Self;
+
+    fn mul(self, other: Self) -> Self::Output {
+        let (a, b) = (self, other);
+        Self {
+            x: a.w * b.x + a.x * b.w + a.y * b.z - a.z * b.y,
+            y: a.w * b.y - a.x * b.z + a.y * b.w + a.z * b.x,
+            z: a.w * b.z + a.x * b.y - a.y * b.x + a.z * b.w,
+            w: a.w * b.w - a.x * b.x - a.y * b.y - a.z * b.z,
+        }
+    }
+}
+
+impl MulAssign for Quat {
+    fn mul_assign(&mut self, other: Self) {
+        *self = *self * other;
+    }
+}
+
+impl Mul<Vec3> for Quat {
+    type Output = Vec3;
+
+    fn mul(self, v: Vec3) -> Self::Output {
+        self.rotate(v)
+    }
+}
+
+impl From<Quat> for Mat4 {
+    fn from(quat: Quat) -> Self {
+        quat.to_mat4()
+    }
+}
+
+impl From<Mat4> for Quat {
+    fn from(matrix: Mat4) -> Self {
+        Self::from_mat4(matrix)
+    }
+}
+
+#[cfg(test)]
+use crate::{
+    angle::{Degrees, Radians, TAU},
+    vec3::Normal,
+};
+
+#[test]
+fn from_axis_angle_matches_mat4_rotation() {
+    for (angle, axis) in [
+        (Radians(TAU / 4.), vec3!(z)),
+        (Radians(1.), vec3!(1., 2., 3.)),
+        (Radians(-2.5), vec3!(-1., 0.5, 0.)),
+        (Radians(TAU / 2.), vec3!(y)),
+    ] {
+        let expected = Mat4::rotation(angle, axis);
+        let actual = Quat::from_axis_angle(angle, axis).to_mat4();
+
+        assert_approx_eq!(actual, expected);
+    }
+}
+
+#[test]
+fn mat4_round_trip_preserves_the_rotation() {
+    // Cover each branch of `from_basis`.
+    for (angle, axis) in [
+        (Radians(0.5), vec3!(1., 1., 1.)),
+        (Radians(3.), vec3!(x)),
+        (Radians(3.), vec3!(y)),
+        (Radians(3.), vec3!(z)),
+    ] {
+        let matrix = Mat4::rotation(angle, axis);
+
+        assert_approx_eq!(Quat::from_mat4(matrix).to_mat4(), matrix);
+    }
+}
+
+#[test]
+fn rotate_matches_mat4_rotation() {
+    let angle = Degrees(33.);
+    let axis = vec3!(0.25, -1., 2.);
+    let v = vec3!(3., 4., 5.);
+
+    let expected = (Mat4::rotation(angle, axis) * crate::vec4::vec4!(v.x, v.y, v.z, 0.)).xyz();
+
+    assert_approx_eq!(Quat::from_axis_angle(angle, axis) * v, expected);
+}
+
+#[test]
+fn mul_applies_the_right_hand_side_first() {
+    let a = Quat::from_axis_angle(Radians(TAU / 4.), vec3!(z));
+    let b = Quat::from_axis_angle(Radians(TAU / 4.), vec3!(x));
+
+    // b takes y to z, which a leaves alone.
+    assert_approx_eq!((a * b) * vec3!(y), vec3!(z));
+    assert_approx_eq!((a * b).to_mat4(), a.to_mat4() * b.to_mat4());
+}
+
+#[test]
+fn inverse_undoes_the_rotation() {
+    let q = Quat::from_euler(Degrees(10.), Degrees(20.), Degrees(30.));
+    let v = vec3!(1., 2., 3.);
+
+    assert_approx_eq!(q.inverse() * (q * v), v);
+    assert_approx_eq!(q * q.inverse(), IDENTITY);
+}
+
+#[test]
+fn from_euler_applies_z_then_x_then_y() {
+    let q = Quat::from_euler(Degrees(90.), Degrees(90.), Degrees(90.));
+
+    // z takes x to y, x takes y to z, y takes z to x.
+    assert_approx_eq!(q * vec3!(x), vec3!(x));
+    // z takes y to -x, x leaves it, y takes -x to z.
+    assert_approx_eq!(q * vec3!(y), vec3!(z));
+}
+
+#[test]
+fn look_rotation_points_z_forward_and_y_up() {
+    let forward = vec3!(1., 0., 1.);
+    let q = Quat::look_rotation(forward, vec3!(y));
+
+    assert_approx_eq!(q * vec3!(z), Vec3::from(Normal::from(forward)));
+    assert_approx_eq!(q * vec3!(y), vec3!(y));
+}
+
+#[test]
+fn slerp_hits_the_endpoints_and_the_midpoint() {
+    let a = Quat::from_axis_angle(Degrees(0.), vec3!(y));
+    let b = Quat::from_axis_angle(Degrees(90.), vec3!(y));
+
+    assert_approx_eq!(a.slerp(b, 0.), a);
+    assert_approx_eq!(a.slerp(b, 1.), b);
+    assert_approx_eq!(a.slerp(b, 0.5), Quat::from_axis_angle(Degrees(45.), vec3!(y)));
+    assert_approx_eq!(
+        a.slerp(b, 0.25),
+        Quat::from_axis_angle(Degrees(22.5), vec3!(y))
+    );
+}
+
+#[test]
+fn slerp_takes_the_shortest_path() {
+    let a = Quat::from_axis_angle(Degrees(10.), vec3!(z));
+    // The same rotation as 30 degrees, but on the other side of the hypersphere.
+    let b = -Quat::from_axis_angle(Degrees(30.), vec3!(z));
+
+    let mid = a.slerp(b, 0.5);
+
+    assert_approx_eq!(mid * vec3!(x), Quat::from_axis_angle(Degrees(20.), vec3!(z)) * vec3!(x));
+}
+
+#[test]
+fn nlerp_hits_the_endpoints_and_the_midpoint() {
+    let a = Quat::from_axis_angle(Degrees(0.), vec3!(x));
+    let b = Quat::from_axis_angle(Degrees(90.), vec3!(x));
+
+    assert_approx_eq!(a.nlerp(b, 0.), a);
+    assert_approx_eq!(a.nlerp(b, 1.), b);
+    // nlerp is exact at the midpoint.
+    assert_approx_eq!(a.nlerp(b, 0.5), Quat::from_axis_angle(Degrees(45.), vec3!(x)));
+}
@@ -0,0 +1,447 @@
+use crate::{Options, Shader, Stage, Uniform};
+use std::fmt::Write;
+
+/// The offset in bytes of each member in a std140 block, and the size of the
+/// whole block. This needs to match `sokol_extras::uniforms::std140_layout`.
+fn std140_layout(uniforms: &[Uniform]) -> (Vec<usize>, usize) {
+    let align_up = |offset: usize, align: usize| offset.div_ceil(align) * align;
+
+    let mut offsets = Vec::with_capacity(uniforms.len());
+    let mut end = 0;
+    for uniform in uniforms {
+        let (align, size) = uniform.align_and_size();
+        let offset = align_up(end, align);
+        offsets.push(offset);
+        end = offset + size;
+    }
+
+    (offsets, align_up(end, 16))
+}
+
+/// What follows the member's name in its `#define`, to read it out of the `vec4`
+/// array. That is a space and an expression, or for arrays, a parameter list
+/// and an expression.
+fn member_expression(array: &str, uniform: &Uniform, offset: usize) -> String {
+    use crate::UniformType::*;
+
+    let index = offset / 16;
+    let component = (offset % 16) / 4;
+    let swizzle = |len: usize| &"xyzw"[component..component + len];
+
+    if uniform.array_len.is_some() {
+        // Arrays become function-like macros, so they can be indexed.
+        return match uniform.type_ {
+            Mat4 => format!(
+                "(i) mat4({array}[{index} + 4 * (i)], {array}[{index} + 4 * (i) + 1], \
+                {array}[{index} + 4 * (i) + 2], {array}[{index} + 4 * (i) + 3])"
+            ),
+            _ => format!("(i) {array}[{index} + (i)]"),
+        }
+    }
+
+    match uniform.type_ {
+        Float => format!(" {array}[{index}].{}", swizzle(1)),
+        Vec2 => format!(" {array}[{index}].{}", swizzle(2)),
+        Vec3 => format!(" {array}[{index}].{}", swizzle(3)),
+        Vec4 => format!(" {array}[{index}]"),
+        Mat4 => format!(
+            " mat4({array}[{}], {array}[{}], {array}[{}], {array}[{}])",
+            index, index + 1, index + 2, index + 3
+        ),
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum GlslVersion {
+    /// For GL Core 3.3.
+    Glsl330,
+    /// For GLES3 and WebGL2.
+    Glsl300Es,
+}
+
+/// The nul terminated vertex and fragment shader source, in that order, that
+/// `generate` would embed for `version`.
+pub fn glsl_sources(shader: &Shader, version: GlslVersion) -> (String, String) {
+    (
+        stage_source(shader, &shader.vs, "vs_params", true, version),
+        stage_source(shader, &shader.fs, "fs_params", false, version),
+    )
+}
+
+fn stage_source(
+    shader: &Shader,
+    stage: &Stage,
+    params: &str,
+    is_vs: bool,
+    version: GlslVersion,
+) -> String {
+    let mut source = String::new();
+
+    match version {
+        GlslVersion::Glsl330 => source.push_str("#version 330\n"),
+        GlslVersion::Glsl300Es => {
+            source.push_str("#version 300 es\n");
+            // Fragment shaders in GLSL ES have no default float precision.
+            if !is_vs {
+                source.push_str("precision mediump float;\nprecision highp int;\n");
+            }
+        }
+    }
+
+    if is_vs {
+        for (location, attr) in shader.attrs.iter().enumerate() {
+            let _ = writeln!(
+                source,
+                "layout(location = {location}) in {} {};",
+                attr.glsl_type,
+                attr.name
+            );
+        }
+    }
+
+    if !stage.uniforms.is_empty() {
+        let (offsets, size) = std140_layout(&stage.uniforms);
+        let _ = writeln!(source, "uniform vec4 {params}[{}];", size / 16);
+        for (uniform, offset) in stage.uniforms.iter().zip(offsets) {
+            let _ = writeln!(
+                source,
+                "#define {}{}",
+                uniform.name,
+                member_expression(params, uniform, offset)
+            );
+        }
+    }
+
+    for image in &stage.images {
+        let _ = writeln!(source, "uniform {} {};", image.type_.glsl_sampler(), image.name);
+    }
+
+    let _ = writeln!(source, "#line {}", stage.body_line);
+    source.push_str(&stage.body);
+    source.push('\0');
+
+    source
+}
+
+/// Generates Rust code for `include!`ing in a module. The code has:
+///
+/// * `ATTR_VS_<NAME>` consts for the attributes, and `SLOT_VS_PARAMS`,
+///   `SLOT_FS_PARAMS` and `SLOT_<NAME>` consts for the uniform blocks and images.
+/// * A `#[repr(C)]` `Vertex` struct with a field for each attribute.
+/// * `VSParams` and `FSParams` structs implementing `UniformBlock`, for the
+///   stages that have uniforms.
+/// * `fn sources(Backend) -> (CharStar, CharStar)`, which panics for backends
+///   other than GL Core 3.3, GLES3 and the dummy backend.
+/// * `fn shader_desc(Backend) -> ShaderDesc`
+/// * `fn layout_desc() -> LayoutDesc`
+pub fn generate(shader: &Shader, options: &Options) -> String {
+    let extras = &options.sokol_extras_path;
+    let mut out = String::new();
+
+    macro_rules! w {
+        ($($tokens: tt)*) => {
+            let _ = writeln!(out, $($tokens)*);
+        }
+    }
+
+    w!("// Generated by shader-gen from {}.glsl. Edit that instead.", shader.name);
+    w!();
+
+    for (i, attr) in shader.attrs.iter().enumerate() {
+        w!("const ATTR_VS_{}: u8 = {i};", attr.name.to_uppercase());
+    }
+    let stages = [
+        (&shader.vs, "vs", "VS", "VSParams"),
+        (&shader.fs, "fs", "FS", "FSParams"),
+    ];
+    for (stage, _, upper, _) in stages {
+        if !stage.uniforms.is_empty() {
+            w!("pub const SLOT_{upper}_PARAMS: u8 = 0;");
+        }
+        for (i, image) in stage.images.iter().enumerate() {
+            w!("pub const SLOT_{}: u8 = {i};", image.name.to_uppercase());
+        }
+    }
+    w!();
+
+    w!("#[repr(C)]");
+    w!("#[derive(Clone, Copy, Debug)]");
+    w!("pub struct Vertex {{");
+    for attr in &shader.attrs {
+        w!("    pub {}: {},", attr.name, attr.format.rust_type());
+    }
+    w!("}}");
+    w!();
+
+    for (stage, lower, _, struct_name) in stages {
+        if stage.uniforms.is_empty() {
+            continue
+        }
+
+        w!("{extras}::uniforms::uniform_block!{{");
+        w!("    #[derive(Clone, Copy, Debug)]");
+        w!("    pub struct {struct_name} {{");
+        for uniform in &stage.uniforms {
+            for line in uniform.doc.lines() {
+                w!("        ///{line}");
+            }
+            w!("        pub {}: {},", uniform.name, uniform.rust_type());
+        }
+        w!("    }}");
+        w!("}}");
+        w!();
+
+        // A check that `uniform_block!` agrees with the layout used in the GLSL.
+        let (_, size) = std140_layout(&stage.uniforms);
+        w!("const _: () = assert!(");
+        w!(
+            "    <{struct_name} as {extras}::uniforms::UniformBlock>::SIZE == {size}, \
+            \"{lower}_params layout mismatch\""
+        );
+        w!(");");
+        w!();
+    }
+
+    let (vs_330, fs_330) = glsl_sources(shader, GlslVersion::Glsl330);
+    let (vs_300_es, fs_300_es) = glsl_sources(shader, GlslVersion::Glsl300Es);
+
+    w!("/// The vertex and fragment shader source, in that order.");
+    w!("///");
+    w!("/// # Panics");
+    w!("/// If the backend is not GL Core 3.3, GLES3 or the dummy backend.");
+    w!("fn sources(");
+    w!("    backend: ::sokol_bindings::sg::Backend");
+    w!(") -> (::sokol_bindings::CharStar, ::sokol_bindings::CharStar) {{");
+    w!("    use ::sokol_bindings::sg::Backend;");
+    w!("    let (vs, fs): (&str, &str) = match backend {{");
+    w!("        // The dummy backend ignores the source, so any will do there.");
+    w!("        Backend::GlCore3_3 | Backend::Dummy => (");
+    w!("            {vs_330:?},");
+    w!("            {fs_330:?},");
+    w!("        ),");
+    w!("        Backend::Gles3 => (");
+    w!("            {vs_300_es:?},");
+    w!("            {fs_300_es:?},");
+    w!("        ),");
+    w!("        _ => panic!(\"unsupported backend: {{backend:?}}\"),");
+    w!("    }};");
+    w!();
+    w!("    (vs.as_ptr() as _, fs.as_ptr() as _)");
+    w!("}}");
+    w!();
+
+    w!("fn shader_desc(backend: ::sokol_bindings::sg::Backend) -> ::sokol_bindings::sg::ShaderDesc {{");
+    w!("    use ::sokol_bindings::{{cstr, sg}};");
+    w!("    let (vs_source, fs_source) = sources(backend);");
+    w!();
+    w!("    let mut desc = sg::ShaderDesc {{");
+    w!("        label: cstr!(\"{}_shader\"),", shader.name);
+    w!("        ..<_>::default()");
+    w!("    }};");
+    w!();
+    for attr in &shader.attrs {
+        w!(
+            "    desc.attrs[ATTR_VS_{} as usize].name = cstr!(\"{}\");",
+            attr.name.to_uppercase(),
+            attr.name
+        );
+    }
+    for (stage, lower, upper, struct_name) in stages {
+        w!();
+        w!("    desc.{lower}.source = {lower}_source;");
+        w!("    desc.{lower}.entry = cstr!(\"main\");");
+        if !stage.uniforms.is_empty() {
+            w!(
+                "    desc.{lower}.uniform_blocks[SLOT_{upper}_PARAMS as usize] = \
+                <{struct_name} as {extras}::uniforms::UniformBlock>::desc(cstr!(\"{lower}_params\"));"
+            );
+        }
+        for image in &stage.images {
+            let slot = format!("SLOT_{} as usize", image.name.to_uppercase());
+            w!("    desc.{lower}.images[{slot}].name = cstr!(\"{}\");", image.name);
+            w!(
+                "    desc.{lower}.images[{slot}].image_type = sg::ImageType::{} as _;",
+                image.type_.variant()
+            );
+            w!("    desc.{lower}.images[{slot}].sampler_type = sg::SamplerType::Float as _;");
+        }
+    }
+    w!();
+    w!("    desc");
+    w!("}}");
+    w!();
+
+    w!("fn layout_desc() -> ::sokol_bindings::sg::LayoutDesc {{");
+    w!("    use ::sokol_bindings::sg::{{LayoutDesc, VertexFormat}};");
+    w!("    let mut layout = LayoutDesc::default();");
+    w!();
+    for attr in &shader.attrs {
+        w!(
+            "    layout.attrs[ATTR_VS_{} as usize].format = VertexFormat::{} as _;",
+            attr.name.to_uppercase(),
+            attr.format
+        );
+    }
+    w!();
+    w!("    layout");
+    w!("}}");
+
+    out
+}
+
+/// The `hot_reload_pipeline` function that `generate_dir` adds, which needs the
+/// `GLSL_PATH` const, and a `fn depth_state() -> DepthState` in the module that
+/// `include!`s the code.
+pub(crate) fn hot_reload_pipeline(shader: &Shader, options: &Options) -> String {
+    let extras = &options.sokol_extras_path;
+    let mut out = String::new();
+
+    macro_rules! w {
+        ($($tokens: tt)*) => {
+            let _ = writeln!(out, $($tokens)*);
+        }
+    }
+
+    w!("/// A pipeline using this shader, that is rebuilt when {}.glsl changes.", shader.name);
+    w!("/// `configure` is passed a builder with the shader, layout and depth state set.");
+    w!("#[cfg(feature = \"hot-reload\")]");
+    w!("pub fn hot_reload_pipeline(");
+    w!("    context: &::sokol_bindings::sg::Context,");
+    w!(
+        "    configure: impl Fn(::sokol_bindings::sg::PipelineBuilder) \
+        -> ::sokol_bindings::sg::PipelineBuilder + 'static,"
+    );
+    w!(
+        ") -> Result<{extras}::hot_reload::HotReloadPipeline, \
+        {extras}::hot_reload::ReloadError> {{"
+    );
+    w!(
+        "    {extras}::hot_reload::HotReloadPipeline::new(\
+        context, GLSL_PATH, shader_desc, move |shader| {{"
+    );
+    w!(
+        "        configure(::sokol_bindings::sg::PipelineBuilder::new(shader, layout_desc())\
+        .depth(depth_state()))"
+    );
+    w!("    }})");
+    w!("}}");
+
+    out
+}
+
+#[test]
+fn std140_layout_packs_like_glsl() {
+    use crate::UniformType::*;
+
+    let uniforms: Vec<Uniform> = [Float, Vec3, Float, Vec2, Mat4, Vec4]
+        .into_iter()
+        .map(|type_| Uniform { type_, name: String::new(), array_len: None, doc: String::new() })
+        .collect();
+
+    assert_eq!(std140_layout(&uniforms), (vec![0, 16, 28, 32, 48, 112], 128));
+}
+
+#[test]
+fn members_are_read_from_the_right_components() {
+    use crate::UniformType::*;
+
+    let uniforms: Vec<Uniform> = [Mat4, Vec3, Float, Vec2]
+        .into_iter()
+        .map(|type_| Uniform { type_, name: String::new(), array_len: None, doc: String::new() })
+        .collect();
+    let (offsets, _) = std140_layout(&uniforms);
+
+    let expressions: Vec<String> = uniforms.iter()
+        .zip(offsets)
+        .map(|(uniform, offset)| member_expression("p", uniform, offset))
+        .collect();
+
+    assert_eq!(
+        expressions,
+        [
+            " mat4(p[0], p[1], p[2], p[3])",
+            " p[4].xyz",
+            " p[4].w",
+            " p[5].xy",
+        ]
+    );
+}
+
+#[test]
+fn arrays_take_an_index() {
+    use crate::UniformType::*;
+
+    let uniforms = [
+        Uniform { type_: Float, name: String::new(), array_len: None, doc: String::new() },
+        Uniform { type_: Vec4, name: String::new(), array_len: Some(3), doc: String::new() },
+        Uniform { type_: Mat4, name: String::new(), array_len: Some(2), doc: String::new() },
+    ];
+    let (offsets, size) = std140_layout(&uniforms);
+
+    assert_eq!((offsets.as_slice(), size), ([0, 16, 64].as_slice(), 192));
+    assert_eq!(member_expression("p", &uniforms[1], offsets[1]), "(i) p[1 + (i)]");
+    assert_eq!(
+        member_expression("p", &uniforms[2], offsets[2]),
+        "(i) mat4(p[4 + 4 * (i)], p[4 + 4 * (i) + 1], p[4 + 4 * (i) + 2], p[4 + 4 * (i) + 3])"
+    );
+}
+
+#[test]
+fn sources_declare_everything_before_the_body() {
+    let shader = crate::parse("example", "\
+@vs
+@in vec4 position Float3
+@uniform mat4 mvp
+void main() { gl_Position = mvp * position; }
+@end
+@fs
+@image cube sky
+out vec4 c;
+void main() { c = vec4(1.0); }
+@end
+").unwrap();
+
+    assert_eq!(
+        stage_source(&shader, &shader.vs, "vs_params", true, GlslVersion::Glsl330),
+        "#version 330\n\
+        layout(location = 0) in vec4 position;\n\
+        uniform vec4 vs_params[4];\n\
+        #define mvp mat4(vs_params[0], vs_params[1], vs_params[2], vs_params[3])\n\
+        #line 2\n\
+        \n\
+        \n\
+        void main() { gl_Position = mvp * position; }\n\
+        \0"
+    );
+    assert_eq!(
+        stage_source(&shader, &shader.fs, "fs_params", false, GlslVersion::Glsl300Es),
+        "#version 300 es\n\
+        precision mediump float;\n\
+        precision highp int;\n\
+        uniform samplerCube sky;\n\
+        #line 7\n\
+        \n\
+        out vec4 c;\n\
+        void main() { c = vec4(1.0); }\n\
+        \0"
+    );
+}
+
+#[test]
+fn hot_reload_pipeline_is_behind_the_feature_and_uses_the_extras_path() {
+    let shader = crate::parse("example", "\
+@vs
+void main() { gl_Position = vec4(0.0); }
+@end
+@fs
+out vec4 c;
+void main() { c = vec4(1.0); }
+@end
+").unwrap();
+
+    let code = hot_reload_pipeline(&shader, &Options { sokol_extras_path: "extras".to_owned() });
+
+    assert!(code.contains("#[cfg(feature = \"hot-reload\")]\npub fn hot_reload_pipeline("));
+    assert!(code.contains("extras::hot_reload::HotReloadPipeline::new("));
+    assert!(code.contains("when example.glsl changes"));
+}
@@ -0,0 +1,480 @@
+use crate::{
+    Attr, Error, ErrorKind, Image, ImageType, Shader, Stage, Uniform, UniformType,
+    VertexFormat, MAX_SHADERSTAGE_IMAGES, MAX_VERTEX_ATTRIBUTES,
+};
+
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum StageKind {
+    VS,
+    FS,
+}
+
+impl StageKind {
+    fn directive(self) -> &'static str {
+        match self {
+            Self::VS => "@vs",
+            Self::FS => "@fs",
+        }
+    }
+}
+
+/// Parses and validates a shader file. `name` is used for the label.
+pub fn parse(name: &str, source: &str) -> Result<Shader, Error> {
+    let mut attrs: Vec<Attr> = Vec::new();
+    let mut vs: Option<Stage> = None;
+    let mut fs: Option<Stage> = None;
+    // The kind, the line it started on, and what has been parsed so far.
+    let mut current: Option<(StageKind, usize, Stage)> = None;
+    // The `///` lines since the last line that was not one.
+    let mut doc = String::new();
+
+    for (i, line) in source.lines().enumerate() {
+        let line_number = i + 1;
+        let err = |kind| Error { line: line_number, kind };
+
+        let trimmed = line.trim();
+
+        let Some((kind, start_line, stage)) = &mut current else {
+            let kind = match trimmed {
+                "@vs" => StageKind::VS,
+                "@fs" => StageKind::FS,
+                "@end" => return Err(err(ErrorKind::UnexpectedEnd)),
+                _ if trimmed.is_empty() || trimmed.starts_with("//") => continue,
+                _ if trimmed.starts_with('@') => return Err(err(
+                    ErrorKind::UnknownDirective(trimmed.to_owned())
+                )),
+                _ => return Err(err(ErrorKind::TextOutsideStage)),
+            };
+
+            let existing = match kind {
+                StageKind::VS => &vs,
+                StageKind::FS => &fs,
+            };
+            if existing.is_some() {
+                return Err(err(ErrorKind::DuplicateStage(kind.directive())));
+            }
+
+            current = Some((
+                kind,
+                line_number,
+                Stage { body_line: line_number + 1, ..<_>::default() }
+            ));
+            continue
+        };
+
+        if let Some(doc_line) = trimmed.strip_prefix("///") {
+            doc.push_str(doc_line);
+            doc.push('\n');
+        } else if !trimmed.starts_with("@uniform") {
+            doc.clear();
+        }
+
+        if !trimmed.starts_with('@') {
+            stage.body.push_str(line);
+            stage.body.push('\n');
+            continue
+        }
+
+        let words: Vec<&str> = trimmed.split_whitespace().collect();
+
+        let check_count = |directive: &'static str, count: usize| {
+            if words.len() == count + 1 {
+                Ok(())
+            } else {
+                Err(err(ErrorKind::WrongArgumentCount(directive, count)))
+            }
+        };
+
+        match words[0] {
+            "@end" => {
+                check_count("@end", 0)?;
+                let stage = std::mem::take(stage);
+                if !defines_main(&stage.body) {
+                    return Err(Error {
+                        line: *start_line,
+                        kind: ErrorKind::MissingMain(kind.directive()),
+                    });
+                }
+                match kind {
+                    StageKind::VS => vs = Some(stage),
+                    StageKind::FS => fs = Some(stage),
+                }
+                current = None;
+                continue
+            }
+            "@vs" | "@fs" => return Err(err(ErrorKind::NestedStage)),
+            "@in" => {
+                check_count("@in", 3)?;
+                if *kind != StageKind::VS {
+                    return Err(err(ErrorKind::AttributeOutsideVertexShader));
+                }
+                let (glsl_type, name, format) = (words[1], words[2], words[3]);
+
+                if !matches!(glsl_type, "float" | "vec2" | "vec3" | "vec4") {
+                    return Err(err(ErrorKind::UnknownAttributeType(glsl_type.to_owned())));
+                }
+                let Some(format) = VertexFormat::from_name(format) else {
+                    return Err(err(ErrorKind::UnknownVertexFormat(format.to_owned())));
+                };
+                check_name(name, line_number)?;
+                if attrs.iter().any(|attr| same_name(&attr.name, name)) || is_reserved(name) {
+                    return Err(err(ErrorKind::DuplicateName(name.to_owned())));
+                }
+                if attrs.len() >= MAX_VERTEX_ATTRIBUTES {
+                    return Err(err(ErrorKind::TooManyAttributes));
+                }
+
+                attrs.push(Attr {
+                    glsl_type: glsl_type.to_owned(),
+                    name: name.to_owned(),
+                    format,
+                });
+            }
+            "@uniform" => {
+                check_count("@uniform", 2)?;
+                let (type_, name) = (words[1], words[2]);
+
+                let Some(type_) = UniformType::from_glsl(type_) else {
+                    return Err(err(ErrorKind::UnknownUniformType(type_.to_owned())));
+                };
+                let (name, array_len) = match name.split_once('[') {
+                    Some((name, rest)) => {
+                        let Some(len) = rest.strip_suffix(']')
+                            .and_then(|len| len.parse::<usize>().ok())
+                            .filter(|&len| len > 0)
+                        else {
+                            let len = rest.trim_end_matches(']').to_owned();
+                            return Err(err(ErrorKind::InvalidArrayLength(len)));
+                        };
+                        if !matches!(type_, UniformType::Vec4 | UniformType::Mat4) {
+                            return Err(err(ErrorKind::UnsupportedArrayType(words[1].to_owned())));
+                        }
+                        (name, Some(len))
+                    }
+                    None => (name, None),
+                };
+                check_name(name, line_number)?;
+                if is_declared_in_stage(stage, &attrs, *kind, name) || is_reserved(name) {
+                    return Err(err(ErrorKind::DuplicateName(name.to_owned())));
+                }
+
+                stage.uniforms.push(Uniform {
+                    type_,
+                    name: name.to_owned(),
+                    array_len,
+                    doc: std::mem::take(&mut doc),
+                });
+            }
+            "@image" => {
+                check_count("@image", 2)?;
+                let (type_, name) = (words[1], words[2]);
+
+                let type_ = match type_ {
+                    "2d" => ImageType::_2D,
+                    "cube" => ImageType::Cube,
+                    _ => return Err(err(ErrorKind::UnknownImageType(type_.to_owned()))),
+                };
+                check_name(name, line_number)?;
+                // Images in both stages get `SLOT_` consts, so their names need to
+                // be unique across both.
+                let in_other_stage = [&vs, &fs].into_iter()
+                    .flatten()
+                    .any(|other| other.images.iter().any(|image| same_name(&image.name, name)));
+                if in_other_stage
+                || is_declared_in_stage(stage, &attrs, *kind, name)
+                || is_reserved(name) {
+                    return Err(err(ErrorKind::DuplicateName(name.to_owned())));
+                }
+                if stage.images.len() >= MAX_SHADERSTAGE_IMAGES {
+                    return Err(err(ErrorKind::TooManyImages));
+                }
+
+                stage.images.push(Image { type_, name: name.to_owned() });
+            }
+            _ => return Err(err(ErrorKind::UnknownDirective(words[0].to_owned()))),
+        }
+
+        // Directives are left out of the GLSL, but the line is kept so the line
+        // numbers in the GLSL compiler's errors still match the file.
+        stage.body.push('\n');
+    }
+
+    if let Some((_, start_line, _)) = current {
+        return Err(Error { line: start_line, kind: ErrorKind::UnterminatedStage });
+    }
+
+    let last_line = source.lines().count();
+    let missing = |stage: &'static str| Error {
+        line: last_line,
+        kind: ErrorKind::MissingStage(stage),
+    };
+
+    Ok(Shader {
+        name: name.to_owned(),
+        attrs,
+        vs: vs.ok_or_else(|| missing("@vs"))?,
+        fs: fs.ok_or_else(|| missing("@fs"))?,
+    })
+}
+
+fn check_name(name: &str, line: usize) -> Result<(), Error> {
+    let mut chars = name.chars();
+    let valid = chars.next().is_some_and(|c| c.is_ascii_alphabetic() || c == '_')
+        && chars.all(|c| c.is_ascii_alphanumeric() || c == '_')
+        // Reserved in GLSL.
+        && !name.starts_with("gl_")
+        && !name.contains("__");
+
+    if valid {
+        Ok(())
+    } else {
+        Err(Error { line, kind: ErrorKind::InvalidName(name.to_owned()) })
+    }
+}
+
+/// Whether `body` has `void`, whitespace, `main`, optional whitespace, then `(`.
+/// So `void main(void)` and `void main ()` count, as GLSL allows them.
+fn defines_main(body: &str) -> bool {
+    let is_ident_char = |c: char| c.is_ascii_alphanumeric() || c == '_';
+
+    body.match_indices("void").any(|(i, _)| {
+        if body[..i].ends_with(is_ident_char) {
+            return false
+        }
+
+        let after_void = &body[i + "void".len()..];
+        let after_space = after_void.trim_start();
+        if after_space.len() == after_void.len() {
+            return false
+        }
+
+        after_space.strip_prefix("main")
+            .is_some_and(|rest| rest.trim_start().starts_with('('))
+    })
+}
+
+/// Names are uppercased for the `ATTR_` and `SLOT_` consts, so names that only
+/// differ by case would collide there.
+fn same_name(a: &str, b: &str) -> bool {
+    a.eq_ignore_ascii_case(b)
+}
+
+/// The names of the arrays the uniform block members are stored in, which also
+/// get `SLOT_VS_PARAMS` and `SLOT_FS_PARAMS` consts.
+fn is_reserved(name: &str) -> bool {
+    same_name(name, "vs_params") || same_name(name, "fs_params")
+}
+
+fn is_declared_in_stage(stage: &Stage, attrs: &[Attr], kind: StageKind, name: &str) -> bool {
+    stage.uniforms.iter().any(|uniform| same_name(&uniform.name, name))
+    || stage.images.iter().any(|image| same_name(&image.name, name))
+    || (kind == StageKind::VS && attrs.iter().any(|attr| same_name(&attr.name, name)))
+}
+
+#[cfg(test)]
+const EXAMPLE: &str = "\
+// A comment.
+@vs
+@in vec4 position Float3
+@in vec2 texcoord0 Short2N
+@uniform mat4 mvp
+out vec2 uv;
+
+void main()
+{
+    gl_Position = mvp * position;
+    uv = texcoord0;
+}
+@end
+
+@fs
+@uniform vec3 tint
+@image 2d tex
+in vec2 uv;
+layout(location = 0) out vec4 fragColor;
+
+void main()
+{
+    fragColor = texture(tex, uv) * vec4(tint, 1.0);
+}
+@end
+";
+
+#[test]
+fn the_example_parses() {
+    let shader = parse("example", EXAMPLE).unwrap();
+
+    assert_eq!(
+        shader.attrs,
+        vec![
+            Attr {
+                glsl_type: "vec4".to_owned(),
+                name: "position".to_owned(),
+                format: VertexFormat::Float3,
+            },
+            Attr {
+                glsl_type: "vec2".to_owned(),
+                name: "texcoord0".to_owned(),
+                format: VertexFormat::Short2N,
+            },
+        ]
+    );
+    assert_eq!(
+        shader.vs.uniforms,
+        vec![Uniform {
+            type_: UniformType::Mat4,
+            name: "mvp".to_owned(),
+            array_len: None,
+            doc: String::new(),
+        }]
+    );
+    assert_eq!(
+        shader.fs.images,
+        vec![Image { type_: ImageType::_2D, name: "tex".to_owned() }]
+    );
+    assert_eq!(shader.vs.body_line, 3);
+    assert_eq!(shader.vs.body.lines().nth(6 - 3), Some("out vec2 uv;"));
+    assert_eq!(shader.fs.body_line, 16);
+}
+
+#[test]
+fn errors_point_at_the_offending_line() {
+    let replace = |from: &str, to: &str| parse("example", &EXAMPLE.replace(from, to))
+        .unwrap_err();
+
+    assert_eq!(
+        replace("@uniform mat4 mvp", "@uniform mat3 mvp"),
+        Error { line: 5, kind: ErrorKind::UnknownUniformType("mat3".to_owned()) }
+    );
+    assert_eq!(
+        replace("Short2N", "Short3N"),
+        Error { line: 4, kind: ErrorKind::UnknownVertexFormat("Short3N".to_owned()) }
+    );
+    assert_eq!(
+        replace("@uniform vec3 tint", "@in vec3 tint Float3"),
+        Error { line: 16, kind: ErrorKind::AttributeOutsideVertexShader }
+    );
+    assert_eq!(
+        replace("@image 2d tex", "@image 2d tint"),
+        Error { line: 17, kind: ErrorKind::DuplicateName("tint".to_owned()) }
+    );
+    assert_eq!(
+        replace("@image 2d tex", "@image 2d"),
+        Error { line: 17, kind: ErrorKind::WrongArgumentCount("@image", 2) }
+    );
+    assert_eq!(
+        replace("@uniform vec3 tint", "@uniform vec3 tint[2]"),
+        Error { line: 16, kind: ErrorKind::UnsupportedArrayType("vec3".to_owned()) }
+    );
+    assert_eq!(
+        replace("@uniform vec3 tint", "@uniform vec4 tint[0]"),
+        Error { line: 16, kind: ErrorKind::InvalidArrayLength("0".to_owned()) }
+    );
+    assert_eq!(
+        replace("// A comment.", "int x;"),
+        Error { line: 1, kind: ErrorKind::TextOutsideStage }
+    );
+}
+
+#[test]
+fn missing_or_unterminated_stages_are_errors() {
+    let vs_only = &EXAMPLE[..EXAMPLE.find("@fs").unwrap()];
+    assert_eq!(
+        parse("vs_only", vs_only).unwrap_err().kind,
+        ErrorKind::MissingStage("@fs")
+    );
+
+    let unterminated = EXAMPLE.trim_end().trim_end_matches("@end");
+    assert_eq!(
+        parse("unterminated", unterminated).unwrap_err(),
+        Error { line: 15, kind: ErrorKind::UnterminatedStage }
+    );
+
+    assert_eq!(
+        parse("no_main", &EXAMPLE.replacen("void main()", "void f()", 1)).unwrap_err(),
+        Error { line: 2, kind: ErrorKind::MissingMain("@vs") }
+    );
+}
+
+#[test]
+fn names_that_would_collide_in_the_generated_code_are_errors() {
+    let replace = |from: &str, to: &str| parse("example", &EXAMPLE.replace(from, to))
+        .unwrap_err();
+    let duplicate = |line: usize, name: &str| Error {
+        line,
+        kind: ErrorKind::DuplicateName(name.to_owned()),
+    };
+
+    assert_eq!(replace("@image 2d tex", "@image 2d vs_params"), duplicate(17, "vs_params"));
+    assert_eq!(replace("@image 2d tex", "@image 2d FS_PARAMS"), duplicate(17, "FS_PARAMS"));
+    assert_eq!(replace("@image 2d tex", "@image 2d Tint"), duplicate(17, "Tint"));
+    assert_eq!(
+        replace("@in vec2 texcoord0", "@in vec2 POSITION"),
+        duplicate(4, "POSITION")
+    );
+    assert_eq!(
+        replace("@uniform vec3 tint", "@uniform vec3 Fs_Params"),
+        duplicate(16, "Fs_Params")
+    );
+}
+
+#[test]
+fn main_can_be_spaced_out_or_take_void() {
+    for main in ["void main(void)", "void main ()", "void\nmain\n(\n)"] {
+        assert!(parse("main", &EXAMPLE.replace("void main()", main)).is_ok(), "{main}");
+    }
+
+    for not_main in ["void mainly()", "avoid main()", "voidmain()"] {
+        assert_eq!(
+            parse("not_main", &EXAMPLE.replacen("void main()", not_main, 1)).unwrap_err(),
+            Error { line: 2, kind: ErrorKind::MissingMain("@vs") },
+            "{not_main}"
+        );
+    }
+}
+
+#[test]
+fn vec4_and_mat4_uniforms_can_be_arrays() {
+    let shader = parse(
+        "example",
+        &EXAMPLE.replace("@uniform vec3 tint", "@uniform vec4 tints[4]\n@uniform mat4 bones[2]")
+    ).unwrap();
+
+    assert_eq!(
+        shader.fs.uniforms,
+        vec![
+            Uniform {
+                type_: UniformType::Vec4,
+                name: "tints".to_owned(),
+                array_len: Some(4),
+                doc: String::new(),
+            },
+            Uniform {
+                type_: UniformType::Mat4,
+                name: "bones".to_owned(),
+                array_len: Some(2),
+                doc: String::new(),
+            },
+        ]
+    );
+}
+
+#[test]
+fn doc_lines_document_the_uniform_below_them() {
+    let shader = parse(
+        "example",
+        &EXAMPLE.replace(
+            "@uniform vec3 tint",
+            "/// Multiplied with the texture.\n///\n/// In linear space.\n@uniform vec3 tint"
+        ),
+    ).unwrap();
+    assert_eq!(shader.fs.uniforms[0].doc, " Multiplied with the texture.\n\n In linear space.\n");
+    // The GLSL line numbers still match.
+    assert_eq!(shader.fs.body.lines().nth(2), Some("/// In linear space."));
+
+    let separated = parse(
+        "example",
+        &EXAMPLE.replace("@uniform vec3 tint", "/// Not about tint.\nvec3 f();\n@uniform vec3 tint"),
+    ).unwrap();
+    assert_eq!(separated.fs.uniforms[0].doc, "");
+}
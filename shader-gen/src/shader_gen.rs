@@ -0,0 +1,409 @@
+//! Generates the Rust side of a sokol shader from annotated GLSL, so that the
+//! attribute slots, vertex layout, uniform blocks and images are only declared
+//! once.
+//!
+//! A shader file has a `@vs` and an `@fs` section, each ending with `@end`.
+//! Inside a section, lines starting with `@` declare things, and every other line
+//! is passed through as GLSL:
+//!
+//! ```text
+//! // Comments and blank lines are allowed outside sections.
+//! @vs
+//! @in vec4 position Float3
+//! @in vec3 normal Float3
+//! @uniform mat4 mvp
+//! out vec3 N;
+//!
+//! void main()
+//! {
+//!     gl_Position = mvp * position;
+//!     N = normal;
+//! }
+//! @end
+//!
+//! @fs
+//! @uniform vec3 colour
+//! @image 2d tex
+//! in vec3 N;
+//! layout(location = 0) out vec4 fragColor;
+//!
+//! void main()
+//! {
+//!     fragColor = vec4(colour, 1.0);
+//! }
+//! @end
+//! ```
+//!
+//! * `@in <GLSL type> <name> <vertex format>` declares a vertex attribute, in
+//!   the vertex shader only. The vertex format is the name of a
+//!   `sg::VertexFormat` variant, and decides the type of the `Vertex` field.
+//! * `@uniform <type> <name>` adds a member to the stage's uniform block, which
+//!   becomes `VSParams` or `FSParams`. The types are `float`, `vec2`, `vec3`,
+//!   `vec4` and `mat4`. `vec4` and `mat4` members can be arrays, like
+//!   `@uniform vec4 colours[4]`, which are read in the GLSL with `colours(i)`,
+//!   since the `#define`s that arrays become need to take the index. `///`
+//!   lines directly above a `@uniform` become the doc comment of its field.
+//! * `@image <2d|cube> <name>` declares a sampler, with a `SLOT_<NAME>` const.
+//!
+//! Uniform blocks are declared in the GLSL as a `vec4` array, with a `#define`
+//! for each member, so the members can be used by name. The GLSL should be
+//! written so that it works as both version 330 and version 300 es, since
+//! sources for both are generated.
+
+use std::fmt;
+
+mod generate;
+mod parse;
+
+pub use generate::{generate, glsl_sources, GlslVersion};
+pub use parse::parse;
+
+/// The maximum amounts sokol_gfx allows.
+const MAX_VERTEX_ATTRIBUTES: usize = 16;
+const MAX_SHADERSTAGE_IMAGES: usize = 12;
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct Shader {
+    /// Used for the shader's label.
+    pub name: String,
+    pub attrs: Vec<Attr>,
+    pub vs: Stage,
+    pub fs: Stage,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct Attr {
+    pub glsl_type: String,
+    pub name: String,
+    pub format: VertexFormat,
+}
+
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct Stage {
+    pub uniforms: Vec<Uniform>,
+    pub images: Vec<Image>,
+    pub body: String,
+    /// The line in the file that the body starts on, so the GLSL compiler's
+    /// errors can point at the right line.
+    pub body_line: usize,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct Uniform {
+    pub type_: UniformType,
+    pub name: String,
+    /// `Some` for arrays, like `vec4 name[4]`.
+    pub array_len: Option<usize>,
+    /// The `///` lines directly above the declaration, without the slashes, one
+    /// per line. These become the doc comment of the field.
+    pub doc: String,
+}
+
+impl Uniform {
+    /// The std140 alignment and size, in bytes.
+    fn align_and_size(&self) -> (usize, usize) {
+        let (align, size) = self.type_.align_and_size();
+        (align, size * self.array_len.unwrap_or(1))
+    }
+
+    fn rust_type(&self) -> String {
+        match self.array_len {
+            Some(len) => format!("[{}; {len}]", self.type_.rust_type()),
+            None => self.type_.rust_type().to_owned(),
+        }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct Image {
+    pub type_: ImageType,
+    pub name: String,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum UniformType {
+    Float,
+    Vec2,
+    Vec3,
+    Vec4,
+    Mat4,
+}
+
+impl UniformType {
+    fn from_glsl(glsl: &str) -> Option<Self> {
+        use UniformType::*;
+        Some(match glsl {
+            "float" => Float,
+            "vec2" => Vec2,
+            "vec3" => Vec3,
+            "vec4" => Vec4,
+            "mat4" => Mat4,
+            _ => return None,
+        })
+    }
+
+    /// The std140 alignment and size, in bytes.
+    fn align_and_size(self) -> (usize, usize) {
+        use UniformType::*;
+        match self {
+            Float => (4, 4),
+            Vec2 => (8, 8),
+            Vec3 => (16, 12),
+            Vec4 => (16, 16),
+            Mat4 => (16, 64),
+        }
+    }
+
+    fn rust_type(self) -> &'static str {
+        use UniformType::*;
+        match self {
+            Float => "f32",
+            Vec2 => "[f32; 2]",
+            Vec3 => "::math::vec3::Vec3",
+            Vec4 => "::math::vec4::Vec4",
+            Mat4 => "::math::mat4::Mat4",
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ImageType {
+    _2D,
+    Cube,
+}
+
+impl ImageType {
+    fn glsl_sampler(self) -> &'static str {
+        match self {
+            Self::_2D => "sampler2D",
+            Self::Cube => "samplerCube",
+        }
+    }
+
+    /// The name of the `sg::ImageType` variant.
+    fn variant(self) -> &'static str {
+        match self {
+            Self::_2D => "_2D",
+            Self::Cube => "Cube",
+        }
+    }
+}
+
+/// The `sg::VertexFormat`s that have an obvious Rust type.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum VertexFormat {
+    Float,
+    Float2,
+    Float3,
+    Float4,
+    Byte4N,
+    UByte4,
+    UByte4N,
+    Short2,
+    Short2N,
+    Short4N,
+}
+
+impl VertexFormat {
+    fn from_name(name: &str) -> Option<Self> {
+        use VertexFormat::*;
+        Some(match name {
+            "Float" => Float,
+            "Float2" => Float2,
+            "Float3" => Float3,
+            "Float4" => Float4,
+            "Byte4N" => Byte4N,
+            "UByte4" => UByte4,
+            "UByte4N" => UByte4N,
+            "Short2" => Short2,
+            "Short2N" => Short2N,
+            "Short4N" => Short4N,
+            _ => return None,
+        })
+    }
+
+    fn rust_type(self) -> &'static str {
+        use VertexFormat::*;
+        match self {
+            Float => "f32",
+            Float2 => "[f32; 2]",
+            Float3 => "::math::vec3::Vec3",
+            Float4 => "::math::vec4::Vec4",
+            Byte4N => "[i8; 4]",
+            UByte4 => "[u8; 4]",
+            // An `ABGR` colour, in the other shaders.
+            UByte4N => "u32",
+            Short2 | Short2N => "[i16; 2]",
+            Short4N => "[i16; 4]",
+        }
+    }
+}
+
+impl fmt::Display for VertexFormat {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Debug::fmt(self, f)
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Error {
+    /// Starting from 1.
+    pub line: usize,
+    pub kind: ErrorKind,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum ErrorKind {
+    UnknownDirective(String),
+    /// The directive, and how many arguments it takes.
+    WrongArgumentCount(&'static str, usize),
+    UnknownUniformType(String),
+    /// Only `vec4` and `mat4` arrays are supported, since the other types would
+    /// need padding between elements.
+    UnsupportedArrayType(String),
+    InvalidArrayLength(String),
+    UnknownImageType(String),
+    UnknownVertexFormat(String),
+    /// Attributes have to be `float` or a `vec`.
+    UnknownAttributeType(String),
+    InvalidName(String),
+    DuplicateName(String),
+    AttributeOutsideVertexShader,
+    TooManyAttributes,
+    TooManyImages,
+    /// GLSL that is not inside a `@vs` or `@fs` section.
+    TextOutsideStage,
+    /// A `@vs` or `@fs` inside another section.
+    NestedStage,
+    DuplicateStage(&'static str),
+    MissingStage(&'static str),
+    UnexpectedEnd,
+    /// The section that starts on the error's line does not have an `@end`.
+    UnterminatedStage,
+    MissingMain(&'static str),
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        use ErrorKind::*;
+        write!(f, "line {}: ", self.line)?;
+        match &self.kind {
+            UnknownDirective(directive) => write!(f, "unknown directive \"{directive}\""),
+            WrongArgumentCount(directive, count) => write!(
+                f,
+                "\"{directive}\" takes {count} arguments"
+            ),
+            UnknownUniformType(type_) => write!(
+                f,
+                "unknown uniform type \"{type_}\", expected float, vec2, vec3, vec4 or mat4"
+            ),
+            UnsupportedArrayType(type_) => write!(
+                f,
+                "arrays of \"{type_}\" are not supported, only vec4 and mat4 arrays are"
+            ),
+            InvalidArrayLength(length) => write!(
+                f,
+                "\"{length}\" is not a valid array length"
+            ),
+            UnknownImageType(type_) => write!(
+                f,
+                "unknown image type \"{type_}\", expected 2d or cube"
+            ),
+            UnknownVertexFormat(format) => write!(f, "unknown vertex format \"{format}\""),
+            UnknownAttributeType(type_) => write!(
+                f,
+                "unknown attribute type \"{type_}\", expected float, vec2, vec3 or vec4"
+            ),
+            InvalidName(name) => write!(f, "\"{name}\" is not a valid name"),
+            DuplicateName(name) => write!(f, "\"{name}\" is declared more than once"),
+            AttributeOutsideVertexShader => write!(f, "attributes can only be declared in @vs"),
+            TooManyAttributes => write!(
+                f,
+                "there can be at most {MAX_VERTEX_ATTRIBUTES} attributes"
+            ),
+            TooManyImages => write!(
+                f,
+                "there can be at most {MAX_SHADERSTAGE_IMAGES} images per stage"
+            ),
+            TextOutsideStage => write!(f, "GLSL needs to be inside @vs or @fs"),
+            NestedStage => write!(f, "sections cannot be nested"),
+            DuplicateStage(stage) => write!(f, "there is more than one {stage} section"),
+            MissingStage(stage) => write!(f, "there is no {stage} section"),
+            UnexpectedEnd => write!(f, "@end outside of a section"),
+            UnterminatedStage => write!(f, "this section has no @end"),
+            MissingMain(stage) => write!(f, "the {stage} section has no main function"),
+        }
+    }
+}
+
+impl std::error::Error for Error {}
+
+#[derive(Clone, Debug)]
+pub struct Options {
+    /// The path that `sokol_extras` can be reached at, from the generated code.
+    /// `"crate"` from inside `sokol_extras` itself.
+    pub sokol_extras_path: String,
+}
+
+impl Default for Options {
+    fn default() -> Self {
+        Self {
+            sokol_extras_path: "::sokol_extras".to_owned(),
+        }
+    }
+}
+
+/// For calling from a build script. Generates a Rust file in `OUT_DIR` for each
+/// `.glsl` file in `dir`, with the same file stem, for use with `include!`.
+/// Along with what `generate` outputs, each file has a `GLSL_PATH` const with the
+/// absolute path of the `.glsl` file, for reloading it at runtime, and a
+/// `hot_reload_pipeline` function that uses it, when the `hot-reload` feature of
+/// the crate running the build script is enabled. That function needs a
+/// `fn depth_state() -> DepthState` in the module that `include!`s the file.
+///
+/// # Panics
+/// If any of the files is invalid, with the file and line in the message, so
+/// that the build fails.
+pub fn generate_dir(dir: &str, options: &Options) {
+    use std::{fs, path::Path};
+
+    println!("cargo:rerun-if-changed={dir}");
+
+    let out_dir = std::env::var("OUT_DIR").expect("OUT_DIR should be set by cargo");
+
+    let mut paths: Vec<_> = fs::read_dir(dir)
+        .unwrap_or_else(|e| panic!("could not read {dir}: {e}"))
+        .map(|entry| entry.expect("directory entry should be readable").path())
+        .filter(|path| path.extension().is_some_and(|ext| ext == "glsl"))
+        .collect();
+    paths.sort();
+
+    for path in paths {
+        println!("cargo:rerun-if-changed={}", path.display());
+
+        let name = path.file_stem()
+            .and_then(|stem| stem.to_str())
+            .unwrap_or_else(|| panic!("{} should have a UTF-8 name", path.display()));
+
+        let source = fs::read_to_string(&path)
+            .unwrap_or_else(|e| panic!("could not read {}: {e}", path.display()));
+
+        let shader = parse(name, &source)
+            .unwrap_or_else(|e| panic!("{}: {e}", path.display()));
+
+        let absolute_path = fs::canonicalize(&path)
+            .unwrap_or_else(|e| panic!("could not find {}: {e}", path.display()));
+
+        let mut code = generate(&shader, options);
+        code.push_str(&format!(
+            "\n\n/// The file this was generated from.\npub const GLSL_PATH: &str = {:?};\n",
+            absolute_path.display().to_string()
+        ));
+        code.push('\n');
+        code.push_str(&generate::hot_reload_pipeline(&shader, options));
+
+        let out_path = Path::new(&out_dir).join(format!("{name}.rs"));
+        fs::write(&out_path, code)
+            .unwrap_or_else(|e| panic!("could not write {}: {e}", out_path.display()));
+    }
+}
@@ -1,3 +1,13 @@
+#[derive(Clone, Copy)]
+enum Backend {
+    GlCore33,
+    Gles3,
+    /// Does not draw anything, and `sokol_app` is left out, so it needs neither a
+    /// window nor a GPU. This allows testing code that sets up graphics resources
+    /// on headless machines.
+    Dummy,
+}
+
 fn main() {
     println!("cargo:rerun-if-changed=wrapper.h");
     println!("cargo:rerun-if-changed=wrapper.c");
@@ -5,23 +15,64 @@ fn main() {
     println!("cargo:rerun-if-changed=third-party/sokol_app.h");
     println!("cargo:rerun-if-changed=third-party/sokol_glue.h");
 
+    let feature = |name: &str| std::env::var_os(format!("CARGO_FEATURE_{name}")).is_some();
+
+    let selected: Vec<Backend> = [
+        ("GLCORE33", Backend::GlCore33),
+        ("GLES3", Backend::Gles3),
+        ("DUMMY", Backend::Dummy),
+    ]
+        .into_iter()
+        .filter(|(name, _)| feature(name))
+        .map(|(_, backend)| backend)
+        .collect();
+
+    let backend = match selected.as_slice() {
+        [] => Backend::GlCore33,
+        &[backend] => backend,
+        // `sys.rs` reports this with a clearer error than the C compiler would
+        // give, so we skip compiling.
+        _ => return,
+    };
+
+    let target_os = std::env::var("CARGO_CFG_TARGET_OS").unwrap_or_default();
+
     // TODO link to different platform specific libraries on other platforms
-    println!("cargo:rustc-link-lib=GL");
+    match backend {
+        Backend::GlCore33 => {
+            println!("cargo:rustc-link-lib=GL");
+            println!("cargo:rustc-link-lib=X11");
+            println!("cargo:rustc-link-lib=asound");
+            println!("cargo:rustc-link-lib=Xi");
+            println!("cargo:rustc-link-lib=Xcursor");
+        }
+        // `sokol_app` only supports GLES3 on Android and the web, and it does not
+        // allow `SOKOL_NO_ENTRY`, which wrapper.h sets, on Android. So the web is
+        // the only target that works for now. Emscripten provides the libraries
+        // itself, when the final binary is linked with `-sUSE_WEBGL2`, which
+        // .cargo/config.toml in the workspace root does. A `-sys` crate's build
+        // script cannot pass linker arguments on to the binaries that use it.
+        // TODO provide an entry point, so that Android works too.
+        Backend::Gles3 => if target_os != "emscripten" {
+            panic!(
+                "The `gles3` feature is only supported when targeting emscripten, not {target_os:?}."
+            );
+        },
+        Backend::Dummy => {}
+    }
     println!("cargo:rustc-link-lib=dl");
     println!("cargo:rustc-link-lib=m");
-    println!("cargo:rustc-link-lib=X11");
-    println!("cargo:rustc-link-lib=asound");
-    println!("cargo:rustc-link-lib=Xi");
-    println!("cargo:rustc-link-lib=Xcursor");
 
-    // TODO select this with a feature flag. Bindings generation will also need to
-    // be updated.
-    const BACKEND: &str = "SOKOL_GLCORE33";
+    let define = match backend {
+        Backend::GlCore33 => "SOKOL_GLCORE33",
+        Backend::Gles3 => "SOKOL_GLES3",
+        Backend::Dummy => "SOKOL_DUMMY_BACKEND",
+    };
 
     let mut builder = cc::Build::new();
     builder
         .file("wrapper.c")
-        .define(BACKEND, None)
+        .define(define, None)
         .flag("-pthread")
         .include("../third-party");
 
@@ -30,4 +81,4 @@ fn main() {
     }
 
     builder.compile("wrapper");
-}
\ No newline at end of file
+}
@@ -1,5 +1,13 @@
 /* automatically generated by rust-bindgen 0.59.2 */
 
+pub type sokol_bindings_log_callback =
+    ::core::option::Option<unsafe extern "C" fn(message: *const ::std::os::raw::c_char)>;
+extern "C" {
+    pub fn sokol_bindings_set_log_callback(callback: sokol_bindings_log_callback);
+}
+extern "C" {
+    pub fn sokol_bindings_log(message: *const ::std::os::raw::c_char);
+}
 pub type __uint32_t = ::std::os::raw::c_uint;
 #[repr(C)]
 #[derive(Debug, Default, Copy, Clone)]
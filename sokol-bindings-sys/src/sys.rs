@@ -1,3 +1,26 @@
+#[cfg(any(
+    all(feature = "glcore33", feature = "gles3"),
+    all(feature = "glcore33", feature = "dummy"),
+    all(feature = "gles3", feature = "dummy"),
+))]
+compile_error!("Only one of the `glcore33`, `gles3` and `dummy` features can be enabled at a time.");
+
+// `glcore33` is used when no backend is selected, so that depending on this
+// crate without any features does not change.
+#[cfg(not(any(feature = "gles3", feature = "dummy")))]
 #[allow(non_snake_case, non_camel_case_types, non_upper_case_globals)]
 mod gl_core_3_3;
-pub use gl_core_3_3::*;
\ No newline at end of file
+#[cfg(not(any(feature = "gles3", feature = "dummy")))]
+pub use gl_core_3_3::*;
+
+#[cfg(all(feature = "gles3", not(feature = "dummy")))]
+#[allow(non_snake_case, non_camel_case_types, non_upper_case_globals)]
+mod gles3;
+#[cfg(all(feature = "gles3", not(feature = "dummy")))]
+pub use gles3::*;
+
+#[cfg(all(feature = "dummy", not(feature = "gles3")))]
+#[allow(non_snake_case, non_camel_case_types, non_upper_case_globals)]
+mod dummy;
+#[cfg(all(feature = "dummy", not(feature = "gles3")))]
+pub use dummy::*;
@@ -1 +1,17 @@
-#include "wrapper.h"
\ No newline at end of file
+#include "wrapper.h"
+
+static sokol_bindings_log_callback log_callback = 0;
+
+void sokol_bindings_set_log_callback(sokol_bindings_log_callback callback) {
+    log_callback = callback;
+}
+
+void sokol_bindings_log(const char* message) {
+    if (log_callback) {
+        log_callback(message);
+        return;
+    }
+#ifdef SOKOL_BINGDINGS_DEBUG
+    fprintf(stderr, "%s\n", message);
+#endif
+}
@@ -1,11 +1,19 @@
 #include <stdio.h>
 
-#ifdef SOKOL_BINGDINGS_DEBUG
-#define SOKOL_LOG(s) { SOKOL_ASSERT(s); fprintf(stderr, "%s\n", s); }
-#endif
+// Everything sokol logs, like shader compile errors, goes through
+// `sokol_bindings_log`, so the Rust side can capture it by setting a callback.
+// With no callback set, debug builds print to stderr, and release builds drop
+// the message.
+typedef void (*sokol_bindings_log_callback)(const char* message);
+void sokol_bindings_set_log_callback(sokol_bindings_log_callback callback);
+void sokol_bindings_log(const char* message);
+#define SOKOL_LOG(s) sokol_bindings_log(s)
 
 #define SOKOL_IMPL
 #include "../third-party/sokol_gfx.h"
+// sokol_app needs a real backend, so it is not available with the dummy one.
+#ifndef SOKOL_DUMMY_BACKEND
 #define SOKOL_NO_ENTRY
 #include "../third-party/sokol_app.h"
 #include "../third-party/sokol_glue.h"
+#endif
@@ -0,0 +1,80 @@
+//! Access to what sokol logs, like shader compile and link errors, which are
+//! otherwise only printed to stderr in debug builds.
+
+use sokol_bindings_sys as sys;
+use std::{
+    ffi::CStr,
+    sync::{Mutex, MutexGuard, PoisonError},
+};
+
+// The log callback is process wide, so sokol calls made on any thread while
+// capturing end up here, not just ones made on the capturing thread.
+static CAPTURED: Mutex<Option<String>> = Mutex::new(None);
+// Held for the whole of each `capture` call, so that captures on different
+// threads do not take each other's messages.
+static CAPTURING: Mutex<()> = Mutex::new(());
+
+// A panic while either lock is held leaves nothing half done that matters, so
+// the locks are still usable.
+fn lock<T>(mutex: &Mutex<T>) -> MutexGuard<'_, T> {
+    mutex.lock().unwrap_or_else(PoisonError::into_inner)
+}
+
+unsafe extern "C" fn capture_message(message: *const std::os::raw::c_char) {
+    if message.is_null() {
+        return
+    }
+
+    // SAFETY: sokol passes nul terminated strings that live for the duration of
+    // the call.
+    let message = unsafe { CStr::from_ptr(message) }.to_string_lossy();
+
+    if let Some(log) = lock(&CAPTURED).as_mut() {
+        log.push_str(&message);
+        if !log.ends_with('\n') {
+            log.push('\n');
+        }
+    }
+}
+
+/// Calls `f`, and returns what it returned along with everything sokol logged
+/// while it ran. The messages are not printed to stderr while `f` runs.
+///
+/// sokol only has one log callback, so calls to this on other threads wait for
+/// this one to finish, and calls to this should not be nested, or they will
+/// deadlock.
+pub fn capture<R>(f: impl FnOnce() -> R) -> (R, String) {
+    let _capturing = lock(&CAPTURING);
+
+    *lock(&CAPTURED) = Some(String::new());
+    // SAFETY: `capture_message` handles any message sokol passes it.
+    unsafe { sys::sokol_bindings_set_log_callback(Some(capture_message)); }
+
+    let output = f();
+
+    // SAFETY: `None` restores the default behaviour.
+    unsafe { sys::sokol_bindings_set_log_callback(None); }
+    let log = lock(&CAPTURED).take().unwrap_or_default();
+
+    (output, log)
+}
+
+#[test]
+fn capture_returns_what_was_logged() {
+    let message = b"shader compile failed\0";
+
+    let (output, log) = capture(|| {
+        // SAFETY: `message` is nul terminated.
+        unsafe { sys::sokol_bindings_log(message.as_ptr() as _); }
+        1
+    });
+
+    assert_eq!(output, 1);
+    // Other tests running at the same time can log things too, which are
+    // captured along with ours.
+    assert!(log.lines().any(|line| line == "shader compile failed"));
+
+    // Messages after `capture` returns are not captured.
+    let ((), log) = capture(|| {});
+    assert!(!log.contains("shader compile failed"));
+}
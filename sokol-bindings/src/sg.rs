@@ -11,11 +11,14 @@ pub use sys::sg_depth_state as DepthState;
 pub use sys::sg_image as Image;
 pub use sys::sg_image_desc as ImageDesc;
 pub use sys::sg_layout_desc as LayoutDesc;
+pub use sys::sg_pass as Pass;
+pub use sys::sg_pass_desc as PassDesc;
 pub use sys::sg_pipeline as Pipeline;
 pub use sys::sg_pipeline_desc as PipelineDesc;
 pub use sys::sg_range as Range;
 pub use sys::sg_shader as Shader;
 pub use sys::sg_shader_desc as ShaderDesc;
+pub use sys::sg_shader_uniform_block_desc as ShaderUniformBlockDesc;
 
 pub use sys::sg_make_buffer as make_buffer;
 pub use sys::sg_make_image as make_image;
@@ -28,6 +31,36 @@ pub use sys::sg_apply_uniforms as apply_uniforms;
 
 pub use sys::sg_draw as draw;
 
+mod capabilities;
+pub use capabilities::{
+    query_features,
+    query_limits,
+    query_pixel_format,
+    Features,
+    Limits,
+    PixelFormatInfo,
+};
+
+mod dynamic;
+pub use dynamic::{make_dynamic_buffer, make_stream_buffer, BufferOverflow, DynamicBuffer};
+
+mod pipeline;
+pub use pipeline::{PipelineBuilder, PipelineError};
+
+mod render_target;
+pub use render_target::{RenderTarget, RenderTargetFormat};
+
+mod resource;
+pub use resource::{
+    Context,
+    OwnedBuffer,
+    OwnedImage,
+    OwnedPass,
+    OwnedPipeline,
+    OwnedShader,
+    ResourceState,
+};
+
 // TODO wrap everywhere we'd want to use this with things that use slices instead.
 #[macro_export]
 macro_rules! _range {
@@ -41,7 +74,7 @@ macro_rules! _range {
     // If you pass an expr, we don't want to evaluate the expr twice, so AFAIK
     // the cleanest way to do that is for you to add the `&`.
     ($arr_ref: expr) => {{
-        let arr_ref: &[u8] = $arr_ref;
+        let arr_ref: &[_] = $arr_ref;
         $crate::sg::Range {
             size: core::mem::size_of_val(arr_ref),
             ptr: arr_ref as *const _ as _,
@@ -50,6 +83,7 @@ macro_rules! _range {
 }
 pub use _range as range;
 
+/// Prefer dropping every `Context` instead, if there is one.
 pub fn shutdown() {
     // SAFETY: There are no currently known safety issues with this fn.
     unsafe{ sys::sg_shutdown() }
@@ -104,6 +138,48 @@ pub fn query_backend() -> Backend {
     }
 }
 
+#[repr(u32)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum BlendFactor {
+    Default = sys::sg_blend_factor__SG_BLENDFACTOR_DEFAULT,
+    Zero = sys::sg_blend_factor_SG_BLENDFACTOR_ZERO,
+    One = sys::sg_blend_factor_SG_BLENDFACTOR_ONE,
+    SrcColor = sys::sg_blend_factor_SG_BLENDFACTOR_SRC_COLOR,
+    OneMinusSrcColor = sys::sg_blend_factor_SG_BLENDFACTOR_ONE_MINUS_SRC_COLOR,
+    SrcAlpha = sys::sg_blend_factor_SG_BLENDFACTOR_SRC_ALPHA,
+    OneMinusSrcAlpha = sys::sg_blend_factor_SG_BLENDFACTOR_ONE_MINUS_SRC_ALPHA,
+    DstColor = sys::sg_blend_factor_SG_BLENDFACTOR_DST_COLOR,
+    OneMinusDstColor = sys::sg_blend_factor_SG_BLENDFACTOR_ONE_MINUS_DST_COLOR,
+    DstAlpha = sys::sg_blend_factor_SG_BLENDFACTOR_DST_ALPHA,
+    OneMinusDstAlpha = sys::sg_blend_factor_SG_BLENDFACTOR_ONE_MINUS_DST_ALPHA,
+    SrcAlphaSaturated = sys::sg_blend_factor_SG_BLENDFACTOR_SRC_ALPHA_SATURATED,
+    BlendColor = sys::sg_blend_factor_SG_BLENDFACTOR_BLEND_COLOR,
+    OneMinusBlendColor = sys::sg_blend_factor_SG_BLENDFACTOR_ONE_MINUS_BLEND_COLOR,
+    BlendAlpha = sys::sg_blend_factor_SG_BLENDFACTOR_BLEND_ALPHA,
+    OneMinusBlendAlpha = sys::sg_blend_factor_SG_BLENDFACTOR_ONE_MINUS_BLEND_ALPHA,
+}
+
+impl Default for BlendFactor {
+    fn default() -> Self {
+        Self::Default
+    }
+}
+
+#[repr(u32)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum BlendOp {
+    Default = sys::sg_blend_op__SG_BLENDOP_DEFAULT,
+    Add = sys::sg_blend_op_SG_BLENDOP_ADD,
+    Subtract = sys::sg_blend_op_SG_BLENDOP_SUBTRACT,
+    ReverseSubtract = sys::sg_blend_op_SG_BLENDOP_REVERSE_SUBTRACT,
+}
+
+impl Default for BlendOp {
+    fn default() -> Self {
+        Self::Default
+    }
+}
+
 #[repr(u32)]
 pub enum BufferType {
     Default = sys::sg_buffer_type__SG_BUFFERTYPE_DEFAULT,
@@ -118,6 +194,7 @@ impl Default for BufferType {
 }
 
 #[repr(u32)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
 pub enum CullMode {
     Default = sys::sg_cull_mode__SG_CULLMODE_DEFAULT,
     None = sys::sg_cull_mode_SG_CULLMODE_NONE,
@@ -132,6 +209,7 @@ impl Default for CullMode {
 }
 
 #[repr(u32)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
 pub enum CompareFunc {
     Default = sys::sg_compare_func__SG_COMPAREFUNC_DEFAULT,
     Never = sys::sg_compare_func_SG_COMPAREFUNC_NEVER,
@@ -151,6 +229,39 @@ impl Default for CompareFunc {
 }
 
 #[repr(u32)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum FaceWinding {
+    Default = sys::sg_face_winding__SG_FACEWINDING_DEFAULT,
+    CCW = sys::sg_face_winding_SG_FACEWINDING_CCW,
+    CW = sys::sg_face_winding_SG_FACEWINDING_CW,
+}
+
+impl Default for FaceWinding {
+    fn default() -> Self {
+        Self::Default
+    }
+}
+
+#[repr(u32)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Filter {
+    Default = sys::sg_filter__SG_FILTER_DEFAULT,
+    Nearest = sys::sg_filter_SG_FILTER_NEAREST,
+    Linear = sys::sg_filter_SG_FILTER_LINEAR,
+    NearestMipmapNearest = sys::sg_filter_SG_FILTER_NEAREST_MIPMAP_NEAREST,
+    NearestMipmapLinear = sys::sg_filter_SG_FILTER_NEAREST_MIPMAP_LINEAR,
+    LinearMipmapNearest = sys::sg_filter_SG_FILTER_LINEAR_MIPMAP_NEAREST,
+    LinearMipmapLinear = sys::sg_filter_SG_FILTER_LINEAR_MIPMAP_LINEAR,
+}
+
+impl Default for Filter {
+    fn default() -> Self {
+        Self::Default
+    }
+}
+
+#[repr(u32)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
 pub enum IndexType {
     Default = sys::sg_index_type__SG_INDEXTYPE_DEFAULT,
     None = sys::sg_index_type_SG_INDEXTYPE_NONE,
@@ -179,6 +290,96 @@ impl Default for ImageType {
     }
 }
 
+#[repr(u32)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum PixelFormat {
+    Default = sys::sg_pixel_format__SG_PIXELFORMAT_DEFAULT,
+    None = sys::sg_pixel_format_SG_PIXELFORMAT_NONE,
+    R8 = sys::sg_pixel_format_SG_PIXELFORMAT_R8,
+    R8SN = sys::sg_pixel_format_SG_PIXELFORMAT_R8SN,
+    R8UI = sys::sg_pixel_format_SG_PIXELFORMAT_R8UI,
+    R8SI = sys::sg_pixel_format_SG_PIXELFORMAT_R8SI,
+    R16 = sys::sg_pixel_format_SG_PIXELFORMAT_R16,
+    R16SN = sys::sg_pixel_format_SG_PIXELFORMAT_R16SN,
+    R16UI = sys::sg_pixel_format_SG_PIXELFORMAT_R16UI,
+    R16SI = sys::sg_pixel_format_SG_PIXELFORMAT_R16SI,
+    R16F = sys::sg_pixel_format_SG_PIXELFORMAT_R16F,
+    RG8 = sys::sg_pixel_format_SG_PIXELFORMAT_RG8,
+    RG8SN = sys::sg_pixel_format_SG_PIXELFORMAT_RG8SN,
+    RG8UI = sys::sg_pixel_format_SG_PIXELFORMAT_RG8UI,
+    RG8SI = sys::sg_pixel_format_SG_PIXELFORMAT_RG8SI,
+    R32UI = sys::sg_pixel_format_SG_PIXELFORMAT_R32UI,
+    R32SI = sys::sg_pixel_format_SG_PIXELFORMAT_R32SI,
+    R32F = sys::sg_pixel_format_SG_PIXELFORMAT_R32F,
+    RG16 = sys::sg_pixel_format_SG_PIXELFORMAT_RG16,
+    RG16SN = sys::sg_pixel_format_SG_PIXELFORMAT_RG16SN,
+    RG16UI = sys::sg_pixel_format_SG_PIXELFORMAT_RG16UI,
+    RG16SI = sys::sg_pixel_format_SG_PIXELFORMAT_RG16SI,
+    RG16F = sys::sg_pixel_format_SG_PIXELFORMAT_RG16F,
+    RGBA8 = sys::sg_pixel_format_SG_PIXELFORMAT_RGBA8,
+    RGBA8SN = sys::sg_pixel_format_SG_PIXELFORMAT_RGBA8SN,
+    RGBA8UI = sys::sg_pixel_format_SG_PIXELFORMAT_RGBA8UI,
+    RGBA8SI = sys::sg_pixel_format_SG_PIXELFORMAT_RGBA8SI,
+    BGRA8 = sys::sg_pixel_format_SG_PIXELFORMAT_BGRA8,
+    RGB10A2 = sys::sg_pixel_format_SG_PIXELFORMAT_RGB10A2,
+    RG11B10F = sys::sg_pixel_format_SG_PIXELFORMAT_RG11B10F,
+    RG32UI = sys::sg_pixel_format_SG_PIXELFORMAT_RG32UI,
+    RG32SI = sys::sg_pixel_format_SG_PIXELFORMAT_RG32SI,
+    RG32F = sys::sg_pixel_format_SG_PIXELFORMAT_RG32F,
+    RGBA16 = sys::sg_pixel_format_SG_PIXELFORMAT_RGBA16,
+    RGBA16SN = sys::sg_pixel_format_SG_PIXELFORMAT_RGBA16SN,
+    RGBA16UI = sys::sg_pixel_format_SG_PIXELFORMAT_RGBA16UI,
+    RGBA16SI = sys::sg_pixel_format_SG_PIXELFORMAT_RGBA16SI,
+    RGBA16F = sys::sg_pixel_format_SG_PIXELFORMAT_RGBA16F,
+    RGBA32UI = sys::sg_pixel_format_SG_PIXELFORMAT_RGBA32UI,
+    RGBA32SI = sys::sg_pixel_format_SG_PIXELFORMAT_RGBA32SI,
+    RGBA32F = sys::sg_pixel_format_SG_PIXELFORMAT_RGBA32F,
+    Depth = sys::sg_pixel_format_SG_PIXELFORMAT_DEPTH,
+    DepthStencil = sys::sg_pixel_format_SG_PIXELFORMAT_DEPTH_STENCIL,
+    BC1RGBA = sys::sg_pixel_format_SG_PIXELFORMAT_BC1_RGBA,
+    BC2RGBA = sys::sg_pixel_format_SG_PIXELFORMAT_BC2_RGBA,
+    BC3RGBA = sys::sg_pixel_format_SG_PIXELFORMAT_BC3_RGBA,
+    BC4R = sys::sg_pixel_format_SG_PIXELFORMAT_BC4_R,
+    BC4RSN = sys::sg_pixel_format_SG_PIXELFORMAT_BC4_RSN,
+    BC5RG = sys::sg_pixel_format_SG_PIXELFORMAT_BC5_RG,
+    BC5RGSN = sys::sg_pixel_format_SG_PIXELFORMAT_BC5_RGSN,
+    BC6HRGBF = sys::sg_pixel_format_SG_PIXELFORMAT_BC6H_RGBF,
+    BC6HRGBUF = sys::sg_pixel_format_SG_PIXELFORMAT_BC6H_RGBUF,
+    BC7RGBA = sys::sg_pixel_format_SG_PIXELFORMAT_BC7_RGBA,
+    PVRTCRGB2BPP = sys::sg_pixel_format_SG_PIXELFORMAT_PVRTC_RGB_2BPP,
+    PVRTCRGB4BPP = sys::sg_pixel_format_SG_PIXELFORMAT_PVRTC_RGB_4BPP,
+    PVRTCRGBA2BPP = sys::sg_pixel_format_SG_PIXELFORMAT_PVRTC_RGBA_2BPP,
+    PVRTCRGBA4BPP = sys::sg_pixel_format_SG_PIXELFORMAT_PVRTC_RGBA_4BPP,
+    ETC2RGB8 = sys::sg_pixel_format_SG_PIXELFORMAT_ETC2_RGB8,
+    ETC2RGB8A1 = sys::sg_pixel_format_SG_PIXELFORMAT_ETC2_RGB8A1,
+    ETC2RGBA8 = sys::sg_pixel_format_SG_PIXELFORMAT_ETC2_RGBA8,
+    ETC2RG11 = sys::sg_pixel_format_SG_PIXELFORMAT_ETC2_RG11,
+    ETC2RG11SN = sys::sg_pixel_format_SG_PIXELFORMAT_ETC2_RG11SN,
+}
+
+impl Default for PixelFormat {
+    fn default() -> Self {
+        Self::Default
+    }
+}
+
+#[repr(u32)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum PrimitiveType {
+    Default = sys::sg_primitive_type__SG_PRIMITIVETYPE_DEFAULT,
+    Points = sys::sg_primitive_type_SG_PRIMITIVETYPE_POINTS,
+    Lines = sys::sg_primitive_type_SG_PRIMITIVETYPE_LINES,
+    LineStrip = sys::sg_primitive_type_SG_PRIMITIVETYPE_LINE_STRIP,
+    Triangles = sys::sg_primitive_type_SG_PRIMITIVETYPE_TRIANGLES,
+    TriangleStrip = sys::sg_primitive_type_SG_PRIMITIVETYPE_TRIANGLE_STRIP,
+}
+
+impl Default for PrimitiveType {
+    fn default() -> Self {
+        Self::Default
+    }
+}
+
 #[repr(u32)]
 pub enum SamplerType {
     Default = sys::sg_sampler_type__SG_SAMPLERTYPE_DEFAULT,
@@ -272,8 +473,41 @@ impl Default for VertexFormat {
     }
 }
 
+#[repr(u32)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Wrap {
+    Default = sys::sg_wrap__SG_WRAP_DEFAULT,
+    Repeat = sys::sg_wrap_SG_WRAP_REPEAT,
+    ClampToEdge = sys::sg_wrap_SG_WRAP_CLAMP_TO_EDGE,
+    ClampToBorder = sys::sg_wrap_SG_WRAP_CLAMP_TO_BORDER,
+    MirroredRepeat = sys::sg_wrap_SG_WRAP_MIRRORED_REPEAT,
+}
+
+impl Default for Wrap {
+    fn default() -> Self {
+        Self::Default
+    }
+}
+
 #[macro_export]
 macro_rules! _make_immutable_vertex_buffer {
+    // For things like `Vec`s, where the size of the `ident` itself is not the
+    // size of the data, pass a reference to the elements instead.
+    (
+        &$vertices: expr,
+        $label: literal $(,)?
+    ) => {{
+        let v_buffer_desc = $crate::sg::BufferDesc {
+            type_: $crate::sg::BufferType::Vertex as _,
+            usage: $crate::sg::Usage::Immutable as _,
+            data: $crate::sg::range!(&$vertices),
+            label: $crate::cstr!($label),
+            ..<_>::default()
+        };
+
+        // SAFETY: See the `ident` case below.
+        unsafe{ $crate::sg::make_buffer(&v_buffer_desc) }
+    }};
     (
         $vertices: ident $(,)?
         $label: literal $(,)?
@@ -297,6 +531,22 @@ pub use _make_immutable_vertex_buffer as make_immutable_vertex_buffer;
 
 #[macro_export]
 macro_rules! _make_immutable_index_buffer {
+    // See the comment in `make_immutable_vertex_buffer`.
+    (
+        &$indices: expr,
+        $label: literal $(,)?
+    ) => {{
+        let i_buffer_desc = $crate::sg::BufferDesc {
+            type_: $crate::sg::BufferType::Index as _,
+            usage: $crate::sg::Usage::Immutable as _,
+            data: $crate::sg::range!(&$indices),
+            label: $crate::cstr!($label),
+            ..<_>::default()
+        };
+
+        // SAFETY: See the `ident` case below.
+        unsafe { $crate::sg::make_buffer(&i_buffer_desc) }
+    }};
     (
         $indices: ident $(,)?
         $label: literal $(,)?
@@ -391,7 +641,7 @@ impl PassAction {
     }
 }
 
-pub fn begin_default_pass(pass_action: &PassAction, width: Int, height: Int) {
+fn pass_action_parameter(pass_action: &PassAction) -> sys::sg_pass_action {
     type ActionInternal = u32;
 
     let mut pass_action_parameter = sys::sg_pass_action::default();
@@ -422,15 +672,156 @@ pub fn begin_default_pass(pass_action: &PassAction, width: Int, height: Int) {
         value: pass_action.stencil.value,
     };
 
+    pass_action_parameter
+}
+
+pub fn begin_default_pass(pass_action: &PassAction, width: Int, height: Int) {
+    let pass_action_parameter = pass_action_parameter(pass_action);
+
     // SAFETY: The PassAction type ensures that the parameter made above is valid.
     unsafe { sys::sg_begin_default_pass(&pass_action_parameter as _, width, height); }
 }
 
+/// Begins a pass that renders into the images of `pass`'s `RenderTarget`.
+pub fn begin_pass(pass: &OwnedPass, pass_action: &PassAction) {
+    let pass_action_parameter = pass_action_parameter(pass_action);
+
+    // SAFETY: The PassAction type ensures that the parameter made above is valid,
+    // and the `OwnedPass` ensures the pass has not been destroyed.
+    unsafe { sys::sg_begin_pass(pass.handle(), &pass_action_parameter as _); }
+}
+
 pub fn end_pass() {
     // SAFETY: There are no currently known safety issues with this fn.
     unsafe{ sys::sg_end_pass() }
 }
 
+/// A rectangle in framebuffer pixels, as `Int`s, or as `f32`s for the `f` versions
+/// of the fns that take one.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub struct Rect<N = Int> {
+    pub x: N,
+    pub y: N,
+    pub width: N,
+    pub height: N,
+}
+
+impl Rect {
+    /// The rectangle covering a whole framebuffer of the given size.
+    pub fn full(width: Int, height: Int) -> Self {
+        Self { x: 0, y: 0, width, height }
+    }
+
+    /// Splits the rectangle into a left and a right part, with the left one
+    /// getting `left_width` pixels, limited to the width of the rectangle.
+    pub fn split_at_x(self, left_width: Int) -> (Self, Self) {
+        let left_width = left_width.clamp(0, self.width);
+
+        (
+            Self { width: left_width, ..self },
+            Self {
+                x: self.x + left_width,
+                width: self.width - left_width,
+                ..self
+            },
+        )
+    }
+
+    /// Splits the rectangle into a top and a bottom part, with the top one
+    /// getting `top_height` pixels, limited to the height of the rectangle. This
+    /// assumes the origin is in the top left.
+    pub fn split_at_y(self, top_height: Int) -> (Self, Self) {
+        let top_height = top_height.clamp(0, self.height);
+
+        (
+            Self { height: top_height, ..self },
+            Self {
+                y: self.y + top_height,
+                height: self.height - top_height,
+                ..self
+            },
+        )
+    }
+
+    /// The width divided by the height, as `Mat4::perspective` expects. Empty
+    /// rectangles give 1, to avoid a degenerate projection.
+    pub fn aspect_ratio(self) -> f32 {
+        if self.width <= 0 || self.height <= 0 {
+            1.
+        } else {
+            self.width as f32 / self.height as f32
+        }
+    }
+}
+
+#[test]
+fn rects_split_into_two_parts_covering_the_whole() {
+    let rect = Rect { x: 10, y: 20, width: 100, height: 50 };
+
+    assert_eq!(
+        rect.split_at_x(30),
+        (
+            Rect { x: 10, y: 20, width: 30, height: 50 },
+            Rect { x: 40, y: 20, width: 70, height: 50 },
+        )
+    );
+    assert_eq!(
+        rect.split_at_y(20),
+        (
+            Rect { x: 10, y: 20, width: 100, height: 20 },
+            Rect { x: 10, y: 40, width: 100, height: 30 },
+        )
+    );
+}
+
+#[test]
+fn rect_splits_are_clamped_to_the_rect() {
+    let rect = Rect { x: 10, y: 20, width: 100, height: 50 };
+    let empty_left = Rect { width: 0, ..rect };
+    let empty_right = Rect { x: 110, width: 0, ..rect };
+    let empty_top = Rect { height: 0, ..rect };
+    let empty_bottom = Rect { y: 70, height: 0, ..rect };
+
+    assert_eq!(rect.split_at_x(-5), (empty_left, rect));
+    assert_eq!(rect.split_at_x(500), (rect, empty_right));
+    assert_eq!(rect.split_at_y(-5), (empty_top, rect));
+    assert_eq!(rect.split_at_y(500), (rect, empty_bottom));
+}
+
+#[test]
+fn empty_rects_have_an_aspect_ratio_of_one() {
+    assert_eq!(Rect::full(200, 100).aspect_ratio(), 2.);
+    assert_eq!(Rect::full(200, 0).aspect_ratio(), 1.);
+    assert_eq!(Rect::full(0, 100).aspect_ratio(), 1.);
+}
+
+/// Sets the part of the framebuffer that clip space is mapped onto, for the rest
+/// of the current pass. Each pass starts with the viewport covering the whole
+/// framebuffer.
+pub fn apply_viewport(rect: Rect, origin_top_left: bool) {
+    // SAFETY: There are no currently known safety issues with this fn.
+    unsafe { sys::sg_apply_viewport(rect.x, rect.y, rect.width, rect.height, origin_top_left) }
+}
+
+pub fn apply_viewportf(rect: Rect<f32>, origin_top_left: bool) {
+    // SAFETY: There are no currently known safety issues with this fn.
+    unsafe { sys::sg_apply_viewportf(rect.x, rect.y, rect.width, rect.height, origin_top_left) }
+}
+
+/// Sets the part of the framebuffer outside of which nothing is drawn, for the
+/// rest of the current pass. Unlike the viewport, this does not change where
+/// things are drawn. Each pass starts with the scissor rect covering the whole
+/// framebuffer.
+pub fn apply_scissor_rect(rect: Rect, origin_top_left: bool) {
+    // SAFETY: There are no currently known safety issues with this fn.
+    unsafe { sys::sg_apply_scissor_rect(rect.x, rect.y, rect.width, rect.height, origin_top_left) }
+}
+
+pub fn apply_scissor_rectf(rect: Rect<f32>, origin_top_left: bool) {
+    // SAFETY: There are no currently known safety issues with this fn.
+    unsafe { sys::sg_apply_scissor_rectf(rect.x, rect.y, rect.width, rect.height, origin_top_left) }
+}
+
 pub fn commit() {
     // SAFETY: There are no currently known safety issues with this fn.
     unsafe{ sys::sg_commit() }
@@ -0,0 +1,161 @@
+//! What the current backend and device support, so that fallbacks can be chosen
+//! at init, instead of things failing later.
+
+use sokol_bindings_sys as sys;
+use crate::{sg::PixelFormat, Int};
+
+/// Optional features the backend may or may not support.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub struct Features {
+    pub instancing: bool,
+    /// Whether framebuffer coordinates start in the top left, rather than the
+    /// bottom left.
+    pub origin_top_left: bool,
+    pub multiple_render_targets: bool,
+    pub msaa_render_targets: bool,
+    pub image_type_3d: bool,
+    pub image_type_array: bool,
+    pub image_clamp_to_border: bool,
+    pub mrt_independent_blend_state: bool,
+    pub mrt_independent_write_mask: bool,
+}
+
+impl From<sys::sg_features> for Features {
+    fn from(features: sys::sg_features) -> Self {
+        Self {
+            instancing: features.instancing,
+            origin_top_left: features.origin_top_left,
+            multiple_render_targets: features.multiple_render_targets,
+            msaa_render_targets: features.msaa_render_targets,
+            image_type_3d: features.imagetype_3d,
+            image_type_array: features.imagetype_array,
+            image_clamp_to_border: features.image_clamp_to_border,
+            mrt_independent_blend_state: features.mrt_independent_blend_state,
+            mrt_independent_write_mask: features.mrt_independent_write_mask,
+        }
+    }
+}
+
+pub fn query_features() -> Features {
+    // SAFETY: There are no currently known safety issues with this fn.
+    Features::from(unsafe { sys::sg_query_features() })
+}
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub struct Limits {
+    /// In pixels, along each side.
+    pub max_image_size_2d: Int,
+    pub max_image_size_cube: Int,
+    pub max_image_size_3d: Int,
+    pub max_image_size_array: Int,
+    pub max_image_array_layers: Int,
+    pub max_vertex_attrs: Int,
+    /// Only set on GL backends. The amount of vec4s that fit in the uniforms of a
+    /// vertex shader.
+    pub gl_max_vertex_uniform_vectors: Int,
+}
+
+impl From<sys::sg_limits> for Limits {
+    fn from(limits: sys::sg_limits) -> Self {
+        Self {
+            max_image_size_2d: limits.max_image_size_2d,
+            max_image_size_cube: limits.max_image_size_cube,
+            max_image_size_3d: limits.max_image_size_3d,
+            max_image_size_array: limits.max_image_size_array,
+            max_image_array_layers: limits.max_image_array_layers,
+            max_vertex_attrs: limits.max_vertex_attrs,
+            gl_max_vertex_uniform_vectors: limits.gl_max_vertex_uniform_vectors,
+        }
+    }
+}
+
+pub fn query_limits() -> Limits {
+    // SAFETY: There are no currently known safety issues with this fn.
+    Limits::from(unsafe { sys::sg_query_limits() })
+}
+
+/// What images of a given `PixelFormat` can be used for. All `false` means the
+/// format is not supported at all.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub struct PixelFormatInfo {
+    /// Can be sampled from in shaders.
+    pub sample: bool,
+    /// Can be sampled from with linear filtering.
+    pub filter: bool,
+    /// Can be used as a render target.
+    pub render: bool,
+    /// Can be blended into when used as a render target.
+    pub blend: bool,
+    /// Can be used as a multisampled render target.
+    pub msaa: bool,
+    /// Is a depth format.
+    pub depth: bool,
+}
+
+impl From<sys::sg_pixelformat_info> for PixelFormatInfo {
+    fn from(info: sys::sg_pixelformat_info) -> Self {
+        Self {
+            sample: info.sample,
+            filter: info.filter,
+            render: info.render,
+            blend: info.blend,
+            msaa: info.msaa,
+            depth: info.depth,
+        }
+    }
+}
+
+pub fn query_pixel_format(format: PixelFormat) -> PixelFormatInfo {
+    // SAFETY: There are no currently known safety issues with this fn.
+    PixelFormatInfo::from(unsafe { sys::sg_query_pixelformat(format as _) })
+}
+
+impl PixelFormat {
+    /// Shorthand for `query_pixel_format`.
+    pub fn info(self) -> PixelFormatInfo {
+        query_pixel_format(self)
+    }
+
+    /// The first of `formats` that has all the capabilities `needed` has, if any.
+    /// For example, a float format that can be rendered to, falling back to an
+    /// 8 bit one.
+    pub fn first_supported(
+        formats: impl IntoIterator<Item = PixelFormat>,
+        needed: PixelFormatInfo,
+    ) -> Option<PixelFormat> {
+        formats.into_iter().find(|format| {
+            let info = format.info();
+
+            (!needed.sample || info.sample)
+            && (!needed.filter || info.filter)
+            && (!needed.render || info.render)
+            && (!needed.blend || info.blend)
+            && (!needed.msaa || info.msaa)
+            && (!needed.depth || info.depth)
+        })
+    }
+}
+
+#[test]
+fn no_format_is_supported_out_of_none() {
+    assert_eq!(PixelFormat::first_supported([], <_>::default()), None);
+}
+
+#[cfg(feature = "dummy-backend")]
+#[test]
+fn the_dummy_backend_supports_uncompressed_formats_only() {
+    use crate::sg::Context;
+
+    Context::with_dummy(|_context| {
+        // The dummy backend does not fill in any optional features or limits.
+        assert_eq!(query_features(), Features::default());
+        assert_eq!(query_limits(), Limits::default());
+
+        let render = PixelFormatInfo { render: true, ..<_>::default() };
+        assert_eq!(
+            PixelFormat::first_supported([PixelFormat::BC1RGBA, PixelFormat::RGBA8], render),
+            Some(PixelFormat::RGBA8)
+        );
+        assert!(PixelFormat::Depth.info().depth);
+    });
+}
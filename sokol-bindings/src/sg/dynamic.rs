@@ -0,0 +1,199 @@
+//! Buffers whose contents can be replaced or added to after they are made, for
+//! things like debug lines, particles or meshes deformed on the CPU.
+
+use core::marker::PhantomData;
+
+use sokol_bindings_sys as sys;
+use crate::{
+    sg::{
+        resource::label_cstring,
+        Buffer, BufferDesc, BufferType, Context, OwnedBuffer, Range, ResourceState, Usage,
+    },
+    Int,
+};
+
+/// Returned when more elements are passed to `DynamicBuffer::update` than fit.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct BufferOverflow {
+    pub capacity: usize,
+    pub requested: usize,
+}
+
+/// A buffer with room for `capacity` elements of type `T`.
+///
+/// `sokol_gfx` allows either one `update`, or any number of `append`s, per
+/// buffer per frame. Calling `update` more than once in a frame, or both
+/// `update` and `append`, is reported by the validation layer in debug builds.
+#[derive(Debug)]
+pub struct DynamicBuffer<T> {
+    buffer: OwnedBuffer,
+    capacity: usize,
+    /// In bytes.
+    size: usize,
+    _marker: PhantomData<T>,
+}
+
+/// A buffer meant to be updated rarely, like when a mesh is edited.
+pub fn make_dynamic_buffer<T>(
+    context: &Context,
+    type_: BufferType,
+    capacity: usize,
+    label: &str,
+) -> Result<DynamicBuffer<T>, ResourceState> {
+    DynamicBuffer::new(context, type_, Usage::Dynamic, capacity, label)
+}
+
+/// A buffer meant to be updated or appended to every frame.
+pub fn make_stream_buffer<T>(
+    context: &Context,
+    type_: BufferType,
+    capacity: usize,
+    label: &str,
+) -> Result<DynamicBuffer<T>, ResourceState> {
+    DynamicBuffer::new(context, type_, Usage::Stream, capacity, label)
+}
+
+impl <T> DynamicBuffer<T> {
+    fn new(
+        context: &Context,
+        type_: BufferType,
+        usage: Usage,
+        capacity: usize,
+        label: &str,
+    ) -> Result<Self, ResourceState> {
+        let label = label_cstring(label);
+
+        // Buffers cannot be empty.
+        let size = capacity.max(1) * core::mem::size_of::<T>().max(1);
+
+        let desc = BufferDesc {
+            size,
+            type_: type_ as _,
+            usage: usage as _,
+            label: label.as_ptr(),
+            ..<_>::default()
+        };
+
+        // SAFETY: `label` lives until after this call, and there is no data for
+        // the buffer to point to.
+        let buffer = unsafe { OwnedBuffer::from_desc(context, &desc) }?;
+
+        Ok(Self {
+            buffer,
+            capacity,
+            size,
+            _marker: PhantomData,
+        })
+    }
+
+    pub fn handle(&self) -> Buffer {
+        self.buffer.handle()
+    }
+
+    /// The amount of elements that fit in the buffer.
+    pub fn capacity(&self) -> usize {
+        self.capacity
+    }
+
+    /// Replaces the contents of the buffer with `data`, which should be done at
+    /// most once per frame.
+    pub fn update(&mut self, data: &[T]) -> Result<(), BufferOverflow> {
+        if data.len() > self.capacity {
+            return Err(BufferOverflow {
+                capacity: self.capacity,
+                requested: data.len(),
+            });
+        }
+
+        let range = Range {
+            size: core::mem::size_of_val(data),
+            ptr: data.as_ptr() as _,
+        };
+
+        // SAFETY: `data` fits in the buffer and lives until after this call.
+        unsafe { sys::sg_update_buffer(self.handle(), &range) }
+
+        Ok(())
+    }
+
+    /// Adds `data` after whatever was appended earlier in the frame. Returns the
+    /// byte offset it was placed at, to be used in
+    /// `Bindings::vertex_buffer_offsets` or `Bindings::index_buffer_offset`. The
+    /// offsets start from 0 again on the next frame.
+    ///
+    /// Returns `None`, without appending anything, if `data` does not fit in what
+    /// is left of the buffer this frame. What was appended earlier is unaffected.
+    pub fn append(&mut self, data: &[T]) -> Option<Int> {
+        let handle = self.handle();
+
+        // `sokol_gfx` only moves the append position back to the start on the
+        // first append of each frame, so we append nothing to make sure the
+        // position we query is the one for this frame.
+        let empty = Range {
+            size: 0,
+            ptr: data.as_ptr() as _,
+        };
+        // SAFETY: `empty` points to `data`, which lives until after this call,
+        // and nothing is read from it.
+        unsafe { sys::sg_append_buffer(handle, &empty) };
+
+        // SAFETY: There are no currently known safety issues with this fn.
+        let info = unsafe { sys::sg_query_buffer_info(handle) };
+
+        let size = core::mem::size_of_val(data);
+        // `sokol_gfx` rounds each append up to a multiple of 4 bytes. An append
+        // that does not fit is reported by the validation layer in debug
+        // builds, so we check before appending.
+        let end = usize::try_from(info.append_pos).ok()? + size.next_multiple_of(4);
+        if info.append_overflow || end > self.size {
+            return None
+        }
+
+        let range = Range {
+            size,
+            ptr: data.as_ptr() as _,
+        };
+
+        // SAFETY: `data` fits in the buffer and lives until after this call.
+        Some(unsafe { sys::sg_append_buffer(handle, &range) })
+    }
+
+    /// Whether `sokol_gfx` marked the buffer as overflowed this frame, in which
+    /// case draws using it are skipped. `append` checks that there is room first,
+    /// so this only happens with appends made through `sg` directly.
+    pub fn overflowed(&self) -> bool {
+        // SAFETY: There are no currently known safety issues with this fn.
+        unsafe { sys::sg_query_buffer_overflow(self.handle()) }
+    }
+}
+
+#[cfg(feature = "dummy-backend")]
+#[test]
+fn data_that_does_not_fit_is_rejected() {
+    Context::with_dummy(|context| {
+        // Separate buffers, since `update` and `append` cannot both be used on one
+        // buffer in the same frame.
+        let mut updated = make_dynamic_buffer::<f32>(context, BufferType::Vertex, 2, "updated")
+            .unwrap();
+
+        assert_eq!(
+            updated.update(&[1., 2., 3.]),
+            Err(BufferOverflow { capacity: 2, requested: 3 })
+        );
+        assert_eq!(updated.update(&[1., 2.]), Ok(()));
+
+        let mut appended = make_stream_buffer::<f32>(context, BufferType::Vertex, 2, "appended")
+            .unwrap();
+
+        assert_eq!(appended.append(&[1.]), Some(0));
+        assert_eq!(appended.append(&[2., 3.]), None);
+        assert!(!appended.overflowed());
+        // Data that still fits can be appended after one that did not.
+        assert_eq!(appended.append(&[2.]), Some(4));
+        assert_eq!(appended.append(&[3.]), None);
+
+        // The whole buffer is available again on the next frame.
+        crate::sg::commit();
+        assert_eq!(appended.append(&[1., 2.]), Some(0));
+    });
+}
@@ -0,0 +1,304 @@
+//! A typed way to make pipelines, so that we don't need to fill in a raw
+//! `PipelineDesc` with `as _` casts and `cstr!` labels each time.
+
+use std::{ffi::CString, fmt, rc::Rc};
+
+use sokol_bindings_sys as sys;
+use crate::{
+    sg::{
+        resource::label_cstring,
+        BlendFactor, BlendOp, Bindings, Color, CompareFunc, Context, CullMode,
+        DepthState, FaceWinding, IndexType, LayoutDesc, OwnedPipeline, OwnedShader,
+        PipelineDesc, PrimitiveType, ResourceState, VertexFormat,
+    },
+    Int,
+};
+
+// These match the array lengths in `PipelineDesc` and `Bindings`.
+const MAX_COLOR_ATTACHMENTS: Int = 4;
+const MAX_VERTEX_BUFFERS: usize = 8;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum PipelineError {
+    /// None of the vertex attributes in the layout have a format set.
+    EmptyLayout,
+    TooManyColorAttachments(Int),
+    /// The bindings passed to `PipelineBuilder::bindings` have an index buffer,
+    /// but the pipeline does not use one.
+    IndexBufferWithoutIndexType,
+    /// The pipeline uses indices, but the bindings passed to
+    /// `PipelineBuilder::bindings` do not have an index buffer.
+    IndexTypeWithoutIndexBuffer,
+    /// The layout refers to this vertex buffer slot, but the bindings passed to
+    /// `PipelineBuilder::bindings` do not have a buffer there.
+    MissingVertexBuffer(Int),
+    /// `sokol_gfx` did not accept the pipeline. The validation layer output,
+    /// in debug builds, says why.
+    Resource(ResourceState),
+}
+
+impl fmt::Display for PipelineError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        use PipelineError::*;
+
+        match self {
+            EmptyLayout => write!(f, "the vertex layout has no attributes"),
+            TooManyColorAttachments(count) => write!(
+                f,
+                "{count} color attachments were requested, but the max is {MAX_COLOR_ATTACHMENTS}"
+            ),
+            IndexBufferWithoutIndexType => write!(
+                f,
+                "the bindings have an index buffer, but the index type is `None`"
+            ),
+            IndexTypeWithoutIndexBuffer => write!(
+                f,
+                "the pipeline uses indices, but the bindings have no index buffer"
+            ),
+            MissingVertexBuffer(slot) => write!(
+                f,
+                "the layout uses vertex buffer slot {slot}, but the bindings have no buffer there"
+            ),
+            Resource(state) => write!(f, "the pipeline ended up in the {state:?} state"),
+        }
+    }
+}
+
+impl std::error::Error for PipelineError {}
+
+/// Builds an `OwnedPipeline` that keeps its shader alive. The blend settings
+/// apply to the first color attachment.
+///
+/// ```ignore
+/// let pipeline = PipelineBuilder::new(shader, layout)
+///     .index_type(IndexType::UInt16)
+///     .cull_mode(CullMode::Back)
+///     .depth(depth)
+///     .label("model-pipeline")
+///     .bindings(&bindings)
+///     .build(&context)?;
+/// ```
+#[derive(Debug)]
+pub struct PipelineBuilder {
+    shader: Rc<OwnedShader>,
+    desc: PipelineDesc,
+    label: CString,
+    has_index_buffer: Option<bool>,
+    vertex_buffer_slots: Option<[bool; MAX_VERTEX_BUFFERS]>,
+}
+
+impl PipelineBuilder {
+    pub fn new(shader: Rc<OwnedShader>, layout: LayoutDesc) -> Self {
+        let desc = PipelineDesc {
+            shader: shader.handle(),
+            layout,
+            ..<_>::default()
+        };
+
+        Self {
+            shader,
+            desc,
+            label: CString::default(),
+            has_index_buffer: None,
+            vertex_buffer_slots: None,
+        }
+    }
+
+    pub fn index_type(mut self, index_type: IndexType) -> Self {
+        self.desc.index_type = index_type as _;
+        self
+    }
+
+    pub fn primitive_type(mut self, primitive_type: PrimitiveType) -> Self {
+        self.desc.primitive_type = primitive_type as _;
+        self
+    }
+
+    pub fn cull_mode(mut self, cull_mode: CullMode) -> Self {
+        self.desc.cull_mode = cull_mode as _;
+        self
+    }
+
+    pub fn face_winding(mut self, face_winding: FaceWinding) -> Self {
+        self.desc.face_winding = face_winding as _;
+        self
+    }
+
+    /// Replaces all the depth settings, for example with the ones from a
+    /// `make_shader_etc` function.
+    pub fn depth(mut self, depth: DepthState) -> Self {
+        self.desc.depth = depth;
+        self
+    }
+
+    pub fn depth_compare(mut self, compare: CompareFunc) -> Self {
+        self.desc.depth.compare = compare as _;
+        self
+    }
+
+    pub fn depth_write(mut self, enabled: bool) -> Self {
+        self.desc.depth.write_enabled = enabled;
+        self
+    }
+
+    /// Enables blending, with the same factors and operation for the color and
+    /// alpha channels. Call `blend_alpha` afterwards to set the alpha ones
+    /// separately.
+    pub fn blend(mut self, src: BlendFactor, dst: BlendFactor, op: BlendOp) -> Self {
+        let blend = &mut self.desc.colors[0].blend;
+        blend.enabled = true;
+        blend.src_factor_rgb = src as _;
+        blend.dst_factor_rgb = dst as _;
+        blend.op_rgb = op as _;
+        blend.src_factor_alpha = src as _;
+        blend.dst_factor_alpha = dst as _;
+        blend.op_alpha = op as _;
+        self
+    }
+
+    pub fn blend_alpha(mut self, src: BlendFactor, dst: BlendFactor, op: BlendOp) -> Self {
+        let blend = &mut self.desc.colors[0].blend;
+        blend.enabled = true;
+        blend.src_factor_alpha = src as _;
+        blend.dst_factor_alpha = dst as _;
+        blend.op_alpha = op as _;
+        self
+    }
+
+    pub fn blend_color(mut self, color: Color) -> Self {
+        self.desc.blend_color = color;
+        self
+    }
+
+    pub fn color_count(mut self, color_count: Int) -> Self {
+        self.desc.color_count = color_count;
+        self
+    }
+
+    pub fn sample_count(mut self, sample_count: Int) -> Self {
+        self.desc.sample_count = sample_count;
+        self
+    }
+
+    pub fn label(mut self, label: &str) -> Self {
+        self.label = label_cstring(label);
+        self
+    }
+
+    /// Checks, when building, that the pipeline can be used with these
+    /// bindings. Only which buffers are present is checked, not their contents.
+    pub fn bindings(mut self, bindings: &Bindings) -> Self {
+        self.has_index_buffer = Some(bindings.index_buffer.id != 0);
+        self.vertex_buffer_slots = Some(bindings.vertex_buffers.map(|buffer| buffer.id != 0));
+        self
+    }
+
+    pub fn build(mut self, context: &Context) -> Result<OwnedPipeline, PipelineError> {
+        self.validate()?;
+
+        self.desc.label = self.label.as_ptr();
+
+        // SAFETY: The desc only points to the label, which lives until after this
+        // call.
+        let mut pipeline = unsafe { OwnedPipeline::from_desc(context, &self.desc) }
+            .map_err(PipelineError::Resource)?;
+
+        pipeline.shader = Some(self.shader);
+
+        Ok(pipeline)
+    }
+
+    fn validate(&self) -> Result<(), PipelineError> {
+        use PipelineError::*;
+
+        let used_attrs = self.desc.layout.attrs.iter()
+            .filter(|attr| attr.format != VertexFormat::Invalid as sys::sg_vertex_format);
+
+        if used_attrs.clone().next().is_none() {
+            return Err(EmptyLayout);
+        }
+
+        if self.desc.color_count > MAX_COLOR_ATTACHMENTS {
+            return Err(TooManyColorAttachments(self.desc.color_count));
+        }
+
+        // `IndexType::Default` means no indices.
+        let uses_indices = self.desc.index_type == IndexType::UInt16 as sys::sg_index_type
+            || self.desc.index_type == IndexType::UInt32 as sys::sg_index_type;
+
+        match (uses_indices, self.has_index_buffer) {
+            (false, Some(true)) => return Err(IndexBufferWithoutIndexType),
+            (true, Some(false)) => return Err(IndexTypeWithoutIndexBuffer),
+            _ => {}
+        }
+
+        if let Some(slots) = self.vertex_buffer_slots {
+            for attr in used_attrs {
+                let has_buffer = slots.get(attr.buffer_index as usize)
+                    .copied()
+                    .unwrap_or(false);
+
+                if !has_buffer {
+                    return Err(MissingVertexBuffer(attr.buffer_index));
+                }
+            }
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(feature = "dummy-backend")]
+#[test]
+fn validate_reports_each_kind_of_mistake() {
+    use crate::sg::{Buffer, ShaderDesc};
+    use PipelineError::*;
+
+    Context::with_dummy(|context| {
+        // SAFETY: The default desc does not point to anything, and the dummy
+        // backend does not need any shader source.
+        let shader = Rc::new(
+            unsafe { OwnedShader::from_desc(context, &ShaderDesc::default()) }.unwrap()
+        );
+
+        let mut layout = LayoutDesc::default();
+        layout.attrs[0].format = VertexFormat::Float3 as _;
+        let builder = || PipelineBuilder::new(shader.clone(), layout);
+
+        let buffer = Buffer { id: 1 };
+        let mut bindings = Bindings::default();
+        bindings.vertex_buffers[0] = buffer;
+        let mut indexed_bindings = bindings;
+        indexed_bindings.index_buffer = buffer;
+
+        assert_eq!(builder().bindings(&bindings).validate(), Ok(()));
+        assert_eq!(
+            builder().index_type(IndexType::UInt16).bindings(&indexed_bindings).validate(),
+            Ok(())
+        );
+
+        assert_eq!(
+            PipelineBuilder::new(shader.clone(), LayoutDesc::default()).validate(),
+            Err(EmptyLayout)
+        );
+        assert_eq!(
+            builder().color_count(MAX_COLOR_ATTACHMENTS + 1).validate(),
+            Err(TooManyColorAttachments(MAX_COLOR_ATTACHMENTS + 1))
+        );
+        assert_eq!(
+            builder().bindings(&indexed_bindings).validate(),
+            Err(IndexBufferWithoutIndexType)
+        );
+        assert_eq!(
+            builder().index_type(IndexType::UInt32).bindings(&bindings).validate(),
+            Err(IndexTypeWithoutIndexBuffer)
+        );
+
+        layout.attrs[1].format = VertexFormat::Float2 as _;
+        layout.attrs[1].buffer_index = 1;
+        assert_eq!(
+            PipelineBuilder::new(shader.clone(), layout).bindings(&bindings).validate(),
+            Err(MissingVertexBuffer(1))
+        );
+    });
+}
@@ -0,0 +1,164 @@
+//! Images that can be rendered into, along with the pass that does so, for
+//! things like post-processing, shadow maps and render-to-texture.
+
+use sokol_bindings_sys as sys;
+use crate::{
+    sg::{
+        resource::label_cstring,
+        Context, Image, ImageDesc, OwnedImage, OwnedPass, PassDesc, PixelFormat,
+        ResourceState,
+    },
+    Int,
+};
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct RenderTargetFormat {
+    /// `PixelFormat::Default` means the same format as the default pass uses.
+    pub color: PixelFormat,
+    /// `None` means there is no depth image, so depth testing cannot be used.
+    pub depth: Option<PixelFormat>,
+    /// Values above 1 give a multisampled target. Multisampled images cannot be
+    /// sampled from directly.
+    pub sample_count: Int,
+}
+
+impl Default for RenderTargetFormat {
+    fn default() -> Self {
+        Self {
+            color: PixelFormat::Default,
+            depth: Some(PixelFormat::Depth),
+            sample_count: 1,
+        }
+    }
+}
+
+/// A color image, an optional depth image, and the pass that renders to them.
+/// Pass it to `sg::begin_pass` to draw into it, and bind `color_image` to sample
+/// from what was drawn afterwards.
+///
+/// Pipelines used in the pass need to have the same formats and sample count.
+#[derive(Debug)]
+pub struct RenderTarget {
+    // Declared first so that it is dropped before the images it refers to.
+    pass: OwnedPass,
+    color: OwnedImage,
+    depth: Option<OwnedImage>,
+    width: Int,
+    height: Int,
+    format: RenderTargetFormat,
+}
+
+impl RenderTarget {
+    pub fn new(
+        context: &Context,
+        width: Int,
+        height: Int,
+        format: RenderTargetFormat,
+        label: &str,
+    ) -> Result<Self, ResourceState> {
+        let make_image = |pixel_format: PixelFormat, label: String| {
+            let label = label_cstring(&label);
+
+            let desc = ImageDesc {
+                render_target: true,
+                width,
+                height,
+                pixel_format: pixel_format as _,
+                sample_count: format.sample_count,
+                min_filter: sys::sg_filter_SG_FILTER_LINEAR,
+                mag_filter: sys::sg_filter_SG_FILTER_LINEAR,
+                wrap_u: sys::sg_wrap_SG_WRAP_CLAMP_TO_EDGE,
+                wrap_v: sys::sg_wrap_SG_WRAP_CLAMP_TO_EDGE,
+                label: label.as_ptr(),
+                ..<_>::default()
+            };
+
+            // SAFETY: `label` lives until after this call, and render targets
+            // have no initial data.
+            unsafe { OwnedImage::from_desc(context, &desc) }
+        };
+
+        let color = make_image(format.color, format!("{label}-color"))?;
+        let depth = format.depth
+            .map(|depth_format| make_image(depth_format, format!("{label}-depth")))
+            .transpose()?;
+
+        let pass_label = label_cstring(&format!("{label}-pass"));
+
+        let mut desc = PassDesc {
+            label: pass_label.as_ptr(),
+            ..<_>::default()
+        };
+        desc.color_attachments[0].image = color.handle();
+        if let Some(depth) = &depth {
+            desc.depth_stencil_attachment.image = depth.handle();
+        }
+
+        // SAFETY: `pass_label` lives until after this call, and the images are
+        // kept alive alongside the pass.
+        let pass = unsafe { OwnedPass::from_desc(context, &desc) }?;
+
+        Ok(Self {
+            pass,
+            color,
+            depth,
+            width,
+            height,
+            format,
+        })
+    }
+
+    pub fn pass(&self) -> &OwnedPass {
+        &self.pass
+    }
+
+    pub fn color_image(&self) -> Image {
+        self.color.handle()
+    }
+
+    pub fn depth_image(&self) -> Option<Image> {
+        self.depth.as_ref().map(OwnedImage::handle)
+    }
+
+    pub fn width(&self) -> Int {
+        self.width
+    }
+
+    pub fn height(&self) -> Int {
+        self.height
+    }
+
+    pub fn format(&self) -> RenderTargetFormat {
+        self.format
+    }
+}
+
+#[cfg(feature = "dummy-backend")]
+#[test]
+fn render_targets_own_their_pass_and_images() {
+    Context::with_dummy(|context| {
+        let target = RenderTarget::new(context, 64, 32, <_>::default(), "target")
+            .unwrap();
+
+        let pass = target.pass().handle();
+        let color = target.color_image();
+        let depth = target.depth_image().unwrap();
+
+        let states = || {
+            // SAFETY: There are no currently known safety issues with these fns.
+            unsafe {
+                [
+                    sys::sg_query_pass_state(pass),
+                    sys::sg_query_image_state(color),
+                    sys::sg_query_image_state(depth),
+                ]
+            }.map(ResourceState::from)
+        };
+
+        assert_eq!(states(), [ResourceState::Valid; 3]);
+
+        drop(target);
+
+        assert_eq!(states(), [ResourceState::Invalid; 3]);
+    });
+}
@@ -0,0 +1,383 @@
+//! Owned versions of the resource handles, that destroy the resource when they
+//! are dropped, and a `Context` token that keeps `sokol_gfx` set up for as long
+//! as anything that needs it is around.
+
+use std::{
+    ffi::CString,
+    rc::Rc,
+    sync::atomic::{AtomicBool, Ordering},
+};
+
+use sokol_bindings_sys as sys;
+use crate::{
+    sg::{
+        Buffer, BufferDesc, BufferType, Image, ImageDesc, Pass, PassDesc, Pipeline,
+        PipelineDesc, Range, Shader, ShaderDesc, Usage,
+    },
+    Int,
+};
+
+#[repr(u32)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum ResourceState {
+    Initial = sys::sg_resource_state_SG_RESOURCESTATE_INITIAL,
+    Alloc = sys::sg_resource_state_SG_RESOURCESTATE_ALLOC,
+    Valid = sys::sg_resource_state_SG_RESOURCESTATE_VALID,
+    Failed = sys::sg_resource_state_SG_RESOURCESTATE_FAILED,
+    Invalid = sys::sg_resource_state_SG_RESOURCESTATE_INVALID,
+}
+
+impl From<sys::sg_resource_state> for ResourceState {
+    fn from(state: sys::sg_resource_state) -> Self {
+        use ResourceState::*;
+
+        match state {
+            sys::sg_resource_state_SG_RESOURCESTATE_INITIAL => Initial,
+            sys::sg_resource_state_SG_RESOURCESTATE_ALLOC => Alloc,
+            sys::sg_resource_state_SG_RESOURCESTATE_VALID => Valid,
+            sys::sg_resource_state_SG_RESOURCESTATE_FAILED => Failed,
+            // Includes handles that do not refer to anything.
+            _ => Invalid,
+        }
+    }
+}
+
+static IS_SET_UP: AtomicBool = AtomicBool::new(false);
+
+#[derive(Debug)]
+struct ContextInner;
+
+impl Drop for ContextInner {
+    fn drop(&mut self) {
+        // SAFETY: A `ContextInner` is only created after `sg_setup` is called, and
+        // only one exists at a time.
+        unsafe { sys::sg_shutdown() }
+
+        IS_SET_UP.store(false, Ordering::SeqCst);
+    }
+}
+
+/// Proof that `sokol_gfx` has been set up. `sg::shutdown` is called once this,
+/// all of its clones, and all of the owned resources, which each hold a clone,
+/// are dropped. So none of the owned resources can outlive `sokol_gfx`.
+///
+/// Since `sokol_app` tears down the graphics context after the cleanup callback
+/// returns, everything holding a `Context` should be dropped in that callback.
+/// `sg::shutdown` should not be called directly while there is a `Context`.
+#[derive(Clone, Debug)]
+pub struct Context {
+    _inner: Rc<ContextInner>,
+}
+
+impl Context {
+    /// Sets up `sokol_gfx` with the graphics context from `sokol_app`, like
+    /// `setup_default_context` does.
+    ///
+    /// # Panics
+    /// If there is already a `Context`.
+    #[cfg(not(feature = "dummy-backend"))]
+    pub fn setup_default() -> Self {
+        let desc = sys::sg_desc {
+            context: crate::glue::sapp_sgcontext(),
+            ..<_>::default()
+        };
+
+        // SAFETY: The desc value is valid. Using `sapp_sg_context` is explicitly
+        // recommended by the header docs.
+        unsafe { Self::setup(&desc) }
+    }
+
+    /// Sets up the dummy backend, which needs no graphics context.
+    ///
+    /// # Panics
+    /// If there is already a `Context`.
+    #[cfg(feature = "dummy-backend")]
+    pub fn setup_default() -> Self {
+        // SAFETY: The default desc has no pointers in it.
+        unsafe { Self::setup(&sys::sg_desc::default()) }
+    }
+
+    /// Runs `f` with a freshly set up `Context`, which is shut down afterwards.
+    /// Since there can only be one `Context` at a time, and tests run on
+    /// multiple threads, this waits for other calls to this function to finish
+    /// first. Resources made in `f` should be dropped before it returns.
+    #[cfg(feature = "dummy-backend")]
+    pub fn with_dummy<R>(f: impl FnOnce(&Context) -> R) -> R {
+        use std::sync::{Mutex, PoisonError};
+
+        static LOCK: Mutex<()> = Mutex::new(());
+
+        // A panic in another call leaves nothing set up, so the lock is still
+        // usable.
+        let _guard = LOCK.lock().unwrap_or_else(PoisonError::into_inner);
+
+        f(&Self::setup_default())
+    }
+
+    /// # Safety
+    /// The pointers in `desc` must be valid, as described in the `sokol_gfx`
+    /// header docs.
+    ///
+    /// # Panics
+    /// If there is already a `Context`.
+    pub unsafe fn setup(desc: &sys::sg_desc) -> Self {
+        let was_set_up = IS_SET_UP.swap(true, Ordering::SeqCst);
+        assert!(!was_set_up, "sokol_gfx should only be set up once at a time");
+
+        unsafe { sys::sg_setup(desc); }
+
+        Self {
+            _inner: Rc::new(ContextInner),
+        }
+    }
+}
+
+/// Labels are optional, so if the label cannot be passed to C, we leave it out.
+pub(super) fn label_cstring(label: &str) -> CString {
+    CString::new(label).unwrap_or_default()
+}
+
+macro_rules! owned_resource {
+    (
+        $(#[$attributes: meta])*
+        $name: ident,
+        $handle: ty,
+        $desc: ty,
+        $make: path,
+        $destroy: path,
+        $query_state: path
+        $(, { $($field: ident: $field_type: ty),* $(,)? })? $(,)?
+    ) => {
+        $(#[$attributes])*
+        #[derive(Debug)]
+        pub struct $name {
+            handle: $handle,
+            $($(pub(super) $field: $field_type,)*)?
+            _context: Context,
+        }
+
+        impl $name {
+            /// Returns the state the resource ended up in, if it is not
+            /// `ResourceState::Valid`. The resource is destroyed in that case.
+            ///
+            /// # Safety
+            /// The pointers in `desc` must be valid, as described in the
+            /// `sokol_gfx` header docs.
+            pub unsafe fn from_desc(context: &Context, desc: &$desc) -> Result<Self, ResourceState> {
+                let handle = unsafe { $make(desc) };
+
+                unsafe { Self::from_handle(context, handle) }
+            }
+
+            /// Takes ownership of a resource that was made some other way. Returns
+            /// the state the resource is in, if it is not `ResourceState::Valid`.
+            /// The resource is destroyed in that case.
+            ///
+            /// # Safety
+            /// Nothing else may destroy the resource, including another owned
+            /// handle.
+            pub unsafe fn from_handle(context: &Context, handle: $handle) -> Result<Self, ResourceState> {
+                // Resources that failed to be created still need to be destroyed,
+                // so we make this first, and let it drop in that case.
+                let owned = Self {
+                    handle,
+                    $($($field: <$field_type>::default(),)*)?
+                    _context: context.clone(),
+                };
+
+                // SAFETY: There are no currently known safety issues with this fn.
+                match ResourceState::from(unsafe { $query_state(handle) }) {
+                    ResourceState::Valid => Ok(owned),
+                    state => Err(state),
+                }
+            }
+
+            /// The raw handle, for use with things like `sg::Bindings`. The handle
+            /// becomes invalid once this is dropped.
+            pub fn handle(&self) -> $handle {
+                self.handle
+            }
+
+            pub fn state(&self) -> ResourceState {
+                // SAFETY: There are no currently known safety issues with this fn.
+                ResourceState::from(unsafe { $query_state(self.handle) })
+            }
+        }
+
+        impl Drop for $name {
+            fn drop(&mut self) {
+                // SAFETY: The `Context` we hold ensures `sokol_gfx` is still set
+                // up, and we are the only owner of the handle.
+                unsafe { $destroy(self.handle) }
+            }
+        }
+    }
+}
+
+owned_resource!{
+    OwnedBuffer,
+    Buffer,
+    BufferDesc,
+    sys::sg_make_buffer,
+    sys::sg_destroy_buffer,
+    sys::sg_query_buffer_state,
+}
+
+owned_resource!{
+    OwnedImage,
+    Image,
+    ImageDesc,
+    sys::sg_make_image,
+    sys::sg_destroy_image,
+    sys::sg_query_image_state,
+}
+
+owned_resource!{
+    /// Pipelines refer to their shader, so the shader should be kept around for
+    /// at least as long as the pipeline.
+    OwnedShader,
+    Shader,
+    ShaderDesc,
+    sys::sg_make_shader,
+    sys::sg_destroy_shader,
+    sys::sg_query_shader_state,
+}
+
+owned_resource!{
+    /// Pipelines made with a `PipelineBuilder` keep their shader alive.
+    OwnedPipeline,
+    Pipeline,
+    PipelineDesc,
+    sys::sg_make_pipeline,
+    sys::sg_destroy_pipeline,
+    sys::sg_query_pipeline_state,
+    {
+        shader: Option<Rc<OwnedShader>>,
+    },
+}
+
+owned_resource!{
+    /// Passes refer to their images, so the images should be kept around for at
+    /// least as long as the pass. `RenderTarget` does this.
+    OwnedPass,
+    Pass,
+    PassDesc,
+    sys::sg_make_pass,
+    sys::sg_destroy_pass,
+    sys::sg_query_pass_state,
+}
+
+impl OwnedBuffer {
+    /// An immutable buffer containing a copy of `data`. Like the
+    /// `make_immutable_*_buffer` macros, but for any slice.
+    pub fn immutable<T>(
+        context: &Context,
+        type_: BufferType,
+        data: &[T],
+        label: &str,
+    ) -> Result<Self, ResourceState> {
+        let label = label_cstring(label);
+
+        let desc = BufferDesc {
+            type_: type_ as _,
+            usage: Usage::Immutable as _,
+            data: Range {
+                size: core::mem::size_of_val(data),
+                ptr: data.as_ptr() as _,
+            },
+            label: label.as_ptr(),
+            ..<_>::default()
+        };
+
+        // SAFETY: `data` and `label` live until after this call.
+        unsafe { Self::from_desc(context, &desc) }
+    }
+
+    pub fn immutable_vertices<V>(
+        context: &Context,
+        vertices: &[V],
+        label: &str,
+    ) -> Result<Self, ResourceState> {
+        Self::immutable(context, BufferType::Vertex, vertices, label)
+    }
+
+    pub fn immutable_indices<I>(
+        context: &Context,
+        indices: &[I],
+        label: &str,
+    ) -> Result<Self, ResourceState> {
+        Self::immutable(context, BufferType::Index, indices, label)
+    }
+}
+
+impl OwnedImage {
+    /// An immutable 2D image with the default RGBA8 pixel format.
+    ///
+    /// # Panics
+    /// If `pixels` does not contain exactly `width * height` pixels.
+    pub fn rgba8(
+        context: &Context,
+        width: Int,
+        height: Int,
+        pixels: &[u8],
+        label: &str,
+    ) -> Result<Self, ResourceState> {
+        assert_eq!(
+            pixels.len(),
+            width.max(0) as usize * height.max(0) as usize * 4,
+            "pixels should contain 4 bytes for each pixel"
+        );
+
+        let label = label_cstring(label);
+
+        let mut desc = ImageDesc {
+            width,
+            height,
+            label: label.as_ptr(),
+            ..<_>::default()
+        };
+        desc.data.subimage[0][0] = Range {
+            size: core::mem::size_of_val(pixels),
+            ptr: pixels.as_ptr() as _,
+        };
+
+        // SAFETY: `pixels` and `label` live until after this call.
+        unsafe { Self::from_desc(context, &desc) }
+    }
+}
+
+#[cfg(feature = "dummy-backend")]
+#[test]
+fn owned_resources_are_destroyed_when_dropped() {
+    Context::with_dummy(|context| {
+        let buffer = OwnedBuffer::immutable_vertices(context, &[1., 2., 3.], "buffer")
+            .unwrap();
+        let handle = buffer.handle();
+
+        assert_eq!(buffer.state(), ResourceState::Valid);
+
+        drop(buffer);
+
+        // SAFETY: There are no currently known safety issues with this fn.
+        let state = unsafe { sys::sg_query_buffer_state(handle) };
+        assert_eq!(ResourceState::from(state), ResourceState::Invalid);
+    });
+}
+
+#[cfg(feature = "dummy-backend")]
+#[test]
+fn the_context_lasts_until_the_last_resource_is_dropped() {
+    // Everything is checked inside `with_dummy`, so no other test can set up a
+    // `Context` while this one's is still alive.
+    Context::with_dummy(|context| {
+        let clone = context.clone();
+        let buffer = OwnedBuffer::immutable_indices(&clone, &[0u16, 1, 2], "buffer")
+            .unwrap();
+
+        // The clone was dropped, but the buffer still holds one of its own.
+        drop(clone);
+
+        assert!(IS_SET_UP.load(Ordering::SeqCst));
+        assert_eq!(buffer.state(), ResourceState::Valid);
+    });
+}
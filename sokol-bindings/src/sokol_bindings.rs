@@ -40,10 +40,14 @@ macro_rules! cb_wrap_userdata {
     }}
 }
 
+pub mod log;
+#[cfg(not(feature = "dummy-backend"))]
 pub mod sapp;
 pub mod sg;
+#[cfg(not(feature = "dummy-backend"))]
 pub mod glue;
 
+#[cfg(not(feature = "dummy-backend"))]
 pub fn setup_default_context() {
     let desc = &sg_desc{
         context: glue::sapp_sgcontext(),
@@ -0,0 +1,10 @@
+fn main() {
+    // Each `src/shaders/*.glsl` becomes `$OUT_DIR/*.rs`, which the shader module
+    // of the same name `include!`s.
+    shader_gen::generate_dir(
+        "src/shaders",
+        &shader_gen::Options {
+            sokol_extras_path: "crate".to_owned(),
+        },
+    );
+}
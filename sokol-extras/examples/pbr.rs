@@ -0,0 +1,162 @@
+use sokol_bindings::{
+    cstr,
+    sapp::{self, IconDesc},
+    setup_default_context,
+    sg::{self, begin_default_pass, end_pass, commit, query_backend, Action, Bindings, Color, ColorAttachmentAction, PassAction, Pipeline, PipelineDesc},
+    Int,
+};
+use sokol_extras::{
+    images::{flat_normal, sky_gradient, white},
+    shaders::pbr,
+};
+use math::{
+    geom::gen_uv_sphere_mesh,
+    mat4::Mat4,
+    vec3::vec3,
+    vec4::vec4,
+    angle::Degrees,
+};
+
+/// The spheres are in a `GRID_SIZE` by `GRID_SIZE` grid, with metallic going
+/// from 0 to 1 bottom to top, and roughness going from 0 to 1 left to right.
+const GRID_SIZE: u16 = 7;
+const SPACING: f32 = 2.5;
+
+#[derive(Default)]
+struct State {
+    bind: Bindings,
+    pipe: Pipeline,
+    index_count: Int,
+    environment: Option<pbr::Environment>,
+    ry: f32,
+}
+
+fn init(state: &mut State) {
+    setup_default_context();
+
+    let sphere = gen_uv_sphere_mesh(1., 32, 16);
+    let vertices = pbr::mesh_vertices(&sphere);
+
+    state.bind.vertex_buffers[0] = sg::make_immutable_vertex_buffer!(
+        &vertices[..],
+        "sphere-vertices"
+    );
+
+    state.bind.index_buffer = sg::make_immutable_index_buffer!(
+        &sphere.indices[..],
+        "sphere-indices"
+    );
+    state.index_count = sphere.indices.len() as _;
+
+    // These are freed by `sg::shutdown` in `cleanup`.
+    let environment = pbr::Environment::sky_gradient(sky_gradient::make());
+    pbr::Maps::neutral(white::make(), flat_normal::make()).bind(&mut state.bind, &environment);
+    state.environment = Some(environment);
+
+    let (shader, layout, depth) = pbr::make_shader_etc(query_backend());
+
+    let pipeline_desc = PipelineDesc{
+        layout,
+        shader,
+        index_type: sg::IndexType::UInt16 as _,
+        cull_mode: sg::CullMode::Back as _,
+        depth,
+        label: cstr!("sphere-pipeline"),
+        ..PipelineDesc::default()
+    };
+    state.pipe = unsafe { sg::make_pipeline(&pipeline_desc) };
+}
+
+fn frame(state: &mut State) {
+    state.ry += sapp::frame_duration() as f32 * 20.;
+
+    let mut pass_action = PassAction::default();
+    pass_action.colors[0] = ColorAttachmentAction {
+        action: Action::Clear,
+        value: Color{ r: 0.75, g: 0.85, b: 0.95, a: 1. },
+    };
+
+    // Checked before the pass begins, so the pass is never left open.
+    let Some(environment) = &state.environment else { return };
+
+    let w = sapp::width();
+    let h = sapp::height();
+
+    begin_default_pass(&pass_action, w, h);
+
+    unsafe {
+        sg::apply_pipeline(state.pipe);
+        sg::apply_bindings(&state.bind);
+    }
+
+    let eye_pos = vec3!(0., 0., 22.);
+    let proj = Mat4::perspective(Degrees(60.), w as f32/h as f32, (0.01, 100.));
+    let view = Mat4::look_at(eye_pos, vec3!(), vec3!(y));
+    let view_proj = proj * view;
+
+    // Swinging the light around shows how the highlights change with roughness.
+    let rym = Mat4::rotation(Degrees(state.ry), vec3!(y));
+    let light_dir = rym * vec4!(-1., -1., -1., 0.);
+
+    let lighting = pbr::Lighting {
+        eye_pos,
+        light_dir: light_dir.xyz().normalize(),
+        light_colour: vec3!(3., 3., 3.),
+        environment,
+    };
+
+    let last = (GRID_SIZE - 1) as f32;
+    for row in 0..GRID_SIZE {
+        for column in 0..GRID_SIZE {
+            let (row, column) = (row as f32, column as f32);
+
+            let model = Mat4::translate(vec3!(
+                (column - last / 2.) * SPACING,
+                (row - last / 2.) * SPACING,
+                0.,
+            ));
+            let mvp = view_proj * model;
+
+            let material = pbr::Material {
+                albedo: vec3!(0.9, 0.1, 0.1),
+                metallic: row / last,
+                roughness: column / last,
+                ..<_>::default()
+            };
+
+            pbr::apply_uniforms(pbr::VSParams { model, mvp }, &lighting, &material);
+
+            unsafe { sg::draw(0, state.index_count, 1); }
+        }
+    }
+
+    end_pass();
+
+    commit();
+}
+
+fn cleanup(_state: &mut State) {
+    sg::shutdown()
+}
+
+fn main() {
+    sapp::run_with_userdata!(
+        cbs: {
+            type: State,
+            init: init,
+            frame: frame,
+            cleanup: cleanup,
+        },
+        sapp::Desc{
+            width: 800,
+            height: 800,
+            sample_count: 4,
+            window_title: concat!(file!(), "\0"),
+            icon: IconDesc {
+                sokol_default: true,
+                ..<_>::default()
+            },
+            ..<_>::default()
+        }
+    );
+}
@@ -6,138 +6,90 @@ use sokol_bindings::{
         Pipeline,
         PipelineDesc,
     },
-    Int,
 };
 use math::{
     Scale,
     angle::{Radians, TAU},
+    geom::Index,
     mat4::Mat4,
     vec3::vec3,
-    vec4::vec4,
 };
-use crate::shaders::{self, basic};
+use crate::{
+    mesh::{MeshBuilder, Submesh},
+    shaders::basic,
+};
 
 #[derive(Default)]
 pub struct State {
     pub bind: Bindings,
     pub pipe: Pipeline,
+    pub submesh: Submesh,
 }
 
-const Z_CYLINDER_INDEX_START: shaders::Index = 0;
-const Z_CYLINDER_INDEX_ONE_PAST_END: shaders::Index = Z_CYLINDER_INDEX_START + math::geom::CYLINDER_INDEX_COUNT as shaders::Index;
-const Y_CYLINDER_INDEX_START: shaders::Index = Z_CYLINDER_INDEX_ONE_PAST_END;
-const Y_CYLINDER_INDEX_ONE_PAST_END: shaders::Index = Y_CYLINDER_INDEX_START + math::geom::CYLINDER_INDEX_COUNT as shaders::Index;
-const X_CYLINDER_INDEX_START: shaders::Index = Y_CYLINDER_INDEX_ONE_PAST_END;
-const X_CYLINDER_INDEX_ONE_PAST_END: shaders::Index = X_CYLINDER_INDEX_START + math::geom::CYLINDER_INDEX_COUNT as shaders::Index;
-const INDEX_LEN: usize = X_CYLINDER_INDEX_ONE_PAST_END as usize;
-
-const Z_CYLINDER_VERTEX_START: shaders::Index = 0;
-const Z_CYLINDER_VERTEX_ONE_PAST_END: shaders::Index = Z_CYLINDER_VERTEX_START + math::geom::CYLINDER_POINT_COUNT as shaders::Index;
-const Y_CYLINDER_VERTEX_START: shaders::Index = Z_CYLINDER_VERTEX_ONE_PAST_END;
-const Y_CYLINDER_VERTEX_ONE_PAST_END: shaders::Index = Y_CYLINDER_VERTEX_START + math::geom::CYLINDER_POINT_COUNT as shaders::Index;
-const X_CYLINDER_VERTEX_START: shaders::Index = Y_CYLINDER_VERTEX_ONE_PAST_END;
-const X_CYLINDER_VERTEX_ONE_PAST_END: shaders::Index = X_CYLINDER_VERTEX_START + math::geom::CYLINDER_POINT_COUNT as shaders::Index;
-const VERTEX_LEN: usize = X_CYLINDER_VERTEX_ONE_PAST_END as usize;
-struct IndexedMesh {
-    pub vertices: [basic::Vertex; VERTEX_LEN],
-    pub indices: [shaders::Index; INDEX_LEN],
-}
-
-fn gen_mesh() -> IndexedMesh {
-    let mut vertices = [basic::VERTEX_DEFAULT; VERTEX_LEN];
-    let mut indices = [0; INDEX_LEN];
+fn gen_mesh() -> MeshBuilder<basic::Vertex> {
+    const SEGMENTS: Index = 16;
 
     let long_length = 1./64.;
     let short_length = long_length / 4.;
+    let offset = long_length + short_length;
+
+    let cylinder_mesh = math::geom::gen_cylinder_mesh(
+        Scale {
+            x: short_length,
+            y: short_length,
+            z: long_length,
+        },
+        SEGMENTS,
+        true,
+    );
 
-    let cylinder_mesh = math::geom::gen_cylinder_mesh(Scale {
-        x: short_length,
-        y: short_length,
-        z: long_length,
-    });
-
-    for i in Z_CYLINDER_VERTEX_START..Z_CYLINDER_VERTEX_ONE_PAST_END {
-        let read_i = (i - Z_CYLINDER_VERTEX_START) as usize;
-        let point = cylinder_mesh.points[read_i];
-
-        vertices[i as usize] = basic::vertex!{
-            point.x,
-            point.y,
-            point.z + long_length + short_length,
-            0xFFC00000,
-        };
-    }
-
-    for i in Z_CYLINDER_INDEX_START..Z_CYLINDER_INDEX_ONE_PAST_END {
-        let i = i as usize;
-        indices[i] = cylinder_mesh.indices[i - Z_CYLINDER_INDEX_START as usize]
-            + Z_CYLINDER_VERTEX_START as shaders::Index;
-    }
-
-    let rotate_x = Mat4::rotation(Radians(TAU / 4.), vec3!(x));
-
-    for i in Y_CYLINDER_VERTEX_START..Y_CYLINDER_VERTEX_ONE_PAST_END {
-        let read_i = (i - Y_CYLINDER_VERTEX_START) as usize;
-        let point = cylinder_mesh.points[read_i];
-
-        let point = rotate_x * vec4!(point.x, point.y, point.z, 1.);
-
-        vertices[i as usize] = basic::vertex!{
-            point.x,
-            point.y + long_length + short_length,
-            point.z,
-            0xFF00C000,
-        };
-    }
-
-    for i in Y_CYLINDER_INDEX_START..Y_CYLINDER_INDEX_ONE_PAST_END {
-        let i = i as usize;
-        indices[i] = cylinder_mesh.indices[i - Y_CYLINDER_INDEX_START as usize]
-            + Y_CYLINDER_VERTEX_START as shaders::Index;
-    }
-
-    let rotate_y = Mat4::rotation(Radians(TAU / 4.), vec3!(y));
-
-    for i in X_CYLINDER_VERTEX_START..X_CYLINDER_VERTEX_ONE_PAST_END {
-        let read_i = (i - X_CYLINDER_VERTEX_START) as usize;
-        let point = cylinder_mesh.points[read_i];
-
-        let point = rotate_y * vec4!(point.x, point.y, point.z, 1.);
-
-        vertices[i as usize] = basic::vertex!{
-            point.x + long_length + short_length,
-            point.y,
-            point.z,
-            0xFF0000C0,
-        };
-    }
+    // Arrowheads, so the positive direction is clear.
+    let cone_length = short_length * 2.;
+    let cone_mesh = math::geom::gen_cone_mesh(
+        Scale {
+            x: short_length * 2.,
+            y: short_length * 2.,
+            z: cone_length,
+        },
+        SEGMENTS,
+        true,
+    );
 
-    for i in X_CYLINDER_INDEX_START..X_CYLINDER_INDEX_ONE_PAST_END {
-        let i = i as usize;
-        indices[i] = cylinder_mesh.indices[i - X_CYLINDER_INDEX_START as usize]
-            + X_CYLINDER_VERTEX_START as shaders::Index;
+    let mut builder = MeshBuilder::new();
+
+    // Each of these rotates `+z` to the given axis.
+    for (axis, rotation, color) in [
+        (vec3!(z), Mat4::identity(), 0xFFC00000),
+        (vec3!(y), Mat4::rotation(Radians(-TAU / 4.), vec3!(x)), 0xFF00C000),
+        (vec3!(x), Mat4::rotation(Radians(TAU / 4.), vec3!(y)), 0xFF0000C0),
+    ] {
+        builder.append(
+            &cylinder_mesh,
+            Some(Mat4::translate(axis * offset) * rotation),
+            color,
+        );
+        builder.append(
+            &cone_mesh,
+            Some(Mat4::translate(axis * (offset + long_length + cone_length)) * rotation),
+            color,
+        );
     }
 
-    IndexedMesh {
-        vertices,
-        indices,
-    }
+    builder
 }
 
 pub fn init(axes: &mut State) {
     let mesh = gen_mesh();
 
-    let vertices = mesh.vertices;
+    axes.submesh = mesh.whole();
 
     axes.bind.vertex_buffers[0] = sg::make_immutable_vertex_buffer!(
-        vertices,
+        &mesh.vertices,
         "axes-vertices"
     );
 
-    let indices = mesh.indices;
-
     axes.bind.index_buffer = sg::make_immutable_index_buffer!(
-        indices,
+        &mesh.indices,
         "axes-indices"
     );
 
@@ -164,5 +116,34 @@ pub fn draw(axes: &State, mvp: Mat4) {
 
     basic::apply_uniforms(mvp.to_column_major());
 
-    unsafe { sg::draw(0, INDEX_LEN as Int, 1); }
-}
\ No newline at end of file
+    unsafe { sg::draw(axes.submesh.base_element, axes.submesh.element_count, 1); }
+}
+
+#[cfg(feature = "dummy-backend")]
+#[test]
+fn init_makes_valid_resources() {
+    use sokol_bindings::sg::{Context, ResourceState};
+
+    Context::with_dummy(|_| {
+        let mut axes = State::default();
+
+        init(&mut axes);
+
+        // SAFETY: There are no currently known safety issues with these fns.
+        unsafe {
+            assert_eq!(
+                ResourceState::from(sokol_bindings::sg_query_buffer_state(axes.bind.vertex_buffers[0])),
+                ResourceState::Valid
+            );
+            assert_eq!(
+                ResourceState::from(sokol_bindings::sg_query_buffer_state(axes.bind.index_buffer)),
+                ResourceState::Valid
+            );
+            assert_eq!(
+                ResourceState::from(sokol_bindings::sg_query_pipeline_state(axes.pipe)),
+                ResourceState::Valid
+            );
+        }
+        assert_eq!(axes.submesh, gen_mesh().whole());
+    });
+}
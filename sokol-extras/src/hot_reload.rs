@@ -0,0 +1,210 @@
+//! Reloading shaders from their `.glsl` files while the program runs, so the
+//! GLSL can be tweaked without rebuilding. Only the GLSL itself can change this
+//! way. The `@` declarations, and the `///` lines documenting them, are compiled
+//! into the Rust code, so changing them still needs a rebuild.
+//!
+//! Enabled with the `hot-reload` feature. The shader modules generated from
+//! `.glsl` files by `shader_gen::generate_dir` have a `hot_reload_pipeline`
+//! function to make a `HotReloadPipeline` with.
+
+use sokol_bindings::{
+    log,
+    sg::{
+        self, Backend, Context, OwnedPipeline, OwnedShader, PipelineBuilder,
+        PipelineError, ResourceState, ShaderDesc,
+    },
+};
+use shader_gen::GlslVersion;
+use std::{
+    fmt,
+    path::PathBuf,
+    rc::Rc,
+    time::SystemTime,
+};
+
+#[derive(Debug)]
+pub enum ReloadError {
+    Io(std::io::Error),
+    Parse(shader_gen::Error),
+    /// The `@` declarations are different from the ones the program was built
+    /// with.
+    DeclarationsChanged,
+    /// The shader failed to compile or link, as reported by
+    /// `sg_query_shader_state`. `log` has what sokol logged while making it, which
+    /// includes the GLSL compiler's errors.
+    Compile { state: ResourceState, log: String },
+    Pipeline(PipelineError),
+}
+
+impl fmt::Display for ReloadError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        use ReloadError::*;
+        match self {
+            Io(e) => write!(f, "could not read the shader: {e}"),
+            Parse(e) => write!(f, "could not parse the shader: {e}"),
+            DeclarationsChanged => write!(
+                f,
+                "the @ declarations changed, which needs a rebuild"
+            ),
+            Compile { state, log } => write!(
+                f,
+                "the shader is {state:?} after making it. The log was:\n{log}"
+            ),
+            Pipeline(e) => write!(f, "could not build the pipeline: {e}"),
+        }
+    }
+}
+
+impl std::error::Error for ReloadError {}
+
+impl From<std::io::Error> for ReloadError {
+    fn from(e: std::io::Error) -> Self {
+        Self::Io(e)
+    }
+}
+
+impl From<shader_gen::Error> for ReloadError {
+    fn from(e: shader_gen::Error) -> Self {
+        Self::Parse(e)
+    }
+}
+
+impl From<PipelineError> for ReloadError {
+    fn from(e: PipelineError) -> Self {
+        Self::Pipeline(e)
+    }
+}
+
+#[derive(Debug)]
+pub enum Reload {
+    Unchanged,
+    Reloaded,
+    /// The previous pipeline is still in use.
+    Failed(ReloadError),
+}
+
+/// A pipeline that is rebuilt when the `.glsl` file its shader was generated
+/// from changes.
+pub struct HotReloadPipeline {
+    path: PathBuf,
+    modified: Option<SystemTime>,
+    /// What the file had when this was made. Used to notice changes that
+    /// need a rebuild.
+    declarations: shader_gen::Shader,
+    shader_desc: fn(Backend) -> ShaderDesc,
+    builder: Box<dyn Fn(Rc<OwnedShader>) -> PipelineBuilder>,
+    pipeline: OwnedPipeline,
+    context: Context,
+}
+
+impl fmt::Debug for HotReloadPipeline {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("HotReloadPipeline")
+            .field("path", &self.path)
+            .field("modified", &self.modified)
+            .field("pipeline", &self.pipeline)
+            .finish_non_exhaustive()
+    }
+}
+
+impl HotReloadPipeline {
+    /// Builds the pipeline with the shader from `shader_desc`, which is the one
+    /// compiled into the program. `path` is the `.glsl` file that `shader_desc` was
+    /// generated from, and `builder` sets up the rest of the pipeline around a
+    /// shader.
+    pub fn new(
+        context: &Context,
+        path: impl Into<PathBuf>,
+        shader_desc: fn(Backend) -> ShaderDesc,
+        builder: impl Fn(Rc<OwnedShader>) -> PipelineBuilder + 'static,
+    ) -> Result<Self, ReloadError> {
+        let path = path.into();
+        let modified = std::fs::metadata(&path)?.modified().ok();
+        let declarations = parse(&path)?;
+
+        let desc = shader_desc(sg::query_backend());
+        let shader = make_shader(context, &desc)?;
+        let pipeline = builder(Rc::new(shader)).build(context)?;
+
+        Ok(Self {
+            path,
+            modified,
+            declarations,
+            shader_desc,
+            builder: Box::new(builder),
+            pipeline,
+            context: context.clone(),
+        })
+    }
+
+    pub fn pipeline(&self) -> &OwnedPipeline {
+        &self.pipeline
+    }
+
+    /// Checks whether the file has been modified since the last call, and if so
+    /// rebuilds the pipeline from it. Meant to be called once a frame.
+    ///
+    /// Failing to check the modification time counts as unchanged, since editors
+    /// can briefly remove the file while saving it.
+    pub fn poll(&mut self) -> Reload {
+        let modified = std::fs::metadata(&self.path)
+            .and_then(|metadata| metadata.modified())
+            .ok();
+        if modified.is_none() || modified == self.modified {
+            return Reload::Unchanged
+        }
+        // Set before reloading, so a broken file is only reported once.
+        self.modified = modified;
+
+        match self.reload() {
+            Ok(()) => Reload::Reloaded,
+            Err(e) => Reload::Failed(e),
+        }
+    }
+
+    fn reload(&mut self) -> Result<(), ReloadError> {
+        let shader = parse(&self.path)?;
+
+        let declarations_match = shader.attrs == self.declarations.attrs
+            && shader.vs.uniforms == self.declarations.vs.uniforms
+            && shader.vs.images == self.declarations.vs.images
+            && shader.fs.uniforms == self.declarations.fs.uniforms
+            && shader.fs.images == self.declarations.fs.images;
+        if !declarations_match {
+            return Err(ReloadError::DeclarationsChanged);
+        }
+
+        let backend = sg::query_backend();
+        let version = match backend {
+            Backend::Gles3 => GlslVersion::Glsl300Es,
+            _ => GlslVersion::Glsl330,
+        };
+        let (vs_source, fs_source) = shader_gen::glsl_sources(&shader, version);
+
+        let mut desc = (self.shader_desc)(backend);
+        desc.vs.source = vs_source.as_ptr() as _;
+        desc.fs.source = fs_source.as_ptr() as _;
+
+        let shader = make_shader(&self.context, &desc)?;
+        self.pipeline = (self.builder)(Rc::new(shader)).build(&self.context)?;
+
+        Ok(())
+    }
+}
+
+fn parse(path: &std::path::Path) -> Result<shader_gen::Shader, ReloadError> {
+    let source = std::fs::read_to_string(path)?;
+    let name = path.file_stem()
+        .and_then(|stem| stem.to_str())
+        .unwrap_or_default();
+
+    Ok(shader_gen::parse(name, &source)?)
+}
+
+fn make_shader(context: &Context, desc: &ShaderDesc) -> Result<OwnedShader, ReloadError> {
+    // SAFETY: The pointers in the desc are either static, or point to sources
+    // that the caller keeps alive until after this returns.
+    let (shader, log) = log::capture(|| unsafe { OwnedShader::from_desc(context, desc) });
+
+    shader.map_err(|state| ReloadError::Compile { state, log })
+}
@@ -1,3 +1,35 @@
 pub mod checkerboard;
 pub mod checkerboard_5x;
-pub mod white;
\ No newline at end of file
+pub mod flat_normal;
+pub mod sky_gradient;
+pub mod white;
+
+use math::vec3::Vec3;
+
+/// The direction from the center of the cube through the point at `s`, `t` on
+/// the face, in sokol's face order: `+x`, `-x`, `+y`, `-y`, `+z`, `-z`. `s` and `t`
+/// go from -1 to 1, left to right and top to bottom, as the face is stored.
+pub(crate) fn cube_face_direction(face: usize, s: f32, t: f32) -> Vec3 {
+    let (x, y, z) = match face {
+        0 => (1., -t, -s),
+        1 => (-1., -t, s),
+        2 => (s, 1., t),
+        3 => (s, -1., -t),
+        4 => (s, -t, 1.),
+        _ => (-s, -t, -1.),
+    };
+
+    Vec3 { x, y, z }
+}
+
+#[test]
+fn the_faces_point_along_their_axes() {
+    use math::vec3::vec3;
+
+    let centers = [0, 1, 2, 3, 4, 5].map(|face| cube_face_direction(face, 0., 0.));
+
+    assert_eq!(
+        centers,
+        [vec3!(x), vec3!(-x), vec3!(y), vec3!(-y), vec3!(z), vec3!(-z)]
+    );
+}
@@ -0,0 +1,95 @@
+//! A cube image that fades from a ground colour at the bottom, through a horizon
+//! colour, to a sky colour at the top. Useful as the environment for image based
+//! lighting when there is no other cube image to use.
+
+use sokol_bindings::{sg, cstr, Int};
+use math::vec3::Vec3;
+
+pub const SIZE: Int = 32;
+/// Down to 1 by 1.
+pub const MIPMAP_COUNT: Int = SIZE.ilog2() as Int + 1;
+
+// R, G, B
+const SKY: [f32; 3] = [0x40 as f32, 0x80 as f32, 0xE0 as f32];
+const HORIZON: [f32; 3] = [0xC0 as f32, 0xD8 as f32, 0xF0 as f32];
+const GROUND: [f32; 3] = [0x50 as f32, 0x48 as f32, 0x40 as f32];
+
+fn colour(direction: Vec3) -> [u8; 4] {
+    let y = direction.normalize().y;
+    let (to, amount) = if y >= 0. {
+        (SKY, y.sqrt())
+    } else {
+        (GROUND, (-y).sqrt())
+    };
+
+    let channel = |i: usize| (HORIZON[i] + (to[i] - HORIZON[i]) * amount) as u8;
+
+    [channel(0), channel(1), channel(2), 0xFF]
+}
+
+/// Each mipmap is made from the gradient directly rather than by blurring the
+/// larger ones, which is close enough since the gradient is already smooth.
+pub fn make() -> sg::Image {
+    let faces: Vec<Vec<Vec<u8>>> = (0..6).map(|face| {
+        (0..MIPMAP_COUNT).map(|level| {
+            let size = (SIZE >> level) as usize;
+            let mut pixels = Vec::with_capacity(size * size * 4);
+
+            for y in 0..size {
+                for x in 0..size {
+                    // The centers of the pixels.
+                    let s = (x as f32 + 0.5) / size as f32 * 2. - 1.;
+                    let t = (y as f32 + 0.5) / size as f32 * 2. - 1.;
+
+                    pixels.extend(colour(super::cube_face_direction(face, s, t)));
+                }
+            }
+
+            pixels
+        }).collect()
+    }).collect();
+
+    let mut image_desc = sg::ImageDesc {
+        type_: sg::ImageType::Cube as _,
+        width: SIZE,
+        height: SIZE,
+        num_mipmaps: MIPMAP_COUNT,
+        min_filter: sg::Filter::LinearMipmapLinear as _,
+        mag_filter: sg::Filter::Linear as _,
+        wrap_u: sg::Wrap::ClampToEdge as _,
+        wrap_v: sg::Wrap::ClampToEdge as _,
+        label: cstr!("sky-gradient-texture"),
+        ..<_>::default()
+    };
+    for (face, levels) in faces.iter().enumerate() {
+        for (level, pixels) in levels.iter().enumerate() {
+            image_desc.data.subimage[face][level] = sg::range!(&pixels[..]);
+        }
+    }
+
+    // SAFETY: `faces` lives until after this call.
+    unsafe { sg::make_image(&image_desc) }
+}
+
+#[test]
+fn the_top_is_sky_and_the_bottom_is_ground() {
+    use math::vec3::vec3;
+
+    assert_eq!(colour(vec3!(y)), [0x40, 0x80, 0xE0, 0xFF]);
+    assert_eq!(colour(vec3!(x)), [0xC0, 0xD8, 0xF0, 0xFF]);
+    assert_eq!(colour(vec3!(-y)), [0x50, 0x48, 0x40, 0xFF]);
+}
+
+#[cfg(feature = "dummy-backend")]
+#[test]
+fn make_makes_a_valid_image() {
+    use sokol_bindings::sg::{Context, ResourceState};
+
+    Context::with_dummy(|_| {
+        let image = make();
+
+        // SAFETY: There are no currently known safety issues with this fn.
+        let state = unsafe { sokol_bindings::sg_query_image_state(image) };
+        assert_eq!(ResourceState::from(state), ResourceState::Valid);
+    });
+}
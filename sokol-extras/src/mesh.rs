@@ -0,0 +1,281 @@
+//! Combining multiple meshes into a single set of vertex and index buffers, so
+//! that the parts can be drawn with a single set of bindings.
+
+use core::ops::Range;
+
+use sokol_bindings::Int;
+use math::{
+    geom::MeshRef,
+    mat4::Mat4,
+    vec3::Vec3,
+    vec4::vec4,
+};
+use crate::shaders::{self, basic, lit, pbr, phong, textured, textured_lit, ABGR};
+
+/// A vertex type that a `MeshBuilder` can produce. Vertex types that do not have
+/// a place for some of the parts, ignore those parts.
+pub trait MeshVertex {
+    fn mesh_vertex(position: Vec3, normal: Vec3, color: ABGR) -> Self;
+}
+
+impl MeshVertex for basic::Vertex {
+    fn mesh_vertex(position: Vec3, _normal: Vec3, color: ABGR) -> Self {
+        basic::vertex!(position.x, position.y, position.z, color)
+    }
+}
+
+impl MeshVertex for textured::Vertex {
+    fn mesh_vertex(position: Vec3, _normal: Vec3, color: ABGR) -> Self {
+        textured::vertex!(position.x, position.y, position.z, color, 0, 0)
+    }
+}
+
+impl MeshVertex for lit::Vertex {
+    fn mesh_vertex(position: Vec3, normal: Vec3, _color: ABGR) -> Self {
+        lit::Vertex {
+            position,
+            normal,
+        }
+    }
+}
+
+impl MeshVertex for pbr::Vertex {
+    fn mesh_vertex(position: Vec3, normal: Vec3, _color: ABGR) -> Self {
+        pbr::Vertex {
+            position,
+            normal,
+            ..pbr::VERTEX_DEFAULT
+        }
+    }
+}
+
+impl MeshVertex for phong::Vertex {
+    fn mesh_vertex(position: Vec3, normal: Vec3, _color: ABGR) -> Self {
+        phong::Vertex {
+            position,
+            normal,
+        }
+    }
+}
+
+impl MeshVertex for textured_lit::Vertex {
+    fn mesh_vertex(position: Vec3, normal: Vec3, color: ABGR) -> Self {
+        textured_lit::Vertex {
+            position,
+            normal,
+            color,
+            ..textured_lit::VERTEX_DEFAULT
+        }
+    }
+}
+
+/// A range of the elements in a `MeshBuilder`'s indices. The fields are in the
+/// form `sg::draw` expects.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct Submesh {
+    pub base_element: Int,
+    pub element_count: Int,
+}
+
+impl Submesh {
+    /// A part of this submesh, given as a range of indices relative to the start
+    /// of it. Useful for meshes that come with ranges for their parts, like
+    /// `math::geom::RoundedCube`.
+    pub fn part(self, range: Range<usize>) -> Self {
+        debug_assert!(range.end <= self.element_count as usize);
+
+        Self {
+            base_element: self.base_element + range.start as Int,
+            element_count: range.len() as Int,
+        }
+    }
+
+    /// The submesh that includes both this one and `other`, as well as anything
+    /// in between them.
+    pub fn merge(self, other: Self) -> Self {
+        let start = self.base_element.min(other.base_element);
+        let one_past_end = (self.base_element + self.element_count)
+            .max(other.base_element + other.element_count);
+
+        Self {
+            base_element: start,
+            element_count: one_past_end - start,
+        }
+    }
+}
+
+#[derive(Debug)]
+pub struct MeshBuilder<V> {
+    pub vertices: Vec<V>,
+    pub indices: Vec<shaders::Index>,
+}
+
+impl <V> Default for MeshBuilder<V> {
+    fn default() -> Self {
+        Self {
+            vertices: Vec::new(),
+            indices: Vec::new(),
+        }
+    }
+}
+
+impl <V: MeshVertex> MeshBuilder<V> {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Appends a copy of the mesh, with each point transformed by `transform` if
+    /// it is passed, and with every vertex set to `color`. Returns the range of
+    /// the indices that were added.
+    ///
+    /// # Panics
+    /// If the index of the last vertex would not fit in a `shaders::Index`. So
+    /// the builder can hold at most `shaders::Index::MAX + 1` vertices.
+    pub fn append<'mesh>(
+        &mut self,
+        mesh: impl Into<MeshRef<'mesh>>,
+        transform: Option<Mat4>,
+        color: ABGR,
+    ) -> Submesh {
+        let MeshRef { points, normals, indices } = mesh.into();
+
+        debug_assert_eq!(points.len(), normals.len());
+
+        // An empty mesh has no indices to offset, even if the builder is full.
+        let vertex_offset = match points.len().checked_sub(1) {
+            Some(last) => shaders::Index::try_from(self.vertices.len() + last)
+                .map(|last_index| last_index - last as shaders::Index)
+                .expect("the last vertex index should fit in a shaders::Index"),
+            None => 0,
+        };
+
+        // Normals need to be transformed by the inverse transpose, so that they
+        // stay perpendicular to the surface when the transform has non-uniform
+        // scaling.
+        let normal_transform = transform.map(|transform| {
+            transform.inverse()
+                .map(Mat4::transpose)
+                .unwrap_or(transform)
+        });
+
+        self.vertices.reserve(points.len());
+        for (&point, &normal) in points.iter().zip(normals.iter()) {
+            let mut position = Vec3::from(point);
+            let mut normal = Vec3::from(normal);
+
+            if let (Some(transform), Some(normal_transform)) = (transform, normal_transform) {
+                position = (transform * vec4!(position.x, position.y, position.z, 1.)).xyz();
+                normal = (normal_transform * vec4!(normal.x, normal.y, normal.z, 0.))
+                    .xyz()
+                    .normalize();
+            }
+
+            self.vertices.push(V::mesh_vertex(position, normal, color));
+        }
+
+        let base_element = self.indices.len() as Int;
+
+        self.indices.extend(indices.iter().map(|&index| index + vertex_offset));
+
+        Submesh {
+            base_element,
+            element_count: indices.len() as Int,
+        }
+    }
+
+    /// The range covering all the indices appended so far.
+    pub fn whole(&self) -> Submesh {
+        Submesh {
+            base_element: 0,
+            element_count: self.indices.len() as Int,
+        }
+    }
+}
+
+#[test]
+fn append_offsets_the_indices_of_later_meshes() {
+    let cube = math::geom::gen_cube_mesh(1.);
+
+    let mut builder = MeshBuilder::<basic::Vertex>::new();
+
+    let first = builder.append(&cube, None, 0xFFFFFFFF);
+    let second = builder.append(&cube, None, 0xFF000000);
+
+    assert_eq!(first, Submesh { base_element: 0, element_count: 36 });
+    assert_eq!(second, Submesh { base_element: 36, element_count: 36 });
+    assert_eq!(first.merge(second), builder.whole());
+
+    assert_eq!(builder.vertices.len(), 48);
+    assert_eq!(builder.vertices[24].color, 0xFF000000);
+    assert_eq!(
+        builder.indices[36..],
+        cube.indices.map(|index| index + 24)
+    );
+}
+
+#[test]
+fn parts_are_relative_to_the_start_of_their_submesh() {
+    let submesh = Submesh { base_element: 36, element_count: 36 };
+
+    assert_eq!(submesh.part(6..12), Submesh { base_element: 42, element_count: 6 });
+    assert_eq!(submesh.part(0..36), submesh);
+    assert_eq!(submesh.part(36..36), Submesh { base_element: 72, element_count: 0 });
+}
+
+#[test]
+fn append_transforms_positions_and_normals() {
+    use math::vec3::vec3;
+
+    let cube = math::geom::gen_cube_mesh(1.);
+
+    let mut builder = MeshBuilder::<lit::Vertex>::new();
+
+    builder.append(
+        &cube,
+        Some(Mat4::translate(vec3!(10., 0., 0.)) * Mat4::scale(vec3!(2., 1., 1.))),
+        0xFFFFFFFF,
+    );
+
+    for (vertex, &point) in builder.vertices.iter().zip(cube.points.iter()) {
+        assert_eq!(vertex.position, vec3!(point.x * 2. + 10., point.y, point.z));
+    }
+
+    // The normals of the x faces should still be unit length after scaling.
+    for vertex in builder.vertices.iter() {
+        assert!((vertex.normal.length() - 1.).abs() < 1. / 65536.);
+    }
+}
+
+#[cfg(test)]
+/// Appends a mesh with `shaders::Index::MAX + 1` vertices to `builder` in two
+/// parts, followed by an empty mesh.
+fn append_a_full_mesh(builder: &mut MeshBuilder<basic::Vertex>) {
+    use math::{geom::Point, vec3::Normal};
+
+    let points = vec![Point::default(); shaders::Index::MAX as usize];
+    let normals = vec![Normal::default(); points.len()];
+
+    builder.append(MeshRef { points: &points, normals: &normals, indices: &[] }, None, 0);
+    builder.append(MeshRef { points: &points[..1], normals: &normals[..1], indices: &[0] }, None, 0);
+    builder.append(MeshRef { points: &[], normals: &[], indices: &[] }, None, 0);
+}
+
+#[test]
+fn append_allows_as_many_vertices_as_there_are_index_values() {
+    let mut builder = MeshBuilder::new();
+
+    append_a_full_mesh(&mut builder);
+
+    assert_eq!(builder.vertices.len(), shaders::Index::MAX as usize + 1);
+    assert_eq!(builder.indices, [shaders::Index::MAX]);
+}
+
+#[test]
+#[should_panic]
+fn append_panics_once_the_last_index_would_not_fit() {
+    let mut builder = MeshBuilder::new();
+
+    append_a_full_mesh(&mut builder);
+
+    builder.append(&math::geom::gen_cube_mesh(1.), None, 0);
+}
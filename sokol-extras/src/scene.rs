@@ -0,0 +1,722 @@
+//! A small version of Unity's `GameObject`s and scripts. Each game object has a
+//! `Transform`, an optional `MeshRenderer`, and any number of `Script`s. The
+//! scripts' hooks are called by `Scene::frame`, which is meant to be called from
+//! the `sapp` frame callback.
+//!
+//! The hooks are called in a deterministic order:
+//! * `awake` is called on each of a game object's scripts, in order, as soon as
+//!   the game object is spawned.
+//! * At the start of each frame, `start` is called on the scripts of each game
+//!   object that has not been started yet, in the order the game objects were
+//!   spawned.
+//! * Then `update` is called on the scripts of each game object that existed at
+//!   the start of the frame, in the order the game objects were spawned.
+//! * Game objects destroyed during a frame are removed at the end of it, in the
+//!   order they were destroyed, with `on_destroy` called on each of their
+//!   scripts. Destroying a game object also destroys the game objects whose
+//!   `Transform`s are its descendants, with parents coming before children.
+
+use std::collections::HashMap;
+
+use sokol_bindings::{
+    sg::{self, Bindings, Pipeline},
+    Int,
+};
+use math::{
+    mat4::Mat4,
+    vec3::Vec3,
+};
+
+pub mod transform;
+
+use transform::{Hierarchy, Transform, TransformId};
+
+/// Refers to a game object inside a `Scene`. Only meaningful for the `Scene` that
+/// produced it. The slot of a destroyed game object is reused by later ones, but
+/// each reuse gets a new generation, so an id of a destroyed game object never
+/// refers to a different one.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct GameObjectId {
+    index: usize,
+    generation: u32,
+}
+
+/// The part of some buffers to draw. That is, the parameters to pass to
+/// `sg::apply_bindings` and `sg::draw`.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct Mesh {
+    pub bindings: Bindings,
+    pub base_element: Int,
+    pub element_count: Int,
+}
+
+/// How to draw a `Mesh`. The uniforms are left up to the caller of `Scene::draw`,
+/// since they depend on the shader used in the pipeline.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct Material {
+    pub pipeline: Pipeline,
+    pub diffuse_colour: Vec3,
+}
+
+#[derive(Clone, Copy, Debug, Default)]
+pub struct MeshRenderer {
+    pub mesh: Mesh,
+    pub material: Material,
+}
+
+/// The hooks that a script can respond to. See the module docs for the order in
+/// which they are called. All of them do nothing by default.
+pub trait Script {
+    fn awake(&mut self, _context: &mut Context) {}
+
+    fn start(&mut self, _context: &mut Context) {}
+
+    /// `dt` is the time since the last frame, in seconds.
+    fn update(&mut self, _context: &mut Context, _dt: f32) {}
+
+    fn on_destroy(&mut self, _context: &mut Context) {}
+}
+
+/// A description of a game object to spawn. Like a Unity prefab.
+#[derive(Default)]
+pub struct GameObject {
+    pub parent: Option<GameObjectId>,
+    /// Relative to the parent's `Transform`, if there is a parent.
+    pub transform: Transform,
+    pub renderer: Option<MeshRenderer>,
+    pub scripts: Vec<Box<dyn Script>>,
+}
+
+struct Entity {
+    transform: TransformId,
+    renderer: Option<MeshRenderer>,
+    scripts: Vec<Box<dyn Script>>,
+    started: bool,
+}
+
+struct Slot {
+    // Incremented each time the slot's entity is destroyed.
+    generation: u32,
+    entity: Option<Entity>,
+}
+
+#[derive(Default)]
+pub struct Scene {
+    // Private, so that every change to the hierarchy goes through the `Scene`,
+    // and `game_objects_by_transform` stays in sync with it.
+    transforms: Hierarchy,
+    // Destroyed entities are set to `None`, so that the indexes of the others
+    // stay the same. Their indexes go on `free` to be reused by `spawn`.
+    slots: Vec<Slot>,
+    free: Vec<usize>,
+    // Since slots are reused, their order says nothing about when the game
+    // objects were spawned, so the order is kept here. Destroyed game objects
+    // are only removed from it at the end of `flush_pending_destroys`.
+    spawn_order: Vec<GameObjectId>,
+    game_objects_by_transform: HashMap<TransformId, GameObjectId>,
+    pending_destroys: Vec<GameObjectId>,
+}
+
+/// Passed to each `Script` hook. Provides access to the game object the script
+/// is attached to, as well as the rest of the scene.
+pub struct Context<'scene> {
+    scene: &'scene mut Scene,
+    game_object: GameObjectId,
+}
+
+impl Context<'_> {
+    /// The game object the script is attached to.
+    pub fn game_object(&self) -> GameObjectId {
+        self.game_object
+    }
+
+    /// The `TransformId` of the game object the script is attached to.
+    pub fn transform_id(&self) -> TransformId {
+        self.scene.transform_id(self.game_object)
+            .expect("the current game object should not have been removed yet")
+    }
+
+    /// The local `Transform` of the game object the script is attached to.
+    pub fn transform(&mut self) -> &mut Transform {
+        let id = self.transform_id();
+
+        self.scene.transforms.local_mut(id)
+    }
+
+    /// Read only access to the `Transform`s of all the game objects. Use
+    /// `local_mut` and `set_parent` to change them.
+    pub fn transforms(&self) -> &Hierarchy {
+        self.scene.transforms()
+    }
+
+    pub fn local_mut(&mut self, id: TransformId) -> &mut Transform {
+        self.scene.local_mut(id)
+    }
+
+    /// See `Scene::set_parent`.
+    pub fn set_parent(&mut self, id: GameObjectId, parent: Option<GameObjectId>) {
+        self.scene.set_parent(id, parent);
+    }
+
+    pub fn renderer_mut(&mut self, id: GameObjectId) -> Option<&mut MeshRenderer> {
+        self.scene.renderer_mut(id)
+    }
+
+    pub fn transform_id_of(&self, id: GameObjectId) -> Option<TransformId> {
+        self.scene.transform_id(id)
+    }
+
+    /// Spawns the game object immediately, calling `awake` on its scripts. It will
+    /// not be started or updated until the next frame.
+    pub fn spawn(&mut self, game_object: GameObject) -> GameObjectId {
+        self.scene.spawn_without_flushing(game_object)
+    }
+
+    /// Destroys the game object at the end of the current frame. Or, if called
+    /// outside of a frame, before `Scene::spawn` returns.
+    pub fn destroy(&mut self, id: GameObjectId) {
+        self.scene.pending_destroys.push(id);
+    }
+}
+
+impl Scene {
+    /// Spawns the game object immediately, calling `awake` on its scripts. It will
+    /// be started and updated during the next `frame`.
+    pub fn spawn(&mut self, game_object: GameObject) -> GameObjectId {
+        let id = self.spawn_without_flushing(game_object);
+
+        self.flush_pending_destroys();
+
+        id
+    }
+
+    fn spawn_without_flushing(&mut self, GameObject {
+        parent,
+        transform,
+        renderer,
+        scripts,
+    }: GameObject) -> GameObjectId {
+        let parent_transform = parent.map(|parent| {
+            self.transform_id(parent)
+                .expect("parent should refer to a game object that has not been destroyed")
+        });
+
+        let transform = match parent_transform {
+            Some(parent) => self.transforms.add_child(parent, transform),
+            None => self.transforms.add(transform),
+        };
+
+        let index = match self.free.pop() {
+            Some(index) => index,
+            None => {
+                self.slots.push(Slot { generation: 0, entity: None });
+                self.slots.len() - 1
+            }
+        };
+
+        let slot = &mut self.slots[index];
+        slot.entity = Some(Entity {
+            transform,
+            renderer,
+            scripts,
+            started: false,
+        });
+
+        let id = GameObjectId { index, generation: slot.generation };
+        self.spawn_order.push(id);
+        self.game_objects_by_transform.insert(transform, id);
+
+        self.call_hook(id, |script, context| script.awake(context));
+
+        id
+    }
+
+    /// Destroys the game object and the game objects whose `Transform`s are its
+    /// descendants, immediately. Does nothing if the game object was already
+    /// destroyed.
+    pub fn destroy(&mut self, id: GameObjectId) {
+        self.pending_destroys.push(id);
+
+        self.flush_pending_destroys();
+    }
+
+    /// Destroys all the game objects, calling `on_destroy` on their scripts. Meant
+    /// to be called from the `sapp` cleanup callback.
+    pub fn destroy_all(&mut self) {
+        self.pending_destroys.extend_from_slice(&self.spawn_order);
+
+        self.flush_pending_destroys();
+    }
+
+    pub fn contains(&self, id: GameObjectId) -> bool {
+        self.entity(id).is_some()
+    }
+
+    pub fn transform_id(&self, id: GameObjectId) -> Option<TransformId> {
+        self.entity(id).map(|entity| entity.transform)
+    }
+
+    /// Read only access to the `Transform`s of all the game objects. Use
+    /// `local_mut` and `set_parent` to change them.
+    pub fn transforms(&self) -> &Hierarchy {
+        &self.transforms
+    }
+
+    /// Changing the `Transform` through the returned reference will be reflected
+    /// in the world space matrices of it and its descendants.
+    pub fn local_mut(&mut self, id: TransformId) -> &mut Transform {
+        self.transforms.local_mut(id)
+    }
+
+    /// Makes `parent`'s `Transform` the parent of `id`'s one, or makes it a root
+    /// if `parent` is `None`. Local values are kept as they are.
+    ///
+    /// # Panics
+    /// Panics if either game object has been destroyed, or if this would make a
+    /// `Transform` its own ancestor.
+    pub fn set_parent(&mut self, id: GameObjectId, parent: Option<GameObjectId>) {
+        let transform = self.transform_id(id)
+            .expect("id should refer to a game object that has not been destroyed");
+        let parent_transform = parent.map(|parent| {
+            self.transform_id(parent)
+                .expect("parent should refer to a game object that has not been destroyed")
+        });
+
+        self.transforms.set_parent(transform, parent_transform);
+    }
+
+    pub fn renderer_mut(&mut self, id: GameObjectId) -> Option<&mut MeshRenderer> {
+        self.entity_mut(id).and_then(|entity| entity.renderer.as_mut())
+    }
+
+    /// Runs the `start` and `update` hooks, then removes any game objects that
+    /// were destroyed. `dt` is the time since the last frame, in seconds.
+    pub fn frame(&mut self, dt: f32) {
+        // Game objects spawned during this frame are not started or updated until
+        // the next one.
+        // Nothing is removed from `spawn_order` until the end of the frame.
+        let entity_count = self.spawn_order.len();
+
+        for i in 0..entity_count {
+            let id = self.spawn_order[i];
+
+            let needs_start = match self.entity_mut(id) {
+                Some(entity) if !entity.started => {
+                    entity.started = true;
+                    true
+                },
+                _ => false,
+            };
+
+            if needs_start {
+                self.call_hook(id, |script, context| script.start(context));
+            }
+        }
+
+        for i in 0..entity_count {
+            self.call_hook(self.spawn_order[i], |script, context| script.update(context, dt));
+        }
+
+        self.flush_pending_destroys();
+    }
+
+    /// Draws each game object that has a `MeshRenderer`, in the order they were
+    /// spawned. `apply_uniforms` is called with each game object's local to world
+    /// matrix and its material, after the pipeline and bindings have been applied,
+    /// and before the mesh is drawn.
+    pub fn draw(&self, mut apply_uniforms: impl FnMut(Mat4, &Material)) {
+        for entity in self.spawn_order.iter().filter_map(|&id| self.entity(id)) {
+            let Some(MeshRenderer { mesh, material }) = &entity.renderer else {
+                continue
+            };
+
+            unsafe {
+                sg::apply_pipeline(material.pipeline);
+                sg::apply_bindings(&mesh.bindings);
+            }
+
+            apply_uniforms(self.transforms.local_to_world(entity.transform), material);
+
+            unsafe { sg::draw(mesh.base_element, mesh.element_count, 1); }
+        }
+    }
+
+    fn entity(&self, id: GameObjectId) -> Option<&Entity> {
+        self.slots.get(id.index)
+            .filter(|slot| slot.generation == id.generation)
+            .and_then(|slot| slot.entity.as_ref())
+    }
+
+    fn entity_mut(&mut self, id: GameObjectId) -> Option<&mut Entity> {
+        self.slots.get_mut(id.index)
+            .filter(|slot| slot.generation == id.generation)
+            .and_then(|slot| slot.entity.as_mut())
+    }
+
+    /// Calls `hook` on each of the game object's scripts, in order, if the game
+    /// object exists.
+    fn call_hook(&mut self, id: GameObjectId, mut hook: impl FnMut(&mut dyn Script, &mut Context)) {
+        // Take the scripts out, so the hooks can have mutable access to the rest
+        // of the scene.
+        let mut scripts = match self.entity_mut(id) {
+            Some(entity) => core::mem::take(&mut entity.scripts),
+            None => return,
+        };
+
+        let mut context = Context {
+            scene: self,
+            game_object: id,
+        };
+
+        for script in scripts.iter_mut() {
+            hook(script.as_mut(), &mut context);
+        }
+
+        // Destroys are deferred, so the entity is still there.
+        if let Some(entity) = self.entity_mut(id) {
+            entity.scripts = scripts;
+        }
+    }
+
+    fn flush_pending_destroys(&mut self) {
+        // `on_destroy` hooks can destroy more game objects, so we can't just
+        // iterate over the list.
+        let mut i = 0;
+        while i < self.pending_destroys.len() {
+            let id = self.pending_destroys[i];
+            i += 1;
+
+            let Some(transform) = self.transform_id(id) else {
+                continue
+            };
+
+            // `on_destroy` hooks can reparent things, so we look for the next game
+            // object to destroy again after each one. A game object that gets
+            // moved out from under the destroyed ones before its own hook is
+            // called survives.
+            let mut destroyed = Vec::new();
+            let mut next = Some(transform);
+            while let Some(descendant) = next {
+                let descendant_id = self.game_objects_by_transform[&descendant];
+
+                self.call_hook(descendant_id, |script, context| script.on_destroy(context));
+
+                let slot = &mut self.slots[descendant_id.index];
+                slot.entity = None;
+                slot.generation = slot.generation.wrapping_add(1);
+                self.free.push(descendant_id.index);
+                self.game_objects_by_transform.remove(&descendant);
+                destroyed.push(descendant);
+
+                next = destroyed.iter()
+                    .flat_map(|&t| self.transforms.descendants_and_self(t))
+                    .find(|t| self.game_objects_by_transform.contains_key(t));
+            }
+
+            // Everything under the destroyed transforms has been destroyed now,
+            // including any that hooks moved elsewhere after they were destroyed.
+            for destroyed_transform in destroyed {
+                if self.transforms.contains(destroyed_transform) {
+                    self.transforms.remove(destroyed_transform);
+                }
+            }
+        }
+
+        self.pending_destroys.clear();
+
+        let slots = &self.slots;
+        self.spawn_order.retain(|id| slots[id.index].generation == id.generation);
+    }
+}
+
+#[cfg(test)]
+use std::{cell::RefCell, rc::Rc};
+
+#[cfg(test)]
+type Log = Rc<RefCell<Vec<String>>>;
+
+#[cfg(test)]
+/// Records each hook call in the log. Can optionally spawn or destroy things
+/// during its first `update`.
+struct Recorder {
+    name: &'static str,
+    log: Log,
+    spawn_on_update: Option<&'static str>,
+    destroy_on_update: Option<GameObjectId>,
+}
+
+#[cfg(test)]
+impl Recorder {
+    fn boxed(name: &'static str, log: &Log) -> Box<dyn Script> {
+        Box::new(Self {
+            name,
+            log: log.clone(),
+            spawn_on_update: None,
+            destroy_on_update: None,
+        })
+    }
+
+    fn record(&self, hook: &str) {
+        self.log.borrow_mut().push(format!("{} {hook}", self.name));
+    }
+}
+
+#[cfg(test)]
+impl Script for Recorder {
+    fn awake(&mut self, _context: &mut Context) {
+        self.record("awake");
+    }
+
+    fn start(&mut self, _context: &mut Context) {
+        self.record("start");
+    }
+
+    fn update(&mut self, context: &mut Context, _dt: f32) {
+        self.record("update");
+
+        if let Some(name) = self.spawn_on_update.take() {
+            context.spawn(GameObject {
+                scripts: vec![Recorder::boxed(name, &self.log)],
+                ..<_>::default()
+            });
+        }
+
+        if let Some(id) = self.destroy_on_update.take() {
+            context.destroy(id);
+        }
+    }
+
+    fn on_destroy(&mut self, _context: &mut Context) {
+        self.record("on_destroy");
+    }
+}
+
+#[cfg(test)]
+fn take_log(log: &Log) -> Vec<String> {
+    core::mem::take(&mut *log.borrow_mut())
+}
+
+#[test]
+fn hooks_are_called_in_the_documented_order() {
+    let log = Log::default();
+    let mut scene = Scene::default();
+
+    scene.spawn(GameObject {
+        scripts: vec![Recorder::boxed("a1", &log), Recorder::boxed("a2", &log)],
+        ..<_>::default()
+    });
+    scene.spawn(GameObject {
+        scripts: vec![Recorder::boxed("b", &log)],
+        ..<_>::default()
+    });
+
+    assert_eq!(take_log(&log), ["a1 awake", "a2 awake", "b awake"]);
+
+    scene.frame(1. / 60.);
+
+    assert_eq!(
+        take_log(&log),
+        [
+            "a1 start", "a2 start", "b start",
+            "a1 update", "a2 update", "b update",
+        ]
+    );
+
+    scene.frame(1. / 60.);
+
+    assert_eq!(take_log(&log), ["a1 update", "a2 update", "b update"]);
+}
+
+#[test]
+fn game_objects_spawned_during_a_frame_are_started_on_the_next_one() {
+    let log = Log::default();
+    let mut scene = Scene::default();
+
+    scene.spawn(GameObject {
+        scripts: vec![Box::new(Recorder {
+            name: "spawner",
+            log: log.clone(),
+            spawn_on_update: Some("spawned"),
+            destroy_on_update: None,
+        })],
+        ..<_>::default()
+    });
+    scene.frame(1. / 60.);
+
+    assert_eq!(
+        take_log(&log),
+        ["spawner awake", "spawner start", "spawner update", "spawned awake"]
+    );
+
+    scene.frame(1. / 60.);
+
+    assert_eq!(take_log(&log), ["spawned start", "spawner update", "spawned update"]);
+}
+
+#[test]
+fn destroying_is_deferred_to_the_end_of_the_frame_and_includes_children() {
+    let log = Log::default();
+    let mut scene = Scene::default();
+
+    let parent = scene.spawn(GameObject {
+        scripts: vec![Recorder::boxed("parent", &log)],
+        ..<_>::default()
+    });
+    let child = scene.spawn(GameObject {
+        parent: Some(parent),
+        scripts: vec![Recorder::boxed("child", &log)],
+        ..<_>::default()
+    });
+    let destroyer = scene.spawn(GameObject {
+        scripts: vec![Box::new(Recorder {
+            name: "destroyer",
+            log: log.clone(),
+            spawn_on_update: None,
+            destroy_on_update: Some(parent),
+        })],
+        ..<_>::default()
+    });
+    scene.frame(1. / 60.);
+
+    assert_eq!(
+        take_log(&log)[6..],
+        [
+            "parent update", "child update", "destroyer update",
+            "parent on_destroy", "child on_destroy",
+        ]
+    );
+    assert!(!scene.contains(parent));
+    assert!(!scene.contains(child));
+    assert!(scene.contains(destroyer));
+
+    scene.frame(1. / 60.);
+
+    assert_eq!(take_log(&log), ["destroyer update"]);
+
+    scene.destroy_all();
+
+    assert_eq!(take_log(&log), ["destroyer on_destroy"]);
+}
+
+#[test]
+fn scripts_can_move_their_own_transform() {
+    struct Mover;
+
+    impl Script for Mover {
+        fn update(&mut self, context: &mut Context, dt: f32) {
+            context.transform().position.x += dt;
+        }
+    }
+
+    let mut scene = Scene::default();
+
+    let id = scene.spawn(GameObject {
+        scripts: vec![Box::new(Mover)],
+        ..<_>::default()
+    });
+
+    scene.frame(0.5);
+    scene.frame(0.25);
+
+    let transform_id = scene.transform_id(id).unwrap();
+
+    assert_eq!(scene.transforms().position(transform_id).x, 0.75);
+}
+
+#[test]
+fn game_objects_reparented_during_on_destroy_are_not_leaked() {
+    /// Moves `rescued`, then the game object it is attached to, out from under
+    /// the root when destroyed.
+    struct Rescuer {
+        rescued: GameObjectId,
+    }
+
+    impl Script for Rescuer {
+        fn on_destroy(&mut self, context: &mut Context) {
+            context.set_parent(self.rescued, None);
+
+            let id = context.game_object();
+            context.set_parent(id, None);
+        }
+    }
+
+    let mut scene = Scene::default();
+
+    let root = scene.spawn(GameObject::default());
+    let rescuer = scene.spawn(GameObject {
+        parent: Some(root),
+        ..<_>::default()
+    });
+    let rescued = scene.spawn(GameObject {
+        parent: Some(rescuer),
+        ..<_>::default()
+    });
+    let rescued_child = scene.spawn(GameObject {
+        parent: Some(rescued),
+        ..<_>::default()
+    });
+    let doomed = scene.spawn(GameObject {
+        parent: Some(rescuer),
+        ..<_>::default()
+    });
+    scene.entity_mut(rescuer).unwrap().scripts.push(Box::new(Rescuer { rescued }));
+
+    let [root_t, rescuer_t, rescued_t, rescued_child_t, doomed_t] =
+        [root, rescuer, rescued, rescued_child, doomed]
+            .map(|id| scene.transform_id(id).unwrap());
+
+    scene.destroy(root);
+
+    // `rescued` was moved before its own `on_destroy` was called, so it survives.
+    assert!(scene.contains(rescued));
+    assert!(scene.contains(rescued_child));
+    assert_eq!(scene.transforms().parent(rescued_t), None);
+    assert_eq!(scene.transforms().parent(rescued_child_t), Some(rescued_t));
+
+    // `rescuer` moved itself out, but it and the rest of its children are still
+    // cleaned up.
+    for (id, transform) in [(root, root_t), (rescuer, rescuer_t), (doomed, doomed_t)] {
+        assert!(!scene.contains(id));
+        assert!(!scene.transforms().contains(transform));
+    }
+}
+
+#[test]
+fn reused_slots_keep_stale_ids_dead_and_the_spawn_order() {
+    let log = Log::default();
+    let mut scene = Scene::default();
+
+    let destroyed = scene.spawn(GameObject::default());
+    scene.spawn(GameObject {
+        scripts: vec![Box::new(Recorder {
+            name: "older",
+            log: log.clone(),
+            spawn_on_update: Some("spawned"),
+            destroy_on_update: None,
+        })],
+        ..<_>::default()
+    });
+    scene.destroy(destroyed);
+    take_log(&log);
+
+    // The game object spawned during this update reuses `destroyed`'s slot, but
+    // is still neither started nor updated until the next frame, and comes
+    // after "older" since it was spawned after it.
+    scene.frame(1. / 60.);
+    assert_eq!(take_log(&log), ["older start", "older update", "spawned awake"]);
+
+    scene.frame(1. / 60.);
+    assert_eq!(
+        take_log(&log),
+        ["spawned start", "older update", "spawned update"]
+    );
+
+    assert_eq!(scene.slots.len(), 2);
+    assert!(!scene.contains(destroyed));
+    assert!(scene.transform_id(destroyed).is_none());
+    assert!(scene.renderer_mut(destroyed).is_none());
+
+    // Destroying a stale id does nothing to the game object now in its slot.
+    scene.destroy(destroyed);
+    assert_eq!(scene.spawn_order.len(), 2);
+}
@@ -0,0 +1,470 @@
+use core::cell::Cell;
+
+use math::{
+    mat4::Mat4,
+    quat::Quat,
+    vec3::{vec3, Vec3},
+};
+
+/// The position, rotation and scale of something, relative to its parent if it
+/// has one, or relative to the world otherwise. These are the same as the local
+/// values that Unity's `Transform` exposes.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Transform {
+    pub position: Vec3,
+    pub rotation: Quat,
+    pub scale: Vec3,
+}
+
+pub const DEFAULT: Transform = Transform {
+    position: vec3!(),
+    rotation: math::quat::IDENTITY,
+    scale: vec3!(1., 1., 1.),
+};
+
+impl Default for Transform {
+    fn default() -> Self {
+        DEFAULT
+    }
+}
+
+impl Transform {
+    /// Applies the scale first, then the rotation, then the translation.
+    pub fn local_to_parent(&self) -> Mat4 {
+        Mat4::compose(self.position, self.rotation, self.scale)
+    }
+}
+
+/// Refers to a `Transform` inside a `Hierarchy`. Only meaningful for the
+/// `Hierarchy` that produced it. The slot of a removed `Transform` is reused by
+/// later ones, but each reuse gets a new generation, so an id of a removed
+/// `Transform` never refers to a different one.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct TransformId {
+    index: usize,
+    generation: u32,
+}
+
+struct Slot {
+    // Incremented each time the slot's node is removed.
+    generation: u32,
+    node: Option<Node>,
+}
+
+struct Node {
+    local: Transform,
+    parent: Option<TransformId>,
+    children: Vec<TransformId>,
+    // These are filled in lazily, and cleared when the node or any of its
+    // ancestors change. We maintain the invariant that if a node's matrix is
+    // cached, then all of its ancestors' matrices are cached as well. So when
+    // we find a node without a cached matrix, we know we can stop clearing.
+    local_to_world: Cell<Option<Mat4>>,
+    world_to_local: Cell<Option<Mat4>>,
+}
+
+/// A collection of `Transform`s, each with an optional parent. A `Transform`'s
+/// world space matrix is its parent's world space matrix multiplied by its own
+/// local matrix. The world space matrices are calculated when they are asked for,
+/// and are cached until the `Transform` or one of its ancestors is changed.
+#[derive(Default)]
+pub struct Hierarchy {
+    // Removed nodes are set to `None`, so that the indexes of the others stay
+    // the same. Their indexes go on `free` to be reused by `add`.
+    slots: Vec<Slot>,
+    free: Vec<usize>,
+}
+
+impl Hierarchy {
+    /// Adds a `Transform` without a parent.
+    pub fn add(&mut self, local: Transform) -> TransformId {
+        let node = Node {
+            local,
+            parent: None,
+            children: Vec::new(),
+            local_to_world: Cell::new(None),
+            world_to_local: Cell::new(None),
+        };
+
+        let index = match self.free.pop() {
+            Some(index) => index,
+            None => {
+                self.slots.push(Slot { generation: 0, node: None });
+                self.slots.len() - 1
+            }
+        };
+
+        let slot = &mut self.slots[index];
+        slot.node = Some(node);
+
+        TransformId { index, generation: slot.generation }
+    }
+
+    /// Adds a `Transform` with `local` relative to `parent`.
+    pub fn add_child(&mut self, parent: TransformId, local: Transform) -> TransformId {
+        let id = self.add(local);
+
+        self.set_parent(id, Some(parent));
+
+        id
+    }
+
+    /// Removes the given `Transform`, along with all of its descendants.
+    pub fn remove(&mut self, id: TransformId) {
+        if let Some(parent) = self.parent(id) {
+            self.node_mut(parent).children.retain(|&child| child != id);
+        }
+
+        self.remove_subtree(id);
+    }
+
+    fn remove_subtree(&mut self, id: TransformId) {
+        let node = self.node_mut(id);
+        let children = core::mem::take(&mut node.children);
+
+        let slot = &mut self.slots[id.index];
+        slot.node = None;
+        slot.generation = slot.generation.wrapping_add(1);
+        self.free.push(id.index);
+
+        for child in children {
+            self.remove_subtree(child);
+        }
+    }
+
+    /// Returns `false` if the `Transform` has been removed.
+    pub fn contains(&self, id: TransformId) -> bool {
+        matches!(
+            self.slots.get(id.index),
+            Some(Slot { generation, node: Some(_) }) if *generation == id.generation
+        )
+    }
+
+    pub fn parent(&self, id: TransformId) -> Option<TransformId> {
+        self.node(id).parent
+    }
+
+    pub fn children(&self, id: TransformId) -> &[TransformId] {
+        &self.node(id).children
+    }
+
+    /// Returns the given `Transform` and all of its descendants, with each
+    /// `Transform` coming before its children, and children in the order they
+    /// were added.
+    pub fn descendants_and_self(&self, id: TransformId) -> Vec<TransformId> {
+        let mut output = Vec::new();
+
+        self.push_descendants_and_self(id, &mut output);
+
+        output
+    }
+
+    fn push_descendants_and_self(&self, id: TransformId, output: &mut Vec<TransformId>) {
+        output.push(id);
+
+        for &child in self.children(id) {
+            self.push_descendants_and_self(child, output);
+        }
+    }
+
+    /// Changes the parent of the given `Transform`, keeping its local values
+    /// as they are. So the world space position, etc. will generally change.
+    ///
+    /// # Panics
+    /// Panics if this would make a `Transform` its own ancestor.
+    pub fn set_parent(&mut self, id: TransformId, parent: Option<TransformId>) {
+        let mut ancestor = parent;
+        while let Some(ancestor_id) = ancestor {
+            assert_ne!(ancestor_id, id, "A Transform cannot be its own ancestor");
+            ancestor = self.parent(ancestor_id);
+        }
+
+        if let Some(old_parent) = self.node(id).parent {
+            self.node_mut(old_parent).children.retain(|&child| child != id);
+        }
+
+        if let Some(new_parent) = parent {
+            self.node_mut(new_parent).children.push(id);
+        }
+
+        self.node_mut(id).parent = parent;
+
+        self.invalidate(id);
+    }
+
+    pub fn local(&self, id: TransformId) -> &Transform {
+        &self.node(id).local
+    }
+
+    /// Changing the `Transform` through the returned reference will be reflected
+    /// in the world space matrices of it and its descendants.
+    pub fn local_mut(&mut self, id: TransformId) -> &mut Transform {
+        self.invalidate(id);
+
+        &mut self.node_mut(id).local
+    }
+
+    pub fn local_to_world(&self, id: TransformId) -> Mat4 {
+        let node = self.node(id);
+
+        if let Some(cached) = node.local_to_world.get() {
+            return cached;
+        }
+
+        let local_to_parent = node.local.local_to_parent();
+
+        let local_to_world = match node.parent {
+            Some(parent) => self.local_to_world(parent) * local_to_parent,
+            None => local_to_parent,
+        };
+
+        node.local_to_world.set(Some(local_to_world));
+
+        local_to_world
+    }
+
+    /// If the `Transform` or one of its ancestors has a scale of zero on some
+    /// axis, then there is no way to go back from world space, so the all zeroes
+    /// matrix is returned.
+    pub fn world_to_local(&self, id: TransformId) -> Mat4 {
+        let node = self.node(id);
+
+        if let Some(cached) = node.world_to_local.get() {
+            return cached;
+        }
+
+        let world_to_local = self.local_to_world(id)
+            .affine_inverse()
+            .unwrap_or_default();
+
+        node.world_to_local.set(Some(world_to_local));
+
+        world_to_local
+    }
+
+    /// The position of the `Transform` in world space.
+    pub fn position(&self, id: TransformId) -> Vec3 {
+        let local_to_world = self.local_to_world(id);
+
+        (local_to_world * math::vec4::vec4!(w)).xyz()
+    }
+
+    /// The direction of the `Transform`'s positive z axis, in world space.
+    pub fn forward(&self, id: TransformId) -> Vec3 {
+        self.local_to_world(id).z_axis().normalize()
+    }
+
+    /// The direction of the `Transform`'s positive x axis, in world space.
+    pub fn right(&self, id: TransformId) -> Vec3 {
+        self.local_to_world(id).x_axis().normalize()
+    }
+
+    /// The direction of the `Transform`'s positive y axis, in world space.
+    pub fn up(&self, id: TransformId) -> Vec3 {
+        self.local_to_world(id).y_axis().normalize()
+    }
+
+    fn invalidate(&self, id: TransformId) {
+        let node = self.node(id);
+
+        node.world_to_local.set(None);
+
+        if node.local_to_world.take().is_none() {
+            // By the invariant mentioned in `Node`, the descendants have nothing
+            // cached either.
+            return;
+        }
+
+        for &child in node.children.iter() {
+            self.invalidate(child);
+        }
+    }
+
+    fn node(&self, id: TransformId) -> &Node {
+        let slot = &self.slots[id.index];
+
+        slot.node.as_ref()
+            .filter(|_| slot.generation == id.generation)
+            .expect("TransformId should refer to a Transform that has not been removed")
+    }
+
+    fn node_mut(&mut self, id: TransformId) -> &mut Node {
+        let slot = &mut self.slots[id.index];
+
+        slot.node.as_mut()
+            .filter(|_| slot.generation == id.generation)
+            .expect("TransformId should refer to a Transform that has not been removed")
+    }
+}
+
+#[cfg(test)]
+use math::angle::Degrees;
+
+#[cfg(test)]
+fn assert_close(actual: Vec3, expected: Vec3) {
+    assert!(
+        (actual - expected).length() < 1. / 65536.,
+        "{actual} was not close to {expected}"
+    );
+}
+
+#[test]
+fn nested_children_combine_their_parents_transforms() {
+    let mut hierarchy = Hierarchy::default();
+
+    let root = hierarchy.add(Transform {
+        position: vec3!(10., 0., 0.),
+        ..DEFAULT
+    });
+
+    let pivot = hierarchy.add_child(root, Transform {
+        rotation: Quat::from_axis_angle(Degrees(90.), vec3!(z)),
+        ..DEFAULT
+    });
+
+    let arm = hierarchy.add_child(pivot, Transform {
+        position: vec3!(0., 2., 0.),
+        scale: vec3!(1., 3., 1.),
+        ..DEFAULT
+    });
+
+    // Rotating y by 90 degrees around z points it down -x.
+    assert_close(hierarchy.position(arm), vec3!(8., 0., 0.));
+    assert_close(hierarchy.up(arm), vec3!(-x));
+    assert_close(hierarchy.right(arm), vec3!(y));
+    assert_close(hierarchy.forward(arm), vec3!(z));
+
+    let expected = Mat4::translate(vec3!(10., 0., 0.))
+        * Mat4::rotation(Degrees(90.), vec3!(z))
+        * Mat4::translate(vec3!(0., 2., 0.))
+        * Mat4::scale(vec3!(1., 3., 1.));
+
+    let actual = hierarchy.local_to_world(arm);
+    for (a, e) in actual.to_row_major().iter().zip(expected.to_row_major().iter()) {
+        assert!((a - e).abs() < 1. / 65536., "{actual} != {expected}");
+    }
+}
+
+#[test]
+fn changing_an_ancestor_updates_the_cached_descendants() {
+    let mut hierarchy = Hierarchy::default();
+
+    let root = hierarchy.add(DEFAULT);
+    let child = hierarchy.add_child(root, DEFAULT);
+    let grandchild = hierarchy.add_child(child, Transform {
+        position: vec3!(1., 0., 0.),
+        ..DEFAULT
+    });
+
+    // Fill the caches.
+    assert_close(hierarchy.position(grandchild), vec3!(1., 0., 0.));
+
+    hierarchy.local_mut(root).position = vec3!(0., 5., 0.);
+    assert_close(hierarchy.position(grandchild), vec3!(1., 5., 0.));
+
+    hierarchy.local_mut(child).scale = vec3!(2., 2., 2.);
+    assert_close(hierarchy.position(grandchild), vec3!(2., 5., 0.));
+
+    hierarchy.local_mut(child).rotation = Quat::from_axis_angle(Degrees(180.), vec3!(y));
+    assert_close(hierarchy.position(grandchild), vec3!(-2., 5., 0.));
+    hierarchy.local_mut(root).position = vec3!();
+    assert_close(hierarchy.position(grandchild), vec3!(-2., 0., 0.));
+}
+
+#[test]
+fn world_to_local_undoes_local_to_world() {
+    let mut hierarchy = Hierarchy::default();
+
+    let root = hierarchy.add(Transform {
+        position: vec3!(1., 2., 3.),
+        rotation: Quat::from_euler(Degrees(10.), Degrees(20.), Degrees(30.)),
+        scale: vec3!(2., 2., 2.),
+    });
+    let child = hierarchy.add_child(root, Transform {
+        position: vec3!(-4., 0., 1.),
+        scale: vec3!(0.5, 1., 4.),
+        ..DEFAULT
+    });
+
+    let point = math::vec4::vec4!(7., 8., 9., 1.);
+    let round_tripped = hierarchy.world_to_local(child)
+        * (hierarchy.local_to_world(child) * point);
+
+    assert_close(round_tripped.xyz(), point.xyz());
+}
+
+#[test]
+fn set_parent_moves_the_transform_between_parents() {
+    let mut hierarchy = Hierarchy::default();
+
+    let a = hierarchy.add(Transform { position: vec3!(1., 0., 0.), ..DEFAULT });
+    let b = hierarchy.add(Transform { position: vec3!(0., 1., 0.), ..DEFAULT });
+    let child = hierarchy.add_child(a, DEFAULT);
+
+    assert_close(hierarchy.position(child), vec3!(1., 0., 0.));
+
+    hierarchy.set_parent(child, Some(b));
+
+    assert_eq!(hierarchy.children(a), &[]);
+    assert_eq!(hierarchy.children(b), &[child]);
+    assert_eq!(hierarchy.parent(child), Some(b));
+    assert_close(hierarchy.position(child), vec3!(0., 1., 0.));
+
+    hierarchy.set_parent(child, None);
+
+    assert_eq!(hierarchy.children(b), &[]);
+    assert_close(hierarchy.position(child), vec3!());
+}
+
+#[test]
+#[should_panic]
+fn set_parent_rejects_cycles() {
+    let mut hierarchy = Hierarchy::default();
+
+    let root = hierarchy.add(DEFAULT);
+    let child = hierarchy.add_child(root, DEFAULT);
+
+    hierarchy.set_parent(root, Some(child));
+}
+
+#[test]
+fn remove_takes_the_descendants_with_it() {
+    let mut hierarchy = Hierarchy::default();
+
+    let root = hierarchy.add(DEFAULT);
+    let child = hierarchy.add_child(root, DEFAULT);
+    let grandchild = hierarchy.add_child(child, DEFAULT);
+    let sibling = hierarchy.add_child(root, DEFAULT);
+
+    assert_eq!(
+        hierarchy.descendants_and_self(root),
+        vec![root, child, grandchild, sibling]
+    );
+
+    hierarchy.remove(child);
+
+    assert!(hierarchy.contains(root));
+    assert!(!hierarchy.contains(child));
+    assert!(!hierarchy.contains(grandchild));
+    assert!(hierarchy.contains(sibling));
+    assert_eq!(hierarchy.children(root), &[sibling]);
+}
+
+#[test]
+fn removed_slots_are_reused_without_reviving_old_ids() {
+    let mut hierarchy = Hierarchy::default();
+
+    let removed = hierarchy.add(DEFAULT);
+    hierarchy.remove(removed);
+
+    let added = hierarchy.add(Transform {
+        position: vec3!(1., 2., 3.),
+        ..DEFAULT
+    });
+
+    assert_eq!(hierarchy.slots.len(), 1);
+    assert_ne!(added, removed);
+    assert!(hierarchy.contains(added));
+    assert!(!hierarchy.contains(removed));
+    assert_eq!(hierarchy.local(added).position, vec3!(1., 2., 3.));
+}
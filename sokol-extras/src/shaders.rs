@@ -1,6 +1,44 @@
+use sokol_bindings::sg::Backend;
+
+/// The backends that each shader in this module has source for. Making one of
+/// the shaders with any other backend panics.
+pub const SUPPORTED_BACKENDS: [Backend; 3] = [
+    Backend::GlCore3_3,
+    Backend::Gles3,
+    Backend::Dummy,
+];
+
+// These take GLSL without a version line, that works with both versions, and
+// produce a `CharStar`.
+macro_rules! glsl_330 {
+    ($source: expr) => {
+        concat!("#version 330\n", $source, "\0").as_ptr() as sokol_bindings::CharStar
+    }
+}
+
+macro_rules! glsl_300_es_vs {
+    ($source: expr) => {
+        concat!("#version 300 es\n", $source, "\0").as_ptr() as sokol_bindings::CharStar
+    }
+}
+
+// Fragment shaders in GLSL ES have no default float precision.
+macro_rules! glsl_300_es_fs {
+    ($source: expr) => {
+        concat!(
+            "#version 300 es\nprecision mediump float;\nprecision highp int;\n",
+            $source,
+            "\0"
+        ).as_ptr() as sokol_bindings::CharStar
+    }
+}
+
 pub mod basic;
 pub mod textured;
 pub mod lit;
+pub mod pbr;
+pub mod phong;
+pub mod skybox;
 pub mod textured_lit;
 
 /// From most significant to least significant. So in a hex literal that's
@@ -43,4 +81,29 @@ pub fn abgr_from_vec3(v: math::vec3::Vec3) -> ABGR {
     | (((v.x * 256.0) as u8) as ABGR)
 }
 
-pub type Index = u16;
\ No newline at end of file
+pub type Index = u16;
+
+#[cfg(test)]
+fn assert_sources_are_valid(
+    sources: fn(Backend) -> (sokol_bindings::CharStar, sokol_bindings::CharStar),
+) {
+    use std::ffi::CStr;
+
+    for backend in SUPPORTED_BACKENDS {
+        let (vs, fs) = sources(backend);
+
+        for source in [vs, fs] {
+            assert!(!source.is_null(), "{backend:?}");
+
+            // SAFETY: The sources are made with `concat!` and end with a nul.
+            let source = unsafe { CStr::from_ptr(source) }.to_str().unwrap();
+
+            let expected_version = match backend {
+                Backend::Gles3 => "#version 300 es\n",
+                _ => "#version 330\n",
+            };
+            assert!(source.starts_with(expected_version), "{backend:?}: {source}");
+            assert!(source.contains("void main()"), "{backend:?}: {source}");
+        }
+    }
+}
@@ -1,5 +1,6 @@
 use sokol_bindings::{
     cstr,
+    CharStar,
     sg::{self, Backend, DepthState, LayoutDesc, ShaderDesc},
 };
 
@@ -69,16 +70,10 @@ const ATTR_VS_POSITION: u8 = 0;
 const ATTR_VS_COLOR0: u8 = 1;
 const SLOT_VS_PARAMS: u8 = 0;
 
-fn shader_desc(backend: Backend) -> ShaderDesc {
-    // TODO support other backends besides `GlCore3_3`
-    assert_eq!(backend, Backend::GlCore3_3);
-
-    let mut desc = ShaderDesc::default();
-
-    desc.attrs[ATTR_VS_POSITION as usize].name = cstr!("position");
-    desc.attrs[ATTR_VS_COLOR0 as usize].name = cstr!("color0");
-    desc.vs.source = cstr!("#version 330
-
+// The GLSL is written to work as both version 330 and version 300 es, and
+// `glsl_330!` and friends add the version line.
+macro_rules! vs_source {
+    () => {"
 uniform vec4 vs_params[4];
 layout(location = 0) in vec4 position;
 out vec4 color;
@@ -89,22 +84,54 @@ void main()
 gl_Position = mat4(vs_params[0], vs_params[1], vs_params[2], vs_params[3]) * position;
 color = color0;
 }
-");
-    desc.vs.uniform_blocks[0].size = 64;
-    desc.vs.uniform_blocks[0].layout = sg::UniformLayout::Std140 as _;
-    desc.vs.uniform_blocks[0].uniforms[0].name = cstr!("vs_params");
-    desc.vs.uniform_blocks[0].uniforms[0].type_ = sg::UniformType::Float4 as _;
-    desc.vs.uniform_blocks[0].uniforms[0].array_count = 4;
-    desc.vs.entry = cstr!("main");
-    desc.fs.source = cstr!("#version 330
+"}
+}
 
+macro_rules! fs_source {
+    () => {"
 layout(location = 0) out vec4 frag_color;
 in vec4 color;
 
 void main()
 {
 frag_color = color;
-}");
+}"}
+}
+
+/// The vertex and fragment shader source, in that order.
+///
+/// # Panics
+/// If the backend is not one of `shaders::SUPPORTED_BACKENDS`.
+fn sources(backend: Backend) -> (CharStar, CharStar) {
+    match backend {
+        // The dummy backend ignores the source, so any will do there.
+        Backend::GlCore3_3 | Backend::Dummy => (
+            glsl_330!(vs_source!()),
+            glsl_330!(fs_source!()),
+        ),
+        Backend::Gles3 => (
+            glsl_300_es_vs!(vs_source!()),
+            glsl_300_es_fs!(fs_source!()),
+        ),
+        _ => panic!("unsupported backend: {backend:?}"),
+    }
+}
+
+fn shader_desc(backend: Backend) -> ShaderDesc {
+    let (vs_source, fs_source) = sources(backend);
+
+    let mut desc = ShaderDesc::default();
+
+    desc.attrs[ATTR_VS_POSITION as usize].name = cstr!("position");
+    desc.attrs[ATTR_VS_COLOR0 as usize].name = cstr!("color0");
+    desc.vs.source = vs_source;
+    desc.vs.uniform_blocks[0].size = 64;
+    desc.vs.uniform_blocks[0].layout = sg::UniformLayout::Std140 as _;
+    desc.vs.uniform_blocks[0].uniforms[0].name = cstr!("vs_params");
+    desc.vs.uniform_blocks[0].uniforms[0].type_ = sg::UniformType::Float4 as _;
+    desc.vs.uniform_blocks[0].uniforms[0].array_count = 4;
+    desc.vs.entry = cstr!("main");
+    desc.fs.source = fs_source;
     desc.fs.entry = cstr!("main");
     desc.label = cstr!("basic_shader");
 
@@ -154,3 +181,8 @@ pub fn apply_uniforms(vs_params: VSParams) {
         );
     }
 }
+
+#[test]
+fn there_are_sources_for_each_supported_backend() {
+    super::assert_sources_are_valid(sources);
+}
@@ -1,18 +1,12 @@
-use sokol_bindings::{
-    cstr,
-    sg::{self, Backend, DepthState, LayoutDesc, ShaderDesc},
-};
+use sokol_bindings::sg::{self, Backend, DepthState, LayoutDesc};
 
-use math::{
-    mat4::Mat4,
-    vec3::{vec3, Vec3},
-};
+use crate::uniforms::UniformBlock;
 
-#[repr(C)]
-pub struct Vertex {
-    pub position: Vec3,
-    pub normal: Vec3,
-}
+use math::vec3::vec3;
+
+// `Vertex`, `VSParams`, `FSParams`, `shader_desc` and `layout_desc`, generated
+// from lit.glsl by build.rs.
+include!(concat!(env!("OUT_DIR"), "/lit.rs"));
 
 pub const VERTEX_DEFAULT: Vertex = Vertex {
     position: vec3!(),
@@ -60,111 +54,6 @@ macro_rules! _lit_vertex_array {
 }
 pub use _lit_vertex_array as vertex_array;
 
-const ATTR_VS_POSITION: u8 = 0;
-const ATTR_VS_NORMAL: u8 = 1;
-pub const SLOT_FS_PARAMS: u8 = 0;
-pub const SLOT_VS_PARAMS: u8 = 0;
-
-fn shader_desc(backend: Backend) -> ShaderDesc {
-    // TODO support other backends besides `GlCore3_3`
-    assert_eq!(backend, Backend::GlCore3_3);
-
-    let mut desc = ShaderDesc::default();
-
-    desc.attrs[ATTR_VS_POSITION as usize].name = cstr!("position");
-    desc.attrs[ATTR_VS_NORMAL as usize].name = cstr!("normal");
-
-    desc.vs.source = cstr!("#version 330
-
-uniform vec4 vs_params[9];
-layout(location = 0) in vec4 position;
-layout(location = 1) in vec3 normal;
-out vec4 P;
-out vec3 N;
-out vec3 color;
-
-void main()
-{
-    mat4 mvp = mat4(vs_params[4], vs_params[5], vs_params[6], vs_params[7]);
-    gl_Position = mvp * position;
-    mat4 model = mat4(vs_params[0], vs_params[1], vs_params[2], vs_params[3]);
-    P = model * position;
-    N = (model * vec4(normal, 0.0)).xyz;
-    color = vs_params[8].xyz;
-}");
-    desc.vs.uniform_blocks[0].size = 144;
-    desc.vs.uniform_blocks[0].layout = sg::UniformLayout::Std140 as _;
-    desc.vs.uniform_blocks[0].uniforms[0].name = cstr!("vs_params");
-    desc.vs.uniform_blocks[0].uniforms[0].type_ = sg::UniformType::Float4 as _;
-    desc.vs.uniform_blocks[0].uniforms[0].array_count = 9;
-    desc.vs.entry = cstr!("main");
-
-    desc.fs.source = cstr!("#version 330
-
-uniform vec4 fs_params[2];
-
-in vec3 N;
-in vec4 P;
-layout(location = 0) out vec4 fragColor;
-in vec3 color;
-
-vec4 linearToGamma(vec4 c)
-{
-    return vec4(pow(c.xyz, vec3(1/2.2)), c.w);
-}
-
-float gammaToLinear(float c)
-{
-    return pow(c, 2.2);
-}
-
-void main()
-{
-    vec3 lightDir = normalize(fs_params[0].xyz);
-    vec3 normal = normalize(N);
-    float incidentLightFrac = dot(normal, lightDir);
-    if (incidentLightFrac > 0.0)
-    {
-        vec3 eye = fs_params[1].xyz;
-        float reflectedLightFrac = dot(
-            reflect(-lightDir, normal),
-            normalize(eye - P.xyz)
-        );
-        fragColor = vec4(
-            (
-                gammaToLinear(max(reflectedLightFrac, 0.0))
-                * incidentLightFrac
-            )
-            + (color * (incidentLightFrac + 0.25)),
-            1.0
-        );
-    } else {
-        fragColor = vec4(color * 0.25, 1.0);
-    }
-    fragColor = linearToGamma(fragColor);
-}");
-
-    desc.fs.entry = cstr!("main");
-    desc.fs.uniform_blocks[0].size = 32;
-    desc.fs.uniform_blocks[0].layout = sg::UniformLayout::Std140 as _;
-    desc.fs.uniform_blocks[0].uniforms[0].name = cstr!("fs_params");
-    desc.fs.uniform_blocks[0].uniforms[0].type_ = sg::UniformType::Float4 as _;
-    desc.fs.uniform_blocks[0].uniforms[0].array_count = 2;
-    desc.label = cstr!("lit_shader");
-
-    desc
-}
-
-fn layout_desc() -> LayoutDesc {
-    use sg::VertexFormat;
-    let mut layout = LayoutDesc::default();
-
-    layout.attrs[ATTR_VS_POSITION as usize].format = VertexFormat::Float3 as _;
-    layout.attrs[ATTR_VS_NORMAL as usize].format = VertexFormat::Float3 as _;
-
-    layout
-}
-
 fn depth_state() -> DepthState {
     let mut depth = DepthState::default();
     depth.write_enabled = true;
@@ -187,52 +76,70 @@ pub fn make_shader_etc(backend: Backend) -> ShaderEtc {
     )
 }
 
-pub struct FSParams {
-    pub light_dir: Vec3,
-    pub eye_pos: Vec3,
+pub fn apply_uniforms(vs_params: VSParams, fs_params: FSParams) {
+    vs_params.apply(sg::ShaderStage::VS, SLOT_VS_PARAMS as _);
+    fs_params.apply(sg::ShaderStage::FS, SLOT_FS_PARAMS as _);
 }
 
-pub struct VSParams {
-    pub model: Mat4,
-    pub mvp: Mat4,
-    pub diffuse_colour: Vec3,
+#[test]
+fn there_are_sources_for_each_supported_backend() {
+    super::assert_sources_are_valid(sources);
 }
 
-pub fn apply_uniforms(
-    VSParams { model, mvp, diffuse_colour: d }: VSParams,
-    FSParams { light_dir: l, eye_pos: e }: FSParams,
-) {
-    let m1 = model.to_column_major();
-    let m2 = mvp.to_column_major();
-
-    let vs_params_array = [
-        m1[ 0], m1[ 1], m1[ 2], m1[ 3],
-        m1[ 4], m1[ 5], m1[ 6], m1[ 7],
-        m1[ 8], m1[ 9], m1[10], m1[11],
-        m1[12], m1[13], m1[14], m1[15],
-        m2[ 0], m2[ 1], m2[ 2], m2[ 3],
-        m2[ 4], m2[ 5], m2[ 6], m2[ 7],
-        m2[ 8], m2[ 9], m2[10], m2[11],
-        m2[12], m2[13], m2[14], m2[15],
-        d.x, d.y, d.z, 0.,
-    ];
-
-    let fs_params_array = [
-        l.x, l.y, l.z, 0.,
-        e.x, e.y, e.z, 0.,
-    ];
-
-    unsafe {
-        sg::apply_uniforms(
-            sg::ShaderStage::VS as _,
-            SLOT_VS_PARAMS as _,
-            &sg::range!(vs_params_array)
-        );
-
-        sg::apply_uniforms(
-            sg::ShaderStage::FS as _,
-            SLOT_FS_PARAMS as _,
-            &sg::range!(fs_params_array)
-        );
-    }
+#[cfg(all(feature = "hot-reload", feature = "dummy-backend"))]
+#[test]
+fn the_pipeline_is_rebuilt_when_the_glsl_changes() {
+    use crate::hot_reload::{HotReloadPipeline, Reload, ReloadError};
+    use sokol_bindings::sg::{Context, PipelineBuilder};
+    use std::{fs, time::{Duration, SystemTime}};
+
+    let dir = std::env::temp_dir().join(format!("hot-reload-test-{}", std::process::id()));
+    fs::create_dir_all(&dir).unwrap();
+    let path = dir.join("lit.glsl");
+    let original = fs::read_to_string(GLSL_PATH).unwrap();
+
+    // Written with an explicit modification time, since the file system's
+    // resolution might not notice a change otherwise.
+    let mut seconds = 0;
+    let mut write = |source: &str| {
+        fs::write(&path, source).unwrap();
+        seconds += 1;
+        fs::File::options().write(true).open(&path).unwrap()
+            .set_modified(SystemTime::UNIX_EPOCH + Duration::from_secs(seconds))
+            .unwrap();
+    };
+    write(&original);
+
+    Context::with_dummy(|context| {
+        let mut pipeline = HotReloadPipeline::new(
+            context,
+            &path,
+            shader_desc,
+            |shader| PipelineBuilder::new(shader, layout_desc()),
+        ).unwrap();
+        let first = pipeline.pipeline().handle();
+
+        assert!(matches!(pipeline.poll(), Reload::Unchanged));
+
+        write(&original.replace("color * 0.25", "color * 0.5"));
+        assert!(matches!(pipeline.poll(), Reload::Reloaded));
+        assert_ne!(pipeline.pipeline().handle().id, first.id);
+        assert!(matches!(pipeline.poll(), Reload::Unchanged));
+
+        let reloaded = pipeline.pipeline().handle();
+
+        write(&original.replace("@uniform vec3 eye_pos", "@uniform vec4 eye_pos"));
+        assert!(matches!(
+            pipeline.poll(),
+            Reload::Failed(ReloadError::DeclarationsChanged)
+        ));
+
+        write(&original.replace("@end", "@ned"));
+        assert!(matches!(pipeline.poll(), Reload::Failed(ReloadError::Parse(_))));
+
+        // The last pipeline that worked is kept.
+        assert_eq!(pipeline.pipeline().handle().id, reloaded.id);
+    });
+
+    fs::remove_dir_all(&dir).unwrap();
 }
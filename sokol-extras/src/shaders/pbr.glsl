@@ -0,0 +1,166 @@
+// See the `shader_gen` crate docs for what the `@` lines mean.
+@vs
+@in vec4 position Float3
+@in vec3 normal Float3
+@in vec4 tangent Float4
+@in vec2 uv Float2
+@uniform mat4 model
+@uniform mat4 mvp
+out vec4 P;
+out vec3 N;
+out vec4 T;
+out vec2 texcoord;
+
+void main()
+{
+    gl_Position = mvp * position;
+    P = model * position;
+    N = (model * vec4(normal, 0.0)).xyz;
+    T = vec4((model * vec4(tangent.xyz, 0.0)).xyz, tangent.w);
+    texcoord = uv;
+}
+@end
+
+@fs
+/// Multiplied with `albedo_map`. In linear space, unlike the map.
+@uniform vec3 albedo
+/// 0 for dielectrics, like plastic or wood, and 1 for metals. Multiplied with
+/// the blue channel of `metallic_roughness_map`.
+@uniform float metallic
+/// 0 for perfectly smooth surfaces, up to 1 for completely rough ones.
+/// Multiplied with the green channel of `metallic_roughness_map`.
+@uniform float roughness
+/// How strongly `normal_map` bends the normals. 0 ignores the map.
+@uniform float normal_scale
+/// How much of the red channel of `occlusion_map` is applied to the ambient
+/// light. 0 ignores the map.
+@uniform float occlusion_strength
+@uniform vec3 eye_pos
+/// The direction the single directional light travels in.
+@uniform vec3 light_dir
+/// In linear space. Can be brighter than 1.
+@uniform vec3 light_colour
+/// Scales the light from `environment`.
+@uniform float environment_intensity
+/// The index of the smallest mipmap of `environment`. Rougher surfaces sample
+/// smaller mipmaps, as a cheap stand-in for blurring the environment.
+@uniform float environment_max_lod
+@image 2d albedo_map
+// Roughness in the green channel and metallic in the blue, like glTF.
+@image 2d metallic_roughness_map
+@image 2d normal_map
+@image 2d occlusion_map
+@image cube environment
+in vec4 P;
+in vec3 N;
+in vec4 T;
+in vec2 texcoord;
+layout(location = 0) out vec4 fragColor;
+
+const float PI = 3.14159265;
+
+vec4 linearToGamma(vec4 c)
+{
+    return vec4(pow(c.xyz, vec3(1.0/2.2)), c.w);
+}
+
+vec3 gammaToLinear(vec3 c)
+{
+    return pow(c, vec3(2.2));
+}
+
+// The GGX/Trowbridge-Reitz normal distribution function.
+float distributionGGX(float NdotH, float alpha)
+{
+    float alpha2 = alpha * alpha;
+    float d = NdotH * NdotH * (alpha2 - 1.0) + 1.0;
+    // `d * d` can be smaller than the smallest mediump float, so we square the
+    // ratio instead, which stays in range.
+    float k = alpha / d;
+    return k * k / PI;
+}
+
+// Smith's geometry function, with Schlick's approximation for each direction.
+float geometrySmith(float NdotV, float NdotL, float r)
+{
+    float k = (r + 1.0) * (r + 1.0) / 8.0;
+    float viewTerm = NdotV / (NdotV * (1.0 - k) + k);
+    float lightTerm = NdotL / (NdotL * (1.0 - k) + k);
+    return viewTerm * lightTerm;
+}
+
+vec3 fresnelSchlick(float cosTheta, vec3 F0)
+{
+    return F0 + (1.0 - F0) * pow(1.0 - cosTheta, 5.0);
+}
+
+// An analytic fit of the split sum BRDF lookup table, from "Physically Based
+// Shading on Mobile" by Brian Karis. Returns the scale and bias to apply to F0.
+vec2 environmentBRDF(float r, float NdotV)
+{
+    const vec4 c0 = vec4(-1.0, -0.0275, -0.572, 0.022);
+    const vec4 c1 = vec4(1.0, 0.0425, 1.04, -0.04);
+    vec4 v = r * c0 + c1;
+    float a004 = min(v.x * v.x, exp2(-9.28 * NdotV)) * v.x + v.y;
+    return vec2(-1.04, 1.04) * a004 + v.zw;
+}
+
+void main()
+{
+    vec3 baseColour = albedo * gammaToLinear(texture(albedo_map, texcoord).rgb);
+    vec4 metallicRoughness = texture(metallic_roughness_map, texcoord);
+    // Perfectly smooth surfaces would have infinitely bright highlights. At this
+    // minimum, the peak of `distributionGGX` is about 3000, which fits in a
+    // mediump float.
+    float r = clamp(roughness * metallicRoughness.g, 0.1, 1.0);
+    float m = clamp(metallic * metallicRoughness.b, 0.0, 1.0);
+    float occlusion = mix(1.0, texture(occlusion_map, texcoord).r, occlusion_strength);
+
+    vec3 surfaceNormal = normalize(N);
+    vec3 surfaceTangent = normalize(T.xyz - surfaceNormal * dot(surfaceNormal, T.xyz));
+    vec3 bitangent = cross(surfaceNormal, surfaceTangent) * T.w;
+    vec3 mapped = texture(normal_map, texcoord).xyz * 2.0 - 1.0;
+    mapped.xy *= normal_scale;
+    vec3 n = normalize(
+        surfaceTangent * mapped.x + bitangent * mapped.y + surfaceNormal * mapped.z
+    );
+
+    vec3 v = normalize(eye_pos - P.xyz);
+    float NdotV = max(dot(n, v), 0.0001);
+
+    // Dielectrics reflect about 4% of light head on, and metals reflect their
+    // colour.
+    vec3 F0 = mix(vec3(0.04), baseColour, m);
+
+    vec3 colour = vec3(0.0);
+
+    vec3 l = normalize(-light_dir);
+    float NdotL = dot(n, l);
+    if (NdotL > 0.0)
+    {
+        vec3 h = normalize(l + v);
+        vec3 F = fresnelSchlick(max(dot(h, v), 0.0), F0);
+        float D = distributionGGX(max(dot(n, h), 0.0), r * r);
+        float G = geometrySmith(NdotV, NdotL, r);
+
+        vec3 specular = D * G * F / (4.0 * NdotV * NdotL);
+        vec3 diffuse = (1.0 - F) * (1.0 - m) * baseColour / PI;
+
+        colour += (diffuse + specular) * light_colour * NdotL;
+    }
+
+    vec3 F = fresnelSchlick(NdotV, F0);
+    vec3 irradiance = gammaToLinear(textureLod(environment, n, environment_max_lod).rgb);
+    vec3 ambientDiffuse = (1.0 - F) * (1.0 - m) * baseColour * irradiance;
+
+    vec3 reflected = gammaToLinear(
+        textureLod(environment, reflect(-v, n), r * environment_max_lod).rgb
+    );
+    vec2 brdf = environmentBRDF(r, NdotV);
+    vec3 ambientSpecular = reflected * (F0 * brdf.x + brdf.y);
+
+    colour += (ambientDiffuse + ambientSpecular) * environment_intensity * occlusion;
+
+    fragColor = linearToGamma(vec4(colour, 1.0));
+}
+@end
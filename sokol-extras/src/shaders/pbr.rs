@@ -0,0 +1,209 @@
+//! A physically based shader, using the metallic-roughness model with a
+//! Cook-Torrance BRDF and the GGX normal distribution. There is one directional
+//! light, and ambient light from a cube image of the surroundings.
+//!
+//! The members of `FSParams` are documented in pbr.glsl, and so on the
+//! generated struct.
+
+use sokol_bindings::{
+    sg::{self, Backend, Bindings, DepthState, LayoutDesc},
+    Int,
+};
+
+use crate::{images::sky_gradient, uniforms::UniformBlock};
+
+use math::{
+    geom::Mesh,
+    vec3::{vec3, Vec3},
+    vec4::vec4,
+};
+
+// `Vertex`, `VSParams`, `FSParams`, `shader_desc` and `layout_desc`, generated
+// from pbr.glsl by build.rs.
+include!(concat!(env!("OUT_DIR"), "/pbr.rs"));
+
+pub const VERTEX_DEFAULT: Vertex = Vertex {
+    position: vec3!(),
+    normal: vec3!(),
+    tangent: vec4!(1., 0., 0., 1.),
+    uv: [0., 0.],
+};
+
+impl Default for Vertex {
+    fn default() -> Self {
+        VERTEX_DEFAULT
+    }
+}
+
+/// The vertices of `mesh`, in the same order as its points. Meshes without
+/// tangents get `VERTEX_DEFAULT`'s, which only suit flat normal maps.
+pub fn mesh_vertices(mesh: &Mesh) -> Vec<Vertex> {
+    (0..mesh.points.len()).map(|i| {
+        let point = mesh.points[i];
+        let uv = mesh.uvs.get(i).copied().unwrap_or_default();
+
+        Vertex {
+            position: vec3!(point.x, point.y, point.z),
+            normal: mesh.normals[i].into(),
+            tangent: mesh.tangents.get(i).copied().unwrap_or(VERTEX_DEFAULT.tangent),
+            uv: [uv.u, uv.v],
+        }
+    }).collect()
+}
+
+fn depth_state() -> DepthState {
+    let mut depth = DepthState::default();
+    depth.write_enabled = true;
+    depth.compare = sg::CompareFunc::LessEqual as _;
+    depth
+}
+
+/// A `Shader` and some other parts of a `sg::Pipeline` that one is unlikely to
+/// change without also changing the shader code.
+pub type ShaderEtc = (sg::Shader, LayoutDesc, DepthState);
+
+pub fn make_shader_etc(backend: Backend) -> ShaderEtc {
+    let shader_desc = shader_desc(backend);
+    let shader = unsafe { sg::make_shader(&shader_desc) };
+
+    (
+        shader,
+        layout_desc(),
+        depth_state(),
+    )
+}
+
+/// The values that scale what is read from the maps. With `Maps::neutral`,
+/// these are the whole description of the surface.
+#[derive(Clone, Copy, Debug)]
+pub struct Material {
+    /// In linear space.
+    pub albedo: Vec3,
+    pub metallic: f32,
+    pub roughness: f32,
+    pub normal_scale: f32,
+    pub occlusion_strength: f32,
+}
+
+impl Default for Material {
+    fn default() -> Self {
+        Self {
+            albedo: vec3!(1., 1., 1.),
+            metallic: 0.,
+            roughness: 0.5,
+            normal_scale: 1.,
+            occlusion_strength: 1.,
+        }
+    }
+}
+
+/// The 2D images that describe a surface, which go in the `SLOT_*_MAP` slots.
+#[derive(Clone, Copy, Debug)]
+pub struct Maps {
+    pub albedo: sg::Image,
+    pub metallic_roughness: sg::Image,
+    pub normal: sg::Image,
+    pub occlusion: sg::Image,
+}
+
+impl Maps {
+    /// Maps that leave the `Material` values as they are. `white` and
+    /// `flat_normal` should be the images that `images::white::make` and
+    /// `images::flat_normal::make` return. They are taken as parameters, so that
+    /// they can be shared, and destroyed by the caller.
+    pub fn neutral(white: sg::Image, flat_normal: sg::Image) -> Self {
+        Self {
+            albedo: white,
+            metallic_roughness: white,
+            normal: flat_normal,
+            occlusion: white,
+        }
+    }
+
+    pub fn bind(&self, bindings: &mut Bindings, environment: &Environment) {
+        let images = &mut bindings.fs_images;
+        images[SLOT_ALBEDO_MAP as usize] = self.albedo;
+        images[SLOT_METALLIC_ROUGHNESS_MAP as usize] = self.metallic_roughness;
+        images[SLOT_NORMAL_MAP as usize] = self.normal;
+        images[SLOT_OCCLUSION_MAP as usize] = self.occlusion;
+        images[SLOT_ENVIRONMENT as usize] = environment.image;
+    }
+}
+
+/// A cube image of the surroundings, which lights everything drawn with this
+/// shader. The image should have mipmaps, which are used for rough surfaces.
+#[derive(Clone, Copy, Debug)]
+pub struct Environment {
+    pub image: sg::Image,
+    pub mipmap_count: Int,
+    pub intensity: f32,
+}
+
+impl Environment {
+    /// `image` should be the one `images::sky_gradient::make` returns. It is
+    /// taken as a parameter, so that it can be destroyed by the caller.
+    pub fn sky_gradient(image: sg::Image) -> Self {
+        Self {
+            image,
+            mipmap_count: sky_gradient::MIPMAP_COUNT,
+            intensity: 1.,
+        }
+    }
+}
+
+/// Everything the fragment shader needs besides the material.
+#[derive(Clone, Copy, Debug)]
+pub struct Lighting<'environment> {
+    pub eye_pos: Vec3,
+    /// The direction the light travels in.
+    pub light_dir: Vec3,
+    /// In linear space. Can be brighter than 1.
+    pub light_colour: Vec3,
+    pub environment: &'environment Environment,
+}
+
+impl Lighting<'_> {
+    pub fn fs_params(&self, material: &Material) -> FSParams {
+        FSParams {
+            albedo: material.albedo,
+            metallic: material.metallic,
+            roughness: material.roughness,
+            normal_scale: material.normal_scale,
+            occlusion_strength: material.occlusion_strength,
+            eye_pos: self.eye_pos,
+            light_dir: self.light_dir,
+            light_colour: self.light_colour,
+            environment_intensity: self.environment.intensity,
+            environment_max_lod: (self.environment.mipmap_count - 1).max(0) as f32,
+        }
+    }
+}
+
+pub fn apply_uniforms(vs_params: VSParams, lighting: &Lighting, material: &Material) {
+    vs_params.apply(sg::ShaderStage::VS, SLOT_VS_PARAMS as _);
+    lighting.fs_params(material).apply(sg::ShaderStage::FS, SLOT_FS_PARAMS as _);
+}
+
+#[test]
+fn there_are_sources_for_each_supported_backend() {
+    super::assert_sources_are_valid(sources);
+}
+
+#[test]
+fn mesh_vertices_keep_the_tangents_and_uvs() {
+    let sphere = math::geom::gen_uv_sphere_mesh(1., 4, 2);
+    let vertices = mesh_vertices(&sphere);
+
+    assert_eq!(vertices.len(), sphere.points.len());
+    for (i, vertex) in vertices.iter().enumerate() {
+        assert_eq!(vertex.tangent, sphere.tangents[i]);
+        assert_eq!(vertex.uv, [sphere.uvs[i].u, sphere.uvs[i].v]);
+    }
+
+    let no_tangents = math::geom::Mesh {
+        points: vec![math::geom::Point::default()],
+        normals: vec![math::vec3::Normal::default()],
+        ..<_>::default()
+    };
+    assert_eq!(mesh_vertices(&no_tangents)[0].tangent, VERTEX_DEFAULT.tangent);
+}
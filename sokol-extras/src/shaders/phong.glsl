@@ -0,0 +1,100 @@
+// See the `shader_gen` crate docs for what the `@` lines mean.
+@vs
+@in vec4 position Float3
+@in vec3 normal Float3
+@uniform mat4 model
+@uniform mat4 mvp
+out vec4 P;
+out vec3 N;
+
+void main()
+{
+    gl_Position = mvp * position;
+    P = model * position;
+    N = (model * vec4(normal, 0.0)).xyz;
+}
+@end
+
+@fs
+@uniform vec3 ambient_colour
+@uniform float light_count
+@uniform vec3 diffuse_colour
+@uniform float shininess
+@uniform vec3 specular_colour
+@uniform vec3 emissive_colour
+@uniform vec3 eye_pos
+// The lights are packed into vec4s, as `phong::Light::pack` describes.
+@uniform vec4 light_position[8]
+@uniform vec4 light_direction[8]
+@uniform vec4 light_colour[8]
+@uniform vec4 light_attenuation[8]
+in vec4 P;
+in vec3 N;
+layout(location = 0) out vec4 fragColor;
+
+const float DIRECTIONAL = 0.0;
+const float POINT = 1.0;
+
+vec4 linearToGamma(vec4 c)
+{
+    return vec4(pow(c.xyz, vec3(1.0/2.2)), c.w);
+}
+
+void main()
+{
+    vec3 normal = normalize(N);
+    vec3 toEye = normalize(eye_pos - P.xyz);
+
+    vec3 colour = emissive_colour + ambient_colour * diffuse_colour;
+
+    for (int i = 0; i < int(light_count); i++)
+    {
+        vec4 position = light_position(i);
+        vec4 direction = light_direction(i);
+        vec4 lightColour = light_colour(i);
+
+        vec3 toLight;
+        float intensity = 1.0;
+        if (position.w == DIRECTIONAL)
+        {
+            toLight = normalize(-direction.xyz);
+        }
+        else
+        {
+            vec3 offset = position.xyz - P.xyz;
+            float dist = length(offset);
+            toLight = offset / dist;
+
+            vec3 attenuation = light_attenuation(i).xyz;
+            intensity = 1.0 / (
+                attenuation.x
+                + attenuation.y * dist
+                + attenuation.z * dist * dist
+            );
+
+            if (position.w != POINT)
+            {
+                // The cosines of the outer and inner angles of the cone.
+                float theta = dot(-toLight, normalize(direction.xyz));
+                intensity *= smoothstep(direction.w, lightColour.w, theta);
+            }
+        }
+
+        float incidentLightFrac = dot(normal, toLight);
+        if (incidentLightFrac <= 0.0)
+        {
+            continue;
+        }
+
+        vec3 halfway = normalize(toLight + toEye);
+        float specularFrac = pow(max(dot(normal, halfway), 0.0), shininess);
+
+        colour += intensity * lightColour.xyz * (
+            diffuse_colour * incidentLightFrac
+            + specular_colour * specularFrac
+        );
+    }
+
+    fragColor = linearToGamma(vec4(colour, 1.0));
+}
+@end
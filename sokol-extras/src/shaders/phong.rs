@@ -0,0 +1,317 @@
+use sokol_bindings::sg::{self, Backend, DepthState, LayoutDesc};
+
+use crate::uniforms::UniformBlock;
+
+use math::{
+    angle::Angle,
+    vec3::{vec3, Vec3},
+    vec4::{vec4, Vec4},
+};
+
+// `Vertex`, `VSParams`, `FSParams`, `shader_desc` and `layout_desc`, generated
+// from phong.glsl by build.rs.
+include!(concat!(env!("OUT_DIR"), "/phong.rs"));
+
+/// The most lights that affect a single draw call. Any past this in
+/// `Lighting::lights` are ignored.
+pub const MAX_LIGHTS: usize = 8;
+
+/// How a surface reflects light, in the Blinn-Phong model.
+#[derive(Clone, Copy, Debug)]
+pub struct Material {
+    pub diffuse: Vec3,
+    pub specular: Vec3,
+    /// The exponent of the specular term. Higher values give smaller, sharper
+    /// highlights.
+    pub shininess: f32,
+    /// Light given off by the surface itself, which is added regardless of the
+    /// lights.
+    pub emissive: Vec3,
+}
+
+impl Default for Material {
+    fn default() -> Self {
+        Self {
+            diffuse: vec3!(1., 1., 1.),
+            specular: vec3!(0.5, 0.5, 0.5),
+            shininess: 32.,
+            emissive: vec3!(),
+        }
+    }
+}
+
+/// The terms of how a light fades with distance `d`, which scales it by
+/// `1 / (constant + linear * d + quadratic * d * d)`. The default does not fade.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Attenuation {
+    pub constant: f32,
+    pub linear: f32,
+    pub quadratic: f32,
+}
+
+impl Default for Attenuation {
+    fn default() -> Self {
+        Self {
+            constant: 1.,
+            linear: 0.,
+            quadratic: 0.,
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug)]
+pub enum Light {
+    /// A light infinitely far away, like the sun. `direction` is the direction the
+    /// light travels in.
+    Directional { direction: Vec3, colour: Vec3 },
+    Point { position: Vec3, colour: Vec3, attenuation: Attenuation },
+    /// A point light that only shines in a cone around `direction`. The light is
+    /// full strength within `inner_angle` of `direction`, fading to nothing at
+    /// `outer_angle`. `inner_angle` must be less than `outer_angle`, since the
+    /// fade between them is undefined otherwise.
+    Spot {
+        position: Vec3,
+        direction: Vec3,
+        colour: Vec3,
+        attenuation: Attenuation,
+        inner_angle: math::angle::Radians,
+        outer_angle: math::angle::Radians,
+    },
+}
+
+impl Light {
+    /// The light's elements of `light_position`, `light_direction`, `light_colour`
+    /// and `light_attenuation`, in that order. The `w` of `light_position` is the
+    /// kind of light, and the `w`s of `light_direction` and `light_colour` are the
+    /// cosines of a spot light's outer and inner angles.
+    fn pack(&self) -> [Vec4; 4] {
+        const DIRECTIONAL: f32 = 0.;
+        const POINT: f32 = 1.;
+        const SPOT: f32 = 2.;
+
+        let packed_attenuation = |a: Attenuation| vec4!(a.constant, a.linear, a.quadratic, 0.);
+
+        match *self {
+            Self::Directional { direction, colour } => [
+                vec4!(0., 0., 0., DIRECTIONAL),
+                vec4!(direction.x, direction.y, direction.z, 0.),
+                vec4!(colour.x, colour.y, colour.z, 0.),
+                packed_attenuation(Attenuation::default()),
+            ],
+            Self::Point { position, colour, attenuation } => [
+                vec4!(position.x, position.y, position.z, POINT),
+                vec4!(),
+                vec4!(colour.x, colour.y, colour.z, 0.),
+                packed_attenuation(attenuation),
+            ],
+            Self::Spot {
+                position,
+                direction,
+                colour,
+                attenuation,
+                inner_angle,
+                outer_angle,
+            } => {
+                debug_assert!(
+                    inner_angle.raw_radians() < outer_angle.raw_radians(),
+                    "a spot light's inner angle should be less than its outer angle"
+                );
+
+                [
+                    vec4!(position.x, position.y, position.z, SPOT),
+                    vec4!(direction.x, direction.y, direction.z, outer_angle.raw_radians().cos()),
+                    vec4!(colour.x, colour.y, colour.z, inner_angle.raw_radians().cos()),
+                    packed_attenuation(attenuation),
+                ]
+            },
+        }
+    }
+}
+
+pub const VERTEX_DEFAULT: Vertex = Vertex {
+    position: vec3!(),
+    normal: vec3!(),
+};
+
+impl Default for Vertex {
+    fn default() -> Self {
+        VERTEX_DEFAULT
+    }
+}
+
+#[macro_export]
+macro_rules! _phong_vertex {
+    (
+        $px: expr, $py: expr, $pz: expr, $nx: expr, $ny: expr, $nz: expr $(,)?
+    ) => {
+        $crate::shaders::phong::Vertex {
+            position: vec3!($px, $py, $pz),
+            normal: vec3!($nx, $ny, $nz),
+        }
+    }
+}
+pub use _phong_vertex as vertex;
+
+
+#[macro_export]
+macro_rules! _phong_vertex_array {
+    (
+        $(
+            {$px: expr, $py: expr, $pz: expr, $nx: expr, $ny: expr, $nz: expr $(,)?}
+        ),*
+
+        $(,)?
+    ) => {
+        [
+            $(
+                $crate::shaders::phong::Vertex {
+                    position: vec3!($px, $py, $pz),
+                    normal: vec3!($nx, $ny, $nz),
+                }
+            ),*
+        ]
+    }
+}
+pub use _phong_vertex_array as vertex_array;
+
+fn depth_state() -> DepthState {
+    let mut depth = DepthState::default();
+    depth.write_enabled = true;
+    depth.compare = sg::CompareFunc::LessEqual as _;
+    depth
+}
+
+/// A `Shader` and some other parts of a `sg::Pipeline` that one is unlikely to
+/// change without also changing the shader code.
+pub type ShaderEtc = (sg::Shader, LayoutDesc, DepthState);
+
+pub fn make_shader_etc(backend: Backend) -> ShaderEtc {
+    let shader_desc = shader_desc(backend);
+    let shader = unsafe { sg::make_shader(&shader_desc) };
+
+    (
+        shader,
+        layout_desc(),
+        depth_state(),
+    )
+}
+
+/// Everything the fragment shader needs besides the vertex data. `ambient` is
+/// light that reaches every surface equally.
+#[derive(Clone, Copy, Debug)]
+pub struct Lighting<'lights> {
+    pub ambient: Vec3,
+    pub lights: &'lights [Light],
+    pub eye_pos: Vec3,
+}
+
+impl Lighting<'_> {
+    pub fn fs_params(&self, material: &Material) -> FSParams {
+        let mut fs_params = FSParams {
+            ambient_colour: self.ambient,
+            light_count: 0.,
+            diffuse_colour: material.diffuse,
+            shininess: material.shininess,
+            specular_colour: material.specular,
+            emissive_colour: material.emissive,
+            eye_pos: self.eye_pos,
+            light_position: [vec4!(); MAX_LIGHTS],
+            light_direction: [vec4!(); MAX_LIGHTS],
+            light_colour: [vec4!(); MAX_LIGHTS],
+            light_attenuation: [vec4!(); MAX_LIGHTS],
+        };
+
+        for (i, light) in self.lights.iter().take(MAX_LIGHTS).enumerate() {
+            let [position, direction, colour, attenuation] = light.pack();
+            fs_params.light_position[i] = position;
+            fs_params.light_direction[i] = direction;
+            fs_params.light_colour[i] = colour;
+            fs_params.light_attenuation[i] = attenuation;
+            fs_params.light_count += 1.;
+        }
+
+        fs_params
+    }
+}
+
+pub fn apply_uniforms(vs_params: VSParams, lighting: &Lighting, material: &Material) {
+    vs_params.apply(sg::ShaderStage::VS, SLOT_VS_PARAMS as _);
+    lighting.fs_params(material).apply(sg::ShaderStage::FS, SLOT_FS_PARAMS as _);
+}
+
+#[test]
+fn there_are_sources_for_each_supported_backend() {
+    super::assert_sources_are_valid(sources);
+}
+
+#[test]
+fn the_lights_are_packed_in_order() {
+    let lights = [
+        Light::Directional { direction: vec3!(0., -1., 0.), colour: vec3!(1., 1., 1.) },
+        Light::Point {
+            position: vec3!(1., 2., 3.),
+            colour: vec3!(1., 0., 0.),
+            attenuation: Attenuation::default(),
+        },
+    ];
+    let lighting = Lighting { ambient: vec3!(), lights: &lights, eye_pos: vec3!() };
+
+    let fs_params = lighting.fs_params(&Material::default());
+
+    assert_eq!(fs_params.light_count, 2.);
+    assert_eq!(fs_params.light_position[0], vec4!(0., 0., 0., 0.));
+    assert_eq!(fs_params.light_direction[0], vec4!(0., -1., 0., 0.));
+    assert_eq!(fs_params.light_position[1], vec4!(1., 2., 3., 1.));
+    assert_eq!(fs_params.light_colour[1], vec4!(1., 0., 0., 0.));
+    assert_eq!(fs_params.light_position[2], vec4!());
+}
+
+#[test]
+fn lights_past_the_max_are_ignored() {
+    let lights: Vec<Light> = (0..MAX_LIGHTS + 1).map(|i| Light::Point {
+        position: vec3!(i as f32, 0., 0.),
+        colour: vec3!(1., 1., 1.),
+        attenuation: Attenuation::default(),
+    }).collect();
+    let lighting = Lighting { ambient: vec3!(), lights: &lights, eye_pos: vec3!() };
+
+    let fs_params = lighting.fs_params(&Material::default());
+
+    assert_eq!(fs_params.light_count, MAX_LIGHTS as f32);
+    assert_eq!(fs_params.light_position[MAX_LIGHTS - 1].x, (MAX_LIGHTS - 1) as f32);
+}
+
+#[test]
+fn spot_lights_pack_the_cosines_of_their_angles() {
+    use math::angle::Radians;
+
+    let [position, direction, colour, attenuation] = Light::Spot {
+        position: vec3!(1., 2., 3.),
+        direction: vec3!(0., -1., 0.),
+        colour: vec3!(0.5, 0.5, 0.5),
+        attenuation: Attenuation { constant: 1., linear: 0.5, quadratic: 0.25 },
+        inner_angle: Radians(0.),
+        outer_angle: Radians(math::angle::PI),
+    }.pack();
+
+    assert_eq!(position, vec4!(1., 2., 3., 2.));
+    assert_eq!(direction, vec4!(0., -1., 0., -1.));
+    assert_eq!(colour, vec4!(0.5, 0.5, 0.5, 1.));
+    assert_eq!(attenuation, vec4!(1., 0.5, 0.25, 0.));
+}
+
+#[cfg(debug_assertions)]
+#[test]
+#[should_panic]
+fn spot_lights_with_equal_angles_are_rejected() {
+    use math::angle::Radians;
+
+    Light::Spot {
+        position: vec3!(),
+        direction: vec3!(0., -1., 0.),
+        colour: vec3!(1., 1., 1.),
+        attenuation: Attenuation::default(),
+        inner_angle: Radians(0.5),
+        outer_angle: Radians(0.5),
+    }.pack();
+}
@@ -0,0 +1,27 @@
+// See the `shader_gen` crate docs for what the `@` lines mean.
+@vs
+@in vec4 position Float3
+/// The view matrix should have its translation removed, so the skybox stays
+/// around the camera.
+@uniform mat4 view_proj
+out vec3 direction;
+
+void main()
+{
+    direction = position.xyz;
+    // Using `w` as `z` puts every point at a depth of 1.0 after the
+    // perspective divide, so the skybox is behind everything else.
+    gl_Position = (view_proj * position).xyww;
+}
+@end
+
+@fs
+@image cube sky
+in vec3 direction;
+layout(location = 0) out vec4 fragColor;
+
+void main()
+{
+    fragColor = texture(sky, direction);
+}
+@end
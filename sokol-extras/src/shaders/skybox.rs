@@ -0,0 +1,54 @@
+use sokol_bindings::sg::{self, Backend, DepthState, LayoutDesc};
+
+use crate::uniforms::UniformBlock;
+
+use math::vec3::vec3;
+
+// `Vertex`, `VSParams`, `shader_desc` and `layout_desc`, generated from
+// skybox.glsl by build.rs.
+include!(concat!(env!("OUT_DIR"), "/skybox.rs"));
+
+pub const VERTEX_DEFAULT: Vertex = Vertex {
+    position: vec3!(),
+};
+
+impl Default for Vertex {
+    fn default() -> Self {
+        VERTEX_DEFAULT
+    }
+}
+
+fn depth_state() -> DepthState {
+    // Everything is drawn at a depth of 1.0, which is what the depth buffer is
+    // cleared to, so `LessEqual` is needed for it to show up. Not writing the
+    // depth lets the skybox be drawn before or after everything else.
+    DepthState {
+        write_enabled: false,
+        compare: sg::CompareFunc::LessEqual as _,
+        ..DepthState::default()
+    }
+}
+
+/// A `Shader` and some other parts of a `sg::Pipeline` that one is unlikely to
+/// change without also changing the shader code.
+pub type ShaderEtc = (sg::Shader, LayoutDesc, DepthState);
+
+pub fn make_shader_etc(backend: Backend) -> ShaderEtc {
+    let shader_desc = shader_desc(backend);
+    let shader = unsafe { sg::make_shader(&shader_desc) };
+
+    (
+        shader,
+        layout_desc(),
+        depth_state(),
+    )
+}
+
+pub fn apply_uniforms(vs_params: VSParams) {
+    vs_params.apply(sg::ShaderStage::VS, SLOT_VS_PARAMS as _);
+}
+
+#[test]
+fn there_are_sources_for_each_supported_backend() {
+    super::assert_sources_are_valid(sources);
+}
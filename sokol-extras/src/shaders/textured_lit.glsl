@@ -0,0 +1,75 @@
+// See the `shader_gen` crate docs for what the `@` lines mean.
+@vs
+@in vec4 position Float3
+@in vec3 normal Float3
+@in vec4 color UByte4N
+@in vec2 uv Short2N
+@uniform mat4 model
+@uniform mat4 mvp
+@uniform vec3 diffuse_colour
+out vec4 vertexColor;
+out vec2 texcoord;
+out vec4 P;
+out vec3 N;
+out vec3 diffuseColor;
+
+void main()
+{
+    gl_Position = mvp * position;
+    P = model * position;
+    N = (model * vec4(normal, 0.0)).xyz;
+    diffuseColor = diffuse_colour;
+
+    vertexColor = color;
+    texcoord = uv;
+}
+@end
+
+@fs
+@uniform vec3 light_dir
+@uniform vec3 eye_pos
+@image 2d tex
+in vec3 N;
+in vec4 P;
+layout(location = 0) out vec4 fragColor;
+in vec4 vertexColor;
+in vec2 texcoord;
+in vec3 diffuseColor;
+
+vec4 linearToGamma(vec4 c)
+{
+    return vec4(pow(c.xyz, vec3(1.0/2.2)), c.w);
+}
+
+float gammaToLinear(float c)
+{
+    return pow(c, 2.2);
+}
+
+void main()
+{
+    vec3 lightDir = normalize(light_dir);
+    vec3 normal = normalize(N);
+    float incidentLightFrac = dot(normal, lightDir);
+    if (incidentLightFrac > 0.0)
+    {
+        float reflectedLightFrac = dot(
+            reflect(-lightDir, normal),
+            normalize(eye_pos - P.xyz)
+        );
+        fragColor = vec4(
+            (
+                gammaToLinear(max(reflectedLightFrac, 0.0))
+                * incidentLightFrac
+            )
+            + (diffuseColor * (incidentLightFrac + 0.25)),
+            1.0
+        );
+    } else {
+        fragColor = vec4(diffuseColor * 0.25, 1.0);
+    }
+    fragColor = linearToGamma(fragColor);
+
+    fragColor *= texture(tex, texcoord) * vertexColor;
+}
+@end
@@ -1,30 +1,17 @@
-use sokol_bindings::{
-    cstr,
-    sg::{self, Backend, DepthState, LayoutDesc, ShaderDesc},
-};
+use sokol_bindings::sg::{self, Backend, DepthState, LayoutDesc};
 
-use crate::shaders::ABGR;
-use math::{
-    mat4::Mat4,
-    vec3::{vec3, Vec3},
-};
+use crate::uniforms::UniformBlock;
+use math::vec3::vec3;
 
-#[repr(C)]
-#[derive(Debug)]
-pub struct Vertex {
-    pub position: Vec3,
-    pub normal: Vec3,
-    pub color: ABGR,
-    pub u: i16,
-    pub v: i16,
-}
+// `Vertex`, `VSParams`, `FSParams`, `shader_desc` and `layout_desc`, generated
+// from textured_lit.glsl by build.rs. `Vertex::uv` is `[u, v]`.
+include!(concat!(env!("OUT_DIR"), "/textured_lit.rs"));
 
 pub const VERTEX_DEFAULT: Vertex = Vertex {
     position: vec3!(),
     normal: vec3!(),
     color: 0xFFFFFFFF,
-    u: 0,
-    v: 0,
+    uv: [0, 0],
 };
 
 impl Default for Vertex {
@@ -44,8 +31,7 @@ macro_rules! _textured_lit_vertex {
             position: vec3!($px, $py, $pz),
             normal: vec3!($nx, $ny, $nz),
             color: $color,
-            u: $u,
-            v: $v,
+            uv: [$u, $v],
         }
     }
 }
@@ -71,8 +57,7 @@ macro_rules! _textured_lit_vertex_array {
                     position: vec3!($px, $py, $pz),
                     normal: vec3!($nx, $ny, $nz),
                     color: $color,
-                    u: $u,
-                    v: $v,
+                    uv: [$u, $v],
                 }
             ),*
         ]
@@ -80,132 +65,6 @@ macro_rules! _textured_lit_vertex_array {
 }
 pub use _textured_lit_vertex_array as vertex_array;
 
-const ATTR_VS_POSITION: u8 = 0;
-const ATTR_VS_NORMAL: u8 = 1;
-const ATTR_VS_COLOR0: u8 = 2;
-const ATTR_VS_TEXCOORD0: u8 = 3;
-pub const SLOT_TEX: u8 = 0;
-pub const SLOT_FS_PARAMS: u8 = 0;
-pub const SLOT_VS_PARAMS: u8 = 0;
-
-fn shader_desc(backend: Backend) -> ShaderDesc {
-    // TODO support other backends besides `GlCore3_3`
-    assert_eq!(backend, Backend::GlCore3_3);
-
-    let mut desc = ShaderDesc::default();
-
-    desc.attrs[ATTR_VS_POSITION as usize].name = cstr!("position");
-    desc.attrs[ATTR_VS_NORMAL as usize].name = cstr!("normal");
-    desc.attrs[ATTR_VS_COLOR0 as usize].name = cstr!("color0");
-    desc.attrs[ATTR_VS_TEXCOORD0 as usize].name = cstr!("texcoord0");
-
-    desc.vs.source = cstr!("#version 330
-
-uniform vec4 vs_params[9];
-layout(location = 0) in vec4 position;
-layout(location = 1) in vec3 normal;
-layout(location = 2) in vec4 color0;
-layout(location = 3) in vec2 texcoord0;
-out vec4 vertexColor;
-out vec2 uv;
-out vec4 P;
-out vec3 N;
-out vec3 diffuseColor;
-
-void main()
-{
-    mat4 mvp = mat4(vs_params[4], vs_params[5], vs_params[6], vs_params[7]);
-    gl_Position = mvp * position;
-    mat4 model = mat4(vs_params[0], vs_params[1], vs_params[2], vs_params[3]);
-    P = model * position;
-    N = (model * vec4(normal, 0.0)).xyz;
-    diffuseColor = vs_params[8].xyz;
-
-    vertexColor = color0;
-    uv = texcoord0;
-}");
-    desc.vs.uniform_blocks[0].size = 144;
-    desc.vs.uniform_blocks[0].layout = sg::UniformLayout::Std140 as _;
-    desc.vs.uniform_blocks[0].uniforms[0].name = cstr!("vs_params");
-    desc.vs.uniform_blocks[0].uniforms[0].type_ = sg::UniformType::Float4 as _;
-    desc.vs.uniform_blocks[0].uniforms[0].array_count = 9;
-    desc.vs.entry = cstr!("main");
-
-    desc.fs.source = cstr!("#version 330
-
-uniform vec4 fs_params[2];
-uniform sampler2D tex;
-in vec3 N;
-in vec4 P;
-layout(location = 0) out vec4 fragColor;
-in vec4 vertexColor;
-in vec2 uv;
-in vec3 diffuseColor;
-
-vec4 linearToGamma(vec4 c)
-{
-    return vec4(pow(c.xyz, vec3(1/2.2)), c.w);
-}
-
-float gammaToLinear(float c)
-{
-    return pow(c, 2.2);
-}
-
-void main()
-{
-    vec3 lightDir = normalize(fs_params[0].xyz);
-    vec3 normal = normalize(N);
-    float incidentLightFrac = dot(normal, lightDir);
-    if (incidentLightFrac > 0.0)
-    {
-        vec3 eye = fs_params[1].xyz;
-        float reflectedLightFrac = dot(
-            reflect(-lightDir, normal),
-            normalize(eye - P.xyz)
-        );
-        fragColor = vec4(
-            (
-                gammaToLinear(max(reflectedLightFrac, 0.0))
-                * incidentLightFrac
-            )
-            + (diffuseColor * (incidentLightFrac + 0.25)),
-            1.0
-        );
-    } else {
-        fragColor = vec4(diffuseColor * 0.25, 1.0);
-    }
-    fragColor = linearToGamma(fragColor);
-
-    fragColor *= texture(tex, uv) * vertexColor;
-}");
-
-    desc.fs.entry = cstr!("main");
-    desc.fs.uniform_blocks[0].size = 32;
-    desc.fs.uniform_blocks[0].layout = sg::UniformLayout::Std140 as _;
-    desc.fs.uniform_blocks[0].uniforms[0].name = cstr!("fs_params");
-    desc.fs.uniform_blocks[0].uniforms[0].type_ = sg::UniformType::Float4 as _;
-    desc.fs.uniform_blocks[0].uniforms[0].array_count = 2;
-    desc.fs.images[0].name = cstr!("tex");
-    desc.fs.images[0].image_type = sg::ImageType::_2D as _;
-    desc.fs.images[0].sampler_type = sg::SamplerType::Float as _;
-    desc.label = cstr!("lit_shader");
-
-    desc
-}
-
-fn layout_desc() -> LayoutDesc {
-    use sg::VertexFormat;
-    let mut layout = LayoutDesc::default();
-
-    layout.attrs[ATTR_VS_POSITION as usize].format = VertexFormat::Float3 as _;
-    layout.attrs[ATTR_VS_NORMAL as usize].format = VertexFormat::Float3 as _;
-    layout.attrs[ATTR_VS_COLOR0 as usize].format = VertexFormat::UByte4N as _;
-    layout.attrs[ATTR_VS_TEXCOORD0 as usize].format = VertexFormat::Short2N as _;
-
-    layout
-}
-
 fn depth_state() -> DepthState {
     let mut depth = DepthState::default();
     depth.write_enabled = true;
@@ -228,54 +87,49 @@ pub fn make_shader_etc(backend: Backend) -> ShaderEtc {
     )
 }
 
-#[derive(Clone, Copy)]
-pub struct FSParams {
-    pub light_dir: Vec3,
-    pub eye_pos: Vec3,
+pub fn apply_uniforms(vs_params: VSParams, fs_params: FSParams) {
+    vs_params.apply(sg::ShaderStage::VS, SLOT_VS_PARAMS as _);
+    fs_params.apply(sg::ShaderStage::FS, SLOT_FS_PARAMS as _);
 }
 
-#[derive(Clone, Copy)]
-pub struct VSParams {
-    pub model: Mat4,
-    pub mvp: Mat4,
-    pub diffuse_colour: Vec3,
-}
+#[cfg(feature = "dummy-backend")]
+#[test]
+fn pipelines_can_be_built_with_the_shader() {
+    use std::rc::Rc;
+    use sokol_bindings::sg::{
+        Context, IndexType, OwnedBuffer, OwnedShader, PipelineBuilder, PipelineError,
+        ResourceState,
+    };
+
+    Context::with_dummy(|context| {
+        let (shader, layout, depth) = make_shader_etc(sg::query_backend());
+
+        // SAFETY: Nothing else has the handle.
+        let shader = Rc::new(
+            unsafe { OwnedShader::from_handle(context, shader) }.unwrap()
+        );
 
-pub fn apply_uniforms(
-    VSParams { model, mvp, diffuse_colour: d }: VSParams,
-    FSParams { light_dir: l, eye_pos: e }: FSParams,
-) {
-    let m1 = model.to_column_major();
-    let m2 = mvp.to_column_major();
+        let vertices = OwnedBuffer::immutable_vertices(context, &[VERTEX_DEFAULT; 3], "vertices")
+            .unwrap();
 
-    let vs_params_array = [
-        m1[ 0], m1[ 1], m1[ 2], m1[ 3],
-        m1[ 4], m1[ 5], m1[ 6], m1[ 7],
-        m1[ 8], m1[ 9], m1[10], m1[11],
-        m1[12], m1[13], m1[14], m1[15],
-        m2[ 0], m2[ 1], m2[ 2], m2[ 3],
-        m2[ 4], m2[ 5], m2[ 6], m2[ 7],
-        m2[ 8], m2[ 9], m2[10], m2[11],
-        m2[12], m2[13], m2[14], m2[15],
-        d.x, d.y, d.z, 0.,
-    ];
+        let mut bindings = sg::Bindings::default();
+        bindings.vertex_buffers[0] = vertices.handle();
 
-    let fs_params_array = [
-        l.x, l.y, l.z, 0.,
-        e.x, e.y, e.z, 0.,
-    ];
+        let builder = || PipelineBuilder::new(Rc::clone(&shader), layout)
+            .depth(depth)
+            .bindings(&bindings);
 
-    unsafe {
-        sg::apply_uniforms(
-            sg::ShaderStage::VS as _,
-            SLOT_VS_PARAMS as _,
-            &sg::range!(vs_params_array)
-        );
+        let pipeline = builder().build(context).unwrap();
+        assert_eq!(pipeline.state(), ResourceState::Valid);
 
-        sg::apply_uniforms(
-            sg::ShaderStage::FS as _,
-            SLOT_FS_PARAMS as _,
-            &sg::range!(fs_params_array)
+        assert_eq!(
+            builder().index_type(IndexType::UInt16).build(context).unwrap_err(),
+            PipelineError::IndexTypeWithoutIndexBuffer
         );
-    }
-}
\ No newline at end of file
+    });
+}
+
+#[test]
+fn there_are_sources_for_each_supported_backend() {
+    super::assert_sources_are_valid(sources);
+}
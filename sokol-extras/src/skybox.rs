@@ -0,0 +1,424 @@
+//! A skybox drawn from a cube image, by sampling it with the direction from the
+//! camera. The cube image can be made from six separate faces, or from a single
+//! image with the faces laid out in a cross. It can also be used as the
+//! environment for `shaders::pbr`, so reflections and ambient light match the sky.
+//!
+//! The faces are as seen from inside the cube, with the front being `-z`, the
+//! direction the camera faces by default. The cross layout is four faces wide and
+//! three tall, like this:
+//!
+//! ```text
+//!         top
+//! left   front  right  back
+//!        bottom
+//! ```
+//!
+//! The side faces have `+y` up. The top and bottom faces have the edge that
+//! touches the front face towards the front face in the cross.
+
+use sokol_bindings::{
+    cstr,
+    sg::{
+        self,
+        Bindings,
+        Pipeline,
+        PipelineDesc,
+    },
+    Int,
+};
+use math::{
+    geom::gen_cube_mesh,
+    mat4::Mat4,
+    vec3::{vec3, Vec3},
+};
+use crate::{
+    images::cube_face_direction,
+    shaders::{pbr, skybox},
+};
+
+/// An image with 4 bytes per pixel: red, green, blue then alpha. The rows go from
+/// top to bottom.
+#[derive(Clone, Copy, Debug)]
+pub struct Rgba8<'pixels> {
+    width: Int,
+    height: Int,
+    pixels: &'pixels [u8],
+}
+
+impl <'pixels> Rgba8<'pixels> {
+    /// # Panics
+    /// If `pixels` does not contain exactly `width * height` pixels, or if the
+    /// image is empty.
+    pub fn new(width: Int, height: Int, pixels: &'pixels [u8]) -> Self {
+        assert!(width > 0 && height > 0, "the image should not be empty");
+        assert_eq!(
+            pixels.len(),
+            width as usize * height as usize * 4,
+            "pixels should contain 4 bytes for each pixel"
+        );
+
+        Self { width, height, pixels }
+    }
+
+    /// The pixel at `x`, `y`, which go from 0 to 1 across the image.
+    fn sample(&self, x: f32, y: f32) -> [u8; 4] {
+        let column = ((x * self.width as f32) as Int).clamp(0, self.width - 1);
+        let row = ((y * self.height as f32) as Int).clamp(0, self.height - 1);
+
+        let i = (row as usize * self.width as usize + column as usize) * 4;
+        [self.pixels[i], self.pixels[i + 1], self.pixels[i + 2], self.pixels[i + 3]]
+    }
+}
+
+/// The six faces of a cube image, as seen from inside the cube. See the module
+/// docs for which way up each face is.
+#[derive(Clone, Copy, Debug)]
+pub struct Faces<'pixels> {
+    /// `+x`
+    pub right: Rgba8<'pixels>,
+    /// `-x`
+    pub left: Rgba8<'pixels>,
+    /// `+y`
+    pub top: Rgba8<'pixels>,
+    /// `-y`
+    pub bottom: Rgba8<'pixels>,
+    /// `+z`
+    pub back: Rgba8<'pixels>,
+    /// `-z`
+    pub front: Rgba8<'pixels>,
+}
+
+/// The direction each face faces, and the directions of its right and up, in
+/// sokol's face order: `+x`, `-x`, `+y`, `-y`, `+z`, `-z`.
+const FACE_AXES: [(Vec3, Vec3, Vec3); 6] = [
+    (vec3!(x), vec3!(z), vec3!(y)),
+    (vec3!(-x), vec3!(-z), vec3!(y)),
+    (vec3!(y), vec3!(x), vec3!(z)),
+    (vec3!(-y), vec3!(x), vec3!(-z)),
+    (vec3!(z), vec3!(-x), vec3!(y)),
+    (vec3!(-z), vec3!(x), vec3!(y)),
+];
+
+/// The column and row of each face in the cross layout, in sokol's face order.
+const CROSS_CELLS: [(Int, Int); 6] = [(2, 1), (0, 1), (1, 0), (1, 2), (3, 1), (1, 1)];
+
+/// Which face `direction` points at, in sokol's face order, and where on that
+/// face, as seen from inside the cube. The position goes from 0 to 1, left to
+/// right and top to bottom.
+fn face_position(direction: Vec3) -> (usize, f32, f32) {
+    let face = FACE_AXES.iter()
+        .enumerate()
+        .max_by(|(_, (a, _, _)), (_, (b, _, _))| {
+            direction.dot(*a).total_cmp(&direction.dot(*b))
+        })
+        .map(|(face, _)| face)
+        .unwrap_or_default();
+
+    let (forward, right, up) = FACE_AXES[face];
+    let distance = direction.dot(forward);
+
+    (
+        face,
+        (direction.dot(right) / distance + 1.) / 2.,
+        (1. - direction.dot(up) / distance) / 2.,
+    )
+}
+
+/// The pixels of a cube image, with mipmaps, ready to be made into an image.
+#[derive(Clone, Debug)]
+pub struct CubePixels {
+    size: Int,
+    /// Each mipmap level from largest to smallest, each with the faces in sokol's
+    /// order.
+    levels: Vec<[Vec<u8>; 6]>,
+}
+
+impl CubePixels {
+    /// Each face of the cube image is `size` by `size` pixels. The faces passed in
+    /// are stretched to that size if needed.
+    pub fn from_faces(faces: &Faces, size: Int) -> Self {
+        let faces = [
+            faces.right,
+            faces.left,
+            faces.top,
+            faces.bottom,
+            faces.back,
+            faces.front,
+        ];
+
+        Self::from_fn(size, |direction| {
+            let (face, x, y) = face_position(direction);
+            faces[face].sample(x, y)
+        })
+    }
+
+    /// Slices `cross` into the faces of a cube image, in the layout described in
+    /// the module docs. Each face is a quarter of the width of `cross` in size.
+    /// The cells do not need to be square, but they are stretched to be.
+    pub fn from_cross(cross: Rgba8) -> Self {
+        let size = (cross.width / 4).max(1);
+
+        Self::from_fn(size, |direction| {
+            let (face, x, y) = face_position(direction);
+            let (column, row) = CROSS_CELLS[face];
+
+            cross.sample((column as f32 + x) / 4., (row as f32 + y) / 3.)
+        })
+    }
+
+    /// `pixel` is passed the direction of each pixel of the largest mipmap, and
+    /// the smaller ones are averaged from that.
+    fn from_fn(size: Int, pixel: impl Fn(Vec3) -> [u8; 4]) -> Self {
+        let size = size.max(1);
+        let width = size as usize;
+
+        let largest = [0, 1, 2, 3, 4, 5].map(|face| {
+            let mut pixels = Vec::with_capacity(width * width * 4);
+
+            for y in 0..width {
+                for x in 0..width {
+                    // The centers of the pixels.
+                    let s = (x as f32 + 0.5) / width as f32 * 2. - 1.;
+                    let t = (y as f32 + 0.5) / width as f32 * 2. - 1.;
+
+                    pixels.extend(pixel(cube_face_direction(face, s, t)));
+                }
+            }
+
+            pixels
+        });
+
+        let mut levels = vec![largest];
+        let mut width = width;
+        while width > 1 {
+            let next = levels[levels.len() - 1].each_ref()
+                .map(|pixels| half_size(pixels, width));
+            levels.push(next);
+            width = (width / 2).max(1);
+        }
+
+        Self { size, levels }
+    }
+
+    pub fn size(&self) -> Int {
+        self.size
+    }
+
+    pub fn mipmap_count(&self) -> Int {
+        self.levels.len() as Int
+    }
+
+    pub fn make_image(&self) -> sg::Image {
+        let mut image_desc = sg::ImageDesc {
+            type_: sg::ImageType::Cube as _,
+            width: self.size,
+            height: self.size,
+            num_mipmaps: self.mipmap_count(),
+            min_filter: sg::Filter::LinearMipmapLinear as _,
+            mag_filter: sg::Filter::Linear as _,
+            wrap_u: sg::Wrap::ClampToEdge as _,
+            wrap_v: sg::Wrap::ClampToEdge as _,
+            label: cstr!("skybox-texture"),
+            ..<_>::default()
+        };
+        for (level, faces) in self.levels.iter().enumerate() {
+            for (face, pixels) in faces.iter().enumerate() {
+                image_desc.data.subimage[face][level] = sg::range!(&pixels[..]);
+            }
+        }
+
+        // SAFETY: `self.levels` lives until after this call.
+        unsafe { sg::make_image(&image_desc) }
+    }
+}
+
+/// Averages each 2 by 2 block of the `width` by `width` image. Odd widths drop the
+/// last row and column, so the output is `width / 2` wide, as GL expects of mip
+/// levels. A 1 by 1 image stays 1 by 1.
+fn half_size(pixels: &[u8], width: usize) -> Vec<u8> {
+    let half = (width / 2).max(1);
+    let mut output = Vec::with_capacity(half * half * 4);
+
+    for y in 0..half {
+        for x in 0..half {
+            let ys = [2 * y, (2 * y + 1).min(width - 1)];
+            let xs = [2 * x, (2 * x + 1).min(width - 1)];
+
+            for channel in 0..4 {
+                let mut sum = 0;
+                for y in ys {
+                    for x in xs {
+                        sum += pixels[(y * width + x) * 4 + channel] as u32;
+                    }
+                }
+                output.push(((sum + 2) / 4) as u8);
+            }
+        }
+    }
+
+    output
+}
+
+#[derive(Default)]
+pub struct State {
+    pub bind: Bindings,
+    pub pipe: Pipeline,
+    pub mipmap_count: Int,
+}
+
+impl State {
+    /// The cube image the skybox is drawn with, for use with reflections, for
+    /// example.
+    pub fn cube_image(&self) -> sg::Image {
+        self.bind.fs_images[skybox::SLOT_SKY as usize]
+    }
+
+    /// The skybox's cube image as the environment for `shaders::pbr`.
+    pub fn environment(&self, intensity: f32) -> pbr::Environment {
+        pbr::Environment {
+            image: self.cube_image(),
+            mipmap_count: self.mipmap_count,
+            intensity,
+        }
+    }
+}
+
+pub fn init(skybox: &mut State, pixels: &CubePixels) {
+    let mesh = gen_cube_mesh(1.);
+    let vertices = mesh.points.map(|point| skybox::Vertex {
+        position: vec3!(point.x, point.y, point.z),
+    });
+
+    skybox.bind.vertex_buffers[0] = sg::make_immutable_vertex_buffer!(
+        vertices
+        "skybox-vertices"
+    );
+
+    let indices = mesh.indices;
+    skybox.bind.index_buffer = sg::make_immutable_index_buffer!(
+        indices
+        "skybox-indices"
+    );
+
+    skybox.bind.fs_images[skybox::SLOT_SKY as usize] = pixels.make_image();
+    skybox.mipmap_count = pixels.mipmap_count();
+
+    let (shader, layout, depth) = skybox::make_shader_etc(sg::query_backend());
+
+    let pipeline_desc = PipelineDesc{
+        shader,
+        layout,
+        depth,
+        index_type: sg::IndexType::UInt16 as _,
+        // The inside of the cube is what is seen.
+        cull_mode: sg::CullMode::Front as _,
+        label: cstr!("skybox-pipeline"),
+        ..PipelineDesc::default()
+    };
+    skybox.pipe = unsafe { sg::make_pipeline(&pipeline_desc) };
+}
+
+/// Draws the skybox around the camera, so only the rotation of `view` matters.
+/// Expects a projection where the far plane has a depth of 1.0, so not one of
+/// the reversed-Z ones.
+pub fn draw(skybox: &State, view: Mat4, proj: Mat4) {
+    unsafe {
+        sg::apply_pipeline(skybox.pipe);
+        sg::apply_bindings(&skybox.bind);
+    }
+
+    skybox::apply_uniforms(skybox::VSParams {
+        view_proj: proj * view.without_translation(),
+    });
+
+    unsafe { sg::draw(0, math::geom::CUBE_INDEX_COUNT as _, 1); }
+}
+
+#[test]
+fn the_cross_is_sliced_into_the_right_faces() {
+    // Each cell of a 4 by 3 cross is a single pixel with its red channel set to
+    // its column and its green channel set to its row.
+    let mut cross = Vec::new();
+    for row in 0..3 {
+        for column in 0..4 {
+            cross.extend([column, row, 0, 0xFF]);
+        }
+    }
+
+    let pixels = CubePixels::from_cross(Rgba8::new(4, 3, &cross));
+
+    assert_eq!(pixels.size(), 1);
+    for (face, (column, row)) in CROSS_CELLS.into_iter().enumerate() {
+        assert_eq!(pixels.levels[0][face], [column as u8, row as u8, 0, 0xFF]);
+    }
+}
+
+#[test]
+fn the_faces_line_up_along_their_edges() {
+    // Near the top left corner of the front face, as seen from inside.
+    let (face, x, y) = face_position(vec3!(-0.99, 0.99, -1.));
+    assert_eq!(face, 5);
+    assert!(x < 0.01 && y < 0.01, "{x} {y}");
+
+    // Across the right edge of the front face is the left edge of the right face.
+    let (face, x, _) = face_position(vec3!(1., 0.5, -0.99));
+    assert_eq!(face, 0);
+    assert!(x < 0.01, "{x}");
+
+    // Across the top edge of the front face is the bottom edge of the top face.
+    let (face, _, y) = face_position(vec3!(0.5, 1., -0.99));
+    assert_eq!(face, 2);
+    assert!(y > 0.99, "{y}");
+
+    // Across the bottom edge of the front face is the top edge of the bottom face.
+    let (face, _, y) = face_position(vec3!(0.5, -1., -0.99));
+    assert_eq!(face, 3);
+    assert!(y < 0.01, "{y}");
+}
+
+#[test]
+fn mipmaps_go_down_to_a_single_pixel() {
+    let white = [0xFF; 5 * 5 * 4];
+    let image = Rgba8::new(5, 5, &white);
+    let faces = Faces {
+        right: image,
+        left: image,
+        top: image,
+        bottom: image,
+        back: image,
+        front: image,
+    };
+
+    let pixels = CubePixels::from_faces(&faces, 5);
+
+    assert_eq!(pixels.mipmap_count(), 3);
+    let sizes: Vec<usize> = pixels.levels.iter().map(|faces| faces[0].len() / 4).collect();
+    assert_eq!(sizes, [25, 4, 1]);
+    assert!(pixels.levels[2].iter().all(|face| face == &[0xFF; 4]));
+}
+
+#[cfg(feature = "dummy-backend")]
+#[test]
+fn init_makes_valid_resources() {
+    use sokol_bindings::sg::{Context, ResourceState};
+
+    Context::with_dummy(|_| {
+        let cross = [0x80; 8 * 6 * 4];
+        let mut skybox = State::default();
+
+        init(&mut skybox, &CubePixels::from_cross(Rgba8::new(8, 6, &cross)));
+
+        // SAFETY: There are no currently known safety issues with these fns.
+        unsafe {
+            assert_eq!(
+                ResourceState::from(sokol_bindings::sg_query_image_state(skybox.cube_image())),
+                ResourceState::Valid
+            );
+            assert_eq!(
+                ResourceState::from(sokol_bindings::sg_query_pipeline_state(skybox.pipe)),
+                ResourceState::Valid
+            );
+        }
+        assert_eq!(skybox.environment(1.).mipmap_count, 2);
+    });
+}
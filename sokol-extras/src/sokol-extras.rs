@@ -2,5 +2,11 @@
 ///! but which don't fit in `sokol_bindings`.
 
 pub mod debug;
+#[cfg(feature = "hot-reload")]
+pub mod hot_reload;
 pub mod images;
-pub mod shaders;
\ No newline at end of file
+pub mod mesh;
+pub mod scene;
+pub mod shaders;
+pub mod skybox;
+pub mod uniforms;
\ No newline at end of file
@@ -0,0 +1,349 @@
+//! Packing of Rust structs into std140 uniform blocks, so that the size given to
+//! sokol in the shader desc, and the data uploaded with `sg::apply_uniforms`,
+//! both come from the same struct definition.
+//!
+//! Blocks are declared in the shader as a single `vec4` array, like
+//! `uniform vec4 vs_params[9];`, which is how sokol-shdc outputs GLSL too.
+
+use sokol_bindings::{
+    sg::{self, ShaderStage, ShaderUniformBlockDesc},
+    CharStar,
+    Int,
+};
+use math::{
+    mat4::Mat4,
+    vec3::Vec3,
+    vec4::Vec4,
+};
+
+/// A type that can be a member of a `UniformBlock`. `ALIGN` and `SIZE` are in
+/// bytes, and follow the std140 rules.
+pub trait Std140 {
+    const ALIGN: usize;
+    const SIZE: usize;
+
+    /// `out` is exactly `Self::SIZE / 4` floats long.
+    fn write_std140(&self, out: &mut [f32]);
+}
+
+impl Std140 for f32 {
+    const ALIGN: usize = 4;
+    const SIZE: usize = 4;
+
+    fn write_std140(&self, out: &mut [f32]) {
+        out[0] = *self;
+    }
+}
+
+impl Std140 for [f32; 2] {
+    const ALIGN: usize = 8;
+    const SIZE: usize = 8;
+
+    fn write_std140(&self, out: &mut [f32]) {
+        out.copy_from_slice(self);
+    }
+}
+
+// A following `f32` can fit in the space after a `vec3`, so the size is not
+// rounded up to the alignment.
+impl Std140 for Vec3 {
+    const ALIGN: usize = 16;
+    const SIZE: usize = 12;
+
+    fn write_std140(&self, out: &mut [f32]) {
+        out.copy_from_slice(&[self.x, self.y, self.z]);
+    }
+}
+
+impl Std140 for Vec4 {
+    const ALIGN: usize = 16;
+    const SIZE: usize = 16;
+
+    fn write_std140(&self, out: &mut [f32]) {
+        out.copy_from_slice(&[self.x, self.y, self.z, self.w]);
+    }
+}
+
+impl Std140 for [f32; 4] {
+    const ALIGN: usize = 16;
+    const SIZE: usize = 16;
+
+    fn write_std140(&self, out: &mut [f32]) {
+        out.copy_from_slice(self);
+    }
+}
+
+impl Std140 for Mat4 {
+    const ALIGN: usize = 16;
+    const SIZE: usize = 64;
+
+    fn write_std140(&self, out: &mut [f32]) {
+        out.copy_from_slice(&self.to_column_major());
+    }
+}
+
+// Arrays of other types would need padding between the elements, since each
+// element of a std140 array is aligned like a `vec4`.
+impl<const N: usize> Std140 for [Vec4; N] {
+    const ALIGN: usize = 16;
+    const SIZE: usize = 16 * N;
+
+    fn write_std140(&self, out: &mut [f32]) {
+        for (element, out) in self.iter().zip(out.chunks_exact_mut(4)) {
+            element.write_std140(out);
+        }
+    }
+}
+
+impl<const N: usize> Std140 for [Mat4; N] {
+    const ALIGN: usize = 16;
+    const SIZE: usize = 64 * N;
+
+    fn write_std140(&self, out: &mut [f32]) {
+        for (element, out) in self.iter().zip(out.chunks_exact_mut(16)) {
+            element.write_std140(out);
+        }
+    }
+}
+
+/// A struct whose members are laid out with the std140 rules. Use `uniform_block!`
+/// to implement this.
+pub trait UniformBlock {
+    /// In bytes. Always a multiple of 16, since a block's size is rounded up to
+    /// that of a `vec4`.
+    const SIZE: usize;
+    /// The length of the `vec4` array the block is declared as in the shader.
+    const VEC4_COUNT: usize = Self::SIZE / 16;
+
+    /// The members, packed into std140 layout. A `[f32; Self::SIZE / 4]`.
+    type Packed: AsRef<[f32]>;
+
+    fn pack(&self) -> Self::Packed;
+
+    /// A desc for a block declared in the shader as a `vec4` array named `name`.
+    /// `name` needs to be valid until the shader is made, so `cstr!` is the
+    /// easiest way to provide it.
+    fn desc(name: CharStar) -> ShaderUniformBlockDesc {
+        let mut desc = ShaderUniformBlockDesc {
+            size: Self::SIZE,
+            layout: sg::UniformLayout::Std140 as _,
+            ..<_>::default()
+        };
+        desc.uniforms[0].name = name;
+        desc.uniforms[0].type_ = sg::UniformType::Float4 as _;
+        desc.uniforms[0].array_count = Self::VEC4_COUNT as Int;
+        desc
+    }
+
+    /// Uploads the block to the given stage and slot of the current pipeline.
+    fn apply(&self, stage: ShaderStage, slot: Int) {
+        let packed = self.pack();
+        let packed = packed.as_ref();
+        debug_assert_eq!(core::mem::size_of_val(packed), Self::SIZE);
+
+        // SAFETY: The range points to `packed`, which lives until after the
+        // call, and has the size of `packed`. sokol checks that the size
+        // matches the desc of the current pipeline's shader.
+        unsafe {
+            sg::apply_uniforms(
+                stage as _,
+                slot,
+                &sg::Range {
+                    size: core::mem::size_of_val(packed),
+                    ptr: packed.as_ptr() as _,
+                },
+            );
+        }
+    }
+}
+
+/// The offsets of each member in a std140 block, given the alignment and size of
+/// each, along with the size of the whole block. All in bytes. Used by
+/// `uniform_block!`.
+#[doc(hidden)]
+pub const fn std140_layout<const N: usize>(
+    members: [(usize, usize); N]
+) -> ([usize; N], usize) {
+    const fn align_up(offset: usize, align: usize) -> usize {
+        offset.div_ceil(align) * align
+    }
+
+    let mut offsets = [0; N];
+    let mut end = 0;
+
+    let mut i = 0;
+    while i < N {
+        let (align, size) = members[i];
+        offsets[i] = align_up(end, align);
+        end = offsets[i] + size;
+        i += 1;
+    }
+
+    (offsets, align_up(end, 16))
+}
+
+/// Declares a struct, and implements `UniformBlock` for it. Each member's type
+/// must implement `Std140`.
+///
+/// ```ignore
+/// uniform_block!{
+///     #[derive(Clone, Copy)]
+///     pub struct VSParams {
+///         pub mvp: Mat4,
+///         pub colour: Vec3,
+///     }
+/// }
+/// ```
+#[macro_export]
+macro_rules! _uniform_block {
+    (
+        $(#[$attr: meta])*
+        $vis: vis struct $name: ident {
+            $(
+                $(#[$field_attr: meta])*
+                $field_vis: vis $field: ident : $type: ty
+            ),+
+            $(,)?
+        }
+    ) => {
+        $(#[$attr])*
+        $vis struct $name {
+            $(
+                $(#[$field_attr])*
+                $field_vis $field: $type
+            ),+
+        }
+
+        impl $name {
+            const STD140_LAYOUT: (
+                [usize; [$(stringify!($field)),+].len()],
+                usize
+            ) = $crate::uniforms::std140_layout([
+                $((
+                    <$type as $crate::uniforms::Std140>::ALIGN,
+                    <$type as $crate::uniforms::Std140>::SIZE,
+                )),+
+            ]);
+        }
+
+        impl $crate::uniforms::UniformBlock for $name {
+            const SIZE: usize = $name::STD140_LAYOUT.1;
+
+            type Packed = [f32; $name::STD140_LAYOUT.1 / 4];
+
+            fn pack(&self) -> Self::Packed {
+                let mut packed = [0.; $name::STD140_LAYOUT.1 / 4];
+                let mut offsets = $name::STD140_LAYOUT.0.iter();
+
+                $(
+                    let start = offsets.next().unwrap() / 4;
+                    let end = start + <$type as $crate::uniforms::Std140>::SIZE / 4;
+                    $crate::uniforms::Std140::write_std140(
+                        &self.$field,
+                        &mut packed[start..end]
+                    );
+                )+
+
+                packed
+            }
+        }
+    }
+}
+pub use _uniform_block as uniform_block;
+
+#[test]
+fn std140_layout_packs_members_as_glsl_does() {
+    let (offsets, size) = std140_layout([
+        (f32::ALIGN, f32::SIZE),
+        (Vec3::ALIGN, Vec3::SIZE),
+        (f32::ALIGN, f32::SIZE),
+        (<[f32; 2]>::ALIGN, <[f32; 2]>::SIZE),
+        (Mat4::ALIGN, Mat4::SIZE),
+    ]);
+
+    // The vec3 starts on the next vec4, and the float after it fits in the
+    // vec3's last slot.
+    assert_eq!(offsets, [0, 16, 28, 32, 48]);
+    assert_eq!(size, 112);
+}
+
+#[test]
+fn block_sizes_are_rounded_up_to_a_vec4() {
+    assert_eq!(std140_layout([(f32::ALIGN, f32::SIZE)]).1, 16);
+    assert_eq!(std140_layout([(Vec3::ALIGN, Vec3::SIZE)]).1, 16);
+}
+
+#[test]
+fn uniform_blocks_pack_members_at_their_offsets() {
+    use math::vec3::vec3;
+
+    uniform_block!{
+        struct Mixed {
+            a: f32,
+            b: Vec3,
+            c: f32,
+            d: [f32; 2],
+            e: Mat4,
+        }
+    }
+
+    let block = Mixed {
+        a: 1.,
+        b: vec3!(2., 3., 4.),
+        c: 5.,
+        d: [6., 7.],
+        e: Mat4::from_column_major([
+            8., 9., 10., 11.,
+            12., 13., 14., 15.,
+            16., 17., 18., 19.,
+            20., 21., 22., 23.,
+        ]),
+    };
+
+    assert_eq!(Mixed::SIZE, 112);
+    assert_eq!(Mixed::VEC4_COUNT, 7);
+
+    assert_eq!(
+        block.pack(),
+        [
+            1., 0., 0., 0.,
+            2., 3., 4., 5.,
+            6., 7., 0., 0.,
+            8., 9., 10., 11.,
+            12., 13., 14., 15.,
+            16., 17., 18., 19.,
+            20., 21., 22., 23.,
+        ]
+    );
+
+    let desc = Mixed::desc(sokol_bindings::cstr!("mixed"));
+    assert_eq!(desc.size, 112);
+    assert_eq!(desc.uniforms[0].array_count, 7);
+}
+
+#[test]
+fn arrays_are_packed_contiguously() {
+    use math::vec4::vec4;
+
+    uniform_block!{
+        struct WithArray {
+            a: f32,
+            colours: [Vec4; 2],
+        }
+    }
+
+    let block = WithArray {
+        a: 1.,
+        colours: [vec4!(2., 3., 4., 5.), vec4!(6., 7., 8., 9.)],
+    };
+
+    assert_eq!(WithArray::SIZE, 48);
+    assert_eq!(
+        block.pack(),
+        [
+            1., 0., 0., 0.,
+            2., 3., 4., 5.,
+            6., 7., 8., 9.,
+        ]
+    );
+}
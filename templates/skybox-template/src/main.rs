@@ -14,9 +14,9 @@ use sokol_extras::{
     debug::axes,
     images::white,
     shaders::textured_lit,
+    skybox,
 };
 
-mod skybox;
 mod decoded;
 
 #[derive(Default)]
@@ -103,13 +103,26 @@ const CUBE_INDICES: [u16; CUBE_INDEX_COUNT as usize] = [
     22, 21, 20,  23, 22, 20
 ];
 
+fn init_skybox(skybox: &mut skybox::State) {
+    let decoded = decoded::png_with_checkerboard_fallback(
+        include_bytes!("../../../assets/skybox.png"),
+    );
+
+    skybox::init(
+        skybox,
+        &skybox::CubePixels::from_cross(
+            skybox::Rgba8::new(decoded.w, decoded.h, &decoded.image_bytes)
+        ),
+    );
+}
+
 fn init(state: &mut State) {
     state.eye = vec3!(0., 0., 1.);
     state.center = vec3!();
 
     setup_default_context();
 
-    skybox::init(&mut state.skybox);
+    init_skybox(&mut state.skybox);
     axes::init(&mut state.axes);
 
     state.model.bind.vertex_buffers[0] = sg::make_immutable_vertex_buffer!(
@@ -155,7 +168,7 @@ fn frame(state: &mut State) {
 
     begin_default_pass(&pass_action, w, h);
 
-    skybox::draw(&state.skybox, view_proj);
+    skybox::draw(&state.skybox, view, proj);
 
     draw_model(&state.model, state.eye, view_proj);
 